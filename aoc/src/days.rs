@@ -0,0 +1,33 @@
+//! Glue between the runner and each day's `Solution`
+
+use common::{Part, Report, Solution};
+use std::error::Error;
+
+/// Every day that has a solution
+pub const DAYS: std::ops::RangeInclusive<u8> = 1..=10;
+
+/// Parse the input and solve the given parts of it
+fn run<S: Solution>(parts: &[Part], input: &str) -> Result<Vec<Report>, Box<dyn Error>> {
+    Ok(common::report::solve::<S>(input, parts)?)
+}
+
+/// Solve `parts` of `day` for the given puzzle input, with a report per part
+///
+/// Returns `None` when there is no solution for `day`.
+pub fn solve(day: u8, parts: &[Part], input: &str) -> Option<Result<Vec<Report>, Box<dyn Error>>> {
+    let reports = match day {
+        1 => run::<day1::Day1>(parts, input),
+        2 => run::<day2::Day2>(parts, input),
+        3 => run::<day3::Day3>(parts, input),
+        4 => run::<day4::Day4>(parts, input),
+        5 => run::<day5::Day5>(parts, input),
+        6 => run::<day6::Day6>(parts, input),
+        7 => run::<day7::Day7>(parts, input),
+        8 => run::<day8::Day8>(parts, input),
+        9 => run::<day9::Day9>(parts, input),
+        10 => run::<day10::Day10>(parts, input),
+        _ => return None,
+    };
+
+    Some(reports)
+}
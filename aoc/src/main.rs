@@ -0,0 +1,173 @@
+mod days;
+mod registry;
+mod verify;
+
+use clap::{Args, Parser, Subcommand};
+use common::{OutputArgs, OutputFormat, Part};
+use input::{HttpFetcher, InputProvider};
+use registry::{default_input, Registry};
+use std::error::Error;
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// Run the solution for any day of Advent of Code 2022
+#[derive(Parser, Debug)]
+#[command(
+    name = "aoc",
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    run: RunArgs,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Check every day against the known answers, and fail on any mismatch
+    Verify(VerifyArgs),
+}
+
+#[derive(Args, Debug)]
+struct RunArgs {
+    /// Day to run, 1 to 10
+    #[arg(long, required = true, value_parser = clap::value_parser!(u8).range(1..=10))]
+    day: Option<u8>,
+
+    /// Part to run, 1 or 2. Runs both when left out
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Puzzle input file, or `-` to read from stdin. Defaults to the cached input for the day,
+    /// which is fetched with the session in AOC_SESSION when it is not cached yet
+    #[arg(long)]
+    input: Option<String>,
+
+    #[command(flatten)]
+    inputs: InputArgs,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(Args, Debug)]
+struct VerifyArgs {
+    /// File with the known answers
+    #[arg(long, default_value = registry::DEFAULT_REGISTRY)]
+    registry: PathBuf,
+
+    #[command(flatten)]
+    inputs: InputArgs,
+}
+
+#[derive(Args, Debug)]
+struct InputArgs {
+    /// Directory puzzle inputs are cached in
+    #[arg(long, env = "AOC_CACHE_DIR", default_value = input::DEFAULT_CACHE_DIR)]
+    cache_dir: PathBuf,
+
+    /// Where to fetch puzzle inputs that are not cached
+    #[arg(long, env = "AOC_BASE_URL", default_value = input::DEFAULT_BASE_URL)]
+    base_url: String,
+}
+
+impl InputArgs {
+    fn provider(&self) -> InputProvider {
+        let session = std::env::var("AOC_SESSION").ok();
+        let fetcher = HttpFetcher::new(self.base_url.as_str(), session);
+        InputProvider::new(&self.cache_dir, fetcher)
+    }
+}
+
+fn read_input(args: &RunArgs, day: u8) -> Result<String, Box<dyn Error>> {
+    match args.input.as_deref() {
+        Some("-") => {
+            let mut buf = String::new();
+            std::io::stdin().read_to_string(&mut buf)?;
+            Ok(buf)
+        }
+        Some(path) => Ok(std::fs::read_to_string(path)?),
+        None => Ok(args.inputs.provider().get(day)?),
+    }
+}
+
+fn run(args: RunArgs) -> ExitCode {
+    // Required by clap whenever there is no subcommand
+    let day = args.day.expect("--day is required");
+    let input = match read_input(&args, day) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Could not read the input for day {}: {}", day, err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let parts = match args.part {
+        Some(1) => vec![Part::One],
+        Some(_) => vec![Part::Two],
+        None => Part::BOTH.to_vec(),
+    };
+
+    // The day was range checked by clap, so there is always a solution
+    let reports = match days::solve(day, &parts, &input).unwrap() {
+        Ok(reports) => reports,
+        Err(err) => {
+            eprintln!("Could not solve day {}: {}", day, err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match args.output.format {
+        OutputFormat::Json => println!("{}", common::report::to_json(&reports)),
+        OutputFormat::Text => {
+            for report in reports {
+                if let common::Answer::Lines(_) = report.answer {
+                    println!("Day {} part {}:\n{}", day, report.part, report.answer);
+                } else {
+                    println!("Day {} part {}: {}", day, report.part, report.answer);
+                }
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn verify(args: VerifyArgs) -> ExitCode {
+    let registry = match Registry::load(&args.registry) {
+        Ok(registry) => registry,
+        Err(err) => {
+            eprintln!("Could not load the known answers: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // The real inputs are fetched on a cache miss, any other input has to be in the cache already
+    let provider = args.inputs.provider();
+    let checks = verify::verify(&registry, |day, name| {
+        if name == default_input(day) {
+            Ok(provider.get(day)?)
+        } else {
+            Ok(std::fs::read_to_string(provider.cache_dir().join(name))?)
+        }
+    });
+
+    print!("{}", verify::report(&checks));
+    if verify::failed(&checks) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command {
+        Some(Command::Verify(args)) => verify(args),
+        None => run(cli.run),
+    }
+}
@@ -0,0 +1,169 @@
+//! Known answers for puzzle inputs, read from a TOML file like `answers.toml`
+
+use crate::days::DAYS;
+use common::Part;
+use serde::{Deserialize, Deserializer};
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+pub const DEFAULT_REGISTRY: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../answers.toml");
+
+/// Why the registry could not be loaded
+#[derive(Debug, Error)]
+pub enum RegistryError {
+    #[error("could not read {}: {source}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+    #[error("invalid registry: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("there is no solution for day {day}")]
+    UnknownDay { day: u8 },
+    #[error("day {day} has more than one entry for {input}")]
+    Duplicate { day: u8, input: String },
+}
+
+/// The expected answers for one day and input
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KnownAnswer {
+    pub day: u8,
+    input: Option<String>,
+    #[serde(default, deserialize_with = "answer")]
+    part_one: Option<String>,
+    #[serde(default, deserialize_with = "answer")]
+    part_two: Option<String>,
+}
+
+impl KnownAnswer {
+    /// The input file name in the cache directory
+    pub fn input(&self) -> String {
+        self.input
+            .clone()
+            .unwrap_or_else(|| default_input(self.day))
+    }
+
+    pub fn expected(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part_one.as_deref(),
+            Part::Two => self.part_two.as_deref(),
+        }
+    }
+}
+
+/// The cache file name of the real puzzle input for `day`
+pub fn default_input(day: u8) -> String {
+    format!("day{day}.txt")
+}
+
+/// Answers are written as numbers or strings, and compared as text
+fn answer<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Answer {
+        Number(i64),
+        Text(String),
+    }
+
+    Ok(Some(match Answer::deserialize(deserializer)? {
+        Answer::Number(n) => n.to_string(),
+        Answer::Text(text) => text,
+    }))
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Registry {
+    #[serde(default, rename = "answer")]
+    answers: Vec<KnownAnswer>,
+}
+
+impl Registry {
+    pub fn load(path: &Path) -> Result<Self, RegistryError> {
+        let text = fs::read_to_string(path).map_err(|source| RegistryError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        text.parse()
+    }
+
+    /// Every entry recorded for `day`, in file order
+    pub fn for_day(&self, day: u8) -> impl Iterator<Item = &KnownAnswer> {
+        self.answers.iter().filter(move |answer| answer.day == day)
+    }
+}
+
+impl std::str::FromStr for Registry {
+    type Err = RegistryError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let registry: Registry = toml::from_str(text)?;
+        let mut seen = HashSet::new();
+        for answer in &registry.answers {
+            if !DAYS.contains(&answer.day) {
+                return Err(RegistryError::UnknownDay { day: answer.day });
+            }
+            if !seen.insert((answer.day, answer.input())) {
+                return Err(RegistryError::Duplicate {
+                    day: answer.day,
+                    input: answer.input(),
+                });
+            }
+        }
+        Ok(registry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_and_multi_line_answers() {
+        let registry: Registry = r#"
+[[answer]]
+day = 1
+part_one = 24000
+
+[[answer]]
+day = 10
+input = "sample.txt"
+part_two = """
+##..
+..##
+"""
+"#
+        .parse()
+        .unwrap();
+
+        let day1 = registry.for_day(1).next().unwrap();
+        assert_eq!("day1.txt", day1.input());
+        assert_eq!(Some("24000"), day1.expected(Part::One));
+        assert_eq!(None, day1.expected(Part::Two));
+
+        let day10 = registry.for_day(10).next().unwrap();
+        assert_eq!("sample.txt", day10.input());
+        assert_eq!(Some("##..\n..##\n"), day10.expected(Part::Two));
+    }
+
+    #[test]
+    fn rejects_unknown_days_and_duplicates() {
+        assert!(matches!(
+            "[[answer]]\nday = 11".parse::<Registry>(),
+            Err(RegistryError::UnknownDay { day: 11 })
+        ));
+        assert!(matches!(
+            "[[answer]]\nday = 2\n[[answer]]\nday = 2\ninput = \"day2.txt\"".parse::<Registry>(),
+            Err(RegistryError::Duplicate { day: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn workspace_registry_is_valid() {
+        let registry = Registry::load(Path::new(DEFAULT_REGISTRY)).unwrap();
+        for day in DAYS {
+            assert!(registry.for_day(day).next().is_some(), "day {day}");
+        }
+    }
+}
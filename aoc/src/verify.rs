@@ -0,0 +1,274 @@
+//! Check every day's answers against the known answers in the registry
+
+use crate::days::{self, DAYS};
+use crate::registry::{default_input, Registry};
+use common::Part;
+use std::error::Error;
+use std::fmt::Write;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// There is no known answer to compare with
+    Missing,
+    /// The input could not be read or parsed
+    Error,
+}
+
+impl Status {
+    fn label(self) -> &'static str {
+        match self {
+            Status::Pass => "PASS",
+            Status::Fail => "FAIL",
+            Status::Missing => "MISSING",
+            Status::Error => "ERROR",
+        }
+    }
+}
+
+/// The outcome of solving one part of one input
+#[derive(Debug)]
+pub struct Check {
+    pub day: u8,
+    pub input: String,
+    pub part: Part,
+    pub status: Status,
+    pub expected: Option<String>,
+    /// The answer, or why there is none
+    pub actual: Result<String, String>,
+}
+
+/// Answers are compared without trailing whitespace, so multi-line answers
+/// match however the registry or the solution ends its lines
+fn normalize(answer: &str) -> String {
+    let lines: Vec<&str> = answer.lines().map(str::trim_end).collect();
+    lines.join("\n").trim_end().to_string()
+}
+
+/// Solve every recorded input, and the default input of days without any,
+/// reading inputs by day and file name with `read`
+pub fn verify<R>(registry: &Registry, read: R) -> Vec<Check>
+where
+    R: Fn(u8, &str) -> Result<String, Box<dyn Error>>,
+{
+    let mut checks = Vec::new();
+    for day in DAYS {
+        let mut entries: Vec<(String, [Option<&str>; 2])> = registry
+            .for_day(day)
+            .map(|known| {
+                let expected = Part::BOTH.map(|part| known.expected(part));
+                (known.input(), expected)
+            })
+            .collect();
+        if entries.is_empty() {
+            entries.push((default_input(day), [None, None]));
+        }
+
+        for (input_name, expected) in entries {
+            let answers: Result<Vec<String>, String> = match read(day, &input_name) {
+                Ok(input) => days::solve(day, &Part::BOTH, &input)
+                    .expect("every day in DAYS has a solution")
+                    .map(|reports| {
+                        reports
+                            .iter()
+                            .map(|report| report.answer.to_string())
+                            .collect()
+                    })
+                    .map_err(|err| format!("could not solve the input: {err}")),
+                Err(err) => Err(format!("could not read the input: {err}")),
+            };
+            for (idx, (part, expected)) in Part::BOTH.into_iter().zip(expected).enumerate() {
+                let actual = answers
+                    .as_ref()
+                    .map(|answers| answers[idx].clone())
+                    .map_err(Clone::clone);
+                let status = match (expected, &actual) {
+                    (None, _) => Status::Missing,
+                    (Some(_), Err(_)) => Status::Error,
+                    (Some(expected), Ok(actual)) if normalize(expected) == normalize(actual) => {
+                        Status::Pass
+                    }
+                    (Some(_), Ok(_)) => Status::Fail,
+                };
+                checks.push(Check {
+                    day,
+                    input: input_name.clone(),
+                    part,
+                    status,
+                    expected: expected.map(normalize),
+                    actual: actual.map(|answer| normalize(&answer)),
+                });
+            }
+        }
+    }
+    checks
+}
+
+/// Whether the checks should fail the run; days without known answers do not
+pub fn failed(checks: &[Check]) -> bool {
+    checks
+        .iter()
+        .any(|check| matches!(check.status, Status::Fail | Status::Error))
+}
+
+/// Multi-line answers do not fit in a table cell, so only their size is shown there
+fn cell(answer: &str) -> String {
+    match answer.lines().count() {
+        0 | 1 => answer.to_string(),
+        lines => format!("<{lines} lines>"),
+    }
+}
+
+/// A table with a row per check, followed by the details of every failure
+pub fn report(checks: &[Check]) -> String {
+    let rows: Vec<[String; 5]> = checks
+        .iter()
+        .map(|check| {
+            let answer = match &check.actual {
+                Ok(answer) => cell(answer),
+                Err(_) => String::from("-"),
+            };
+            [
+                check.day.to_string(),
+                check.input.clone(),
+                check.part.number().to_string(),
+                check.status.label().to_string(),
+                answer,
+            ]
+        })
+        .collect();
+    let header = ["Day", "Input", "Part", "Status", "Answer"].map(String::from);
+
+    let mut widths = header.clone().map(|title| title.len());
+    for row in &rows {
+        for (width, value) in widths.iter_mut().zip(row) {
+            *width = (*width).max(value.len());
+        }
+    }
+
+    let mut out = String::new();
+    for row in std::iter::once(&header).chain(&rows) {
+        let line: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(value, width)| format!("{value:<width$}"))
+            .collect();
+        writeln!(out, "{}", line.join("  ").trim_end()).unwrap();
+    }
+
+    for check in checks {
+        let heading = format!(
+            "Day {} part {} ({})",
+            check.day,
+            check.part.number(),
+            check.input
+        );
+        match (check.status, &check.expected, &check.actual) {
+            (Status::Fail, Some(expected), Ok(actual)) => {
+                write!(
+                    out,
+                    "\n{heading} does not match\nexpected:\n{expected}\nactual:\n{actual}\n"
+                )
+                .unwrap();
+            }
+            (Status::Error, _, Err(err)) => {
+                write!(out, "\n{heading}: {err}\n").unwrap();
+            }
+            _ => {}
+        }
+    }
+
+    let count = |status| checks.iter().filter(|c| c.status == status).count();
+    write!(
+        out,
+        "\n{} passed, {} failed, {} missing, {} errors\n",
+        count(Status::Pass),
+        count(Status::Fail),
+        count(Status::Missing),
+        count(Status::Error)
+    )
+    .unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY6: &str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n";
+
+    fn registry() -> Registry {
+        r#"
+[[answer]]
+day = 6
+input = "sample.txt"
+part_one = 7
+part_two = 19
+
+[[answer]]
+day = 6
+input = "wrong.txt"
+part_one = 7
+part_two = 21
+
+[[answer]]
+day = 7
+part_one = 95437
+"#
+        .parse()
+        .unwrap()
+    }
+
+    fn read(day: u8, name: &str) -> Result<String, Box<dyn Error>> {
+        match (day, name) {
+            (6, _) => Ok(String::from(DAY6)),
+            _ => Err(format!("{name} is not cached").into()),
+        }
+    }
+
+    fn status(checks: &[Check], day: u8, input: &str, part: Part) -> Status {
+        checks
+            .iter()
+            .find(|c| c.day == day && c.input == input && c.part == part)
+            .unwrap()
+            .status
+    }
+
+    #[test]
+    fn compares_every_recorded_answer() {
+        let checks = verify(&registry(), read);
+
+        assert_eq!(Status::Pass, status(&checks, 6, "sample.txt", Part::One));
+        assert_eq!(Status::Pass, status(&checks, 6, "sample.txt", Part::Two));
+        assert_eq!(Status::Pass, status(&checks, 6, "wrong.txt", Part::One));
+        assert_eq!(Status::Fail, status(&checks, 6, "wrong.txt", Part::Two));
+        assert_eq!(Status::Error, status(&checks, 7, "day7.txt", Part::One));
+        assert_eq!(Status::Missing, status(&checks, 7, "day7.txt", Part::Two));
+        assert_eq!(Status::Missing, status(&checks, 1, "day1.txt", Part::One));
+        assert!(failed(&checks));
+    }
+
+    #[test]
+    fn missing_answers_do_not_fail() {
+        let checks = verify(&Registry::default(), read);
+
+        assert!(checks.iter().all(|c| c.status == Status::Missing));
+        assert!(!failed(&checks));
+    }
+
+    #[test]
+    fn multi_line_answers_ignore_trailing_whitespace() {
+        assert_eq!(normalize("#..\n.#.  \n"), normalize("#..\n.#."));
+        assert_eq!("<2 lines>", cell("#..\n.#."));
+    }
+
+    #[test]
+    fn report_lists_failures() {
+        let report = report(&verify(&registry(), read));
+
+        assert!(report
+            .contains("Day 6 part 2 (wrong.txt) does not match\nexpected:\n21\nactual:\n19\n"));
+        assert!(report.contains("3 passed, 1 failed, 17 missing, 1 errors"));
+    }
+}
@@ -0,0 +1,63 @@
+//! Times parsing and each part separately for every day, on the real input
+//! and on inputs scaled up from it
+//!
+//! Benchmarks are named `day<N>/<phase>/<input>`, so a single day or phase can
+//! be picked with a filter, like `cargo bench -p bench -- day8/part_one`.
+
+use common::Solution;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+use std::time::Duration;
+
+/// How much bigger than the real input the synthetic inputs are
+const SCALES: [usize; 2] = [4, 16];
+
+/// Scaled inputs can make the slower days take seconds per iteration, so
+/// those are capped at this many samples
+const SCALED_SAMPLES: usize = 10;
+
+fn bench_day<S: Solution>(c: &mut Criterion) {
+    let real = input::load(S::DAY).expect("the real input is available");
+    let mut inputs = vec![(String::from("real"), real.clone())];
+    for factor in SCALES {
+        let scaled = bench::scaled(S::DAY, &real, factor).expect("every day can be scaled");
+        inputs.push((format!("x{factor}"), scaled));
+    }
+
+    let mut group = c.benchmark_group(format!("day{}", S::DAY));
+    for (name, input) in &inputs {
+        if name != "real" {
+            group
+                .sample_size(SCALED_SAMPLES)
+                .warm_up_time(Duration::from_millis(500));
+        }
+        let parsed = S::parse(input).expect("benchmark inputs are valid");
+
+        group.bench_with_input(BenchmarkId::new("parse", name), input, |b, input| {
+            b.iter(|| S::parse(black_box(input)).is_ok())
+        });
+        group.bench_with_input(BenchmarkId::new("part_one", name), &parsed, |b, parsed| {
+            b.iter(|| S::part_one(black_box(parsed)))
+        });
+        group.bench_with_input(BenchmarkId::new("part_two", name), &parsed, |b, parsed| {
+            b.iter(|| S::part_two(black_box(parsed)))
+        });
+    }
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<day1::Day1>(c);
+    bench_day::<day2::Day2>(c);
+    bench_day::<day3::Day3>(c);
+    bench_day::<day4::Day4>(c);
+    bench_day::<day5::Day5>(c);
+    bench_day::<day6::Day6>(c);
+    bench_day::<day7::Day7>(c);
+    bench_day::<day8::Day8>(c);
+    bench_day::<day9::Day9>(c);
+    bench_day::<day10::Day10>(c);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
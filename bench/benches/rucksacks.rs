@@ -0,0 +1,59 @@
+//! Day 3 on large generated inputs, next to the item by item search it replaced
+//!
+//! Both engines read the same rucksacks and give the same answers. The lists
+//! engine is day 3 as it was before, kept in the `lists` module. Pick one size
+//! with a filter, like `cargo bench -p bench --bench rucksacks -- 100000`.
+
+#[path = "rucksacks/lists.rs"]
+mod lists;
+
+use common::{parse_lines, Solution};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use day3::generator::generate;
+use day3::Day3;
+use std::hint::black_box;
+use std::time::Duration;
+
+/// Rucksacks in each generated input
+const SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+
+/// Both answers with the item by item search day 3 used before
+fn lists(input: &str) -> (usize, usize) {
+    let sacks: Vec<lists::Rucksack> = parse_lines(input).expect("generated inputs are valid");
+    (
+        lists::sum_common_priorities(&sacks),
+        lists::sum_badge_priorities(&sacks),
+    )
+}
+
+/// Both answers with the item sets of day 3
+fn bitsets(input: &str) -> (usize, usize) {
+    let sacks = Day3::parse(input).expect("generated inputs are valid");
+    let answer = |answer: Result<usize, _>| answer.expect("generated inputs make whole groups");
+    (
+        answer(Day3::part_one(&sacks)),
+        answer(Day3::part_two(&sacks)),
+    )
+}
+
+fn rucksacks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day3_engines");
+    group
+        .sample_size(20)
+        .warm_up_time(Duration::from_millis(500));
+    for size in SIZES {
+        let input = generate(size as u64, size).input;
+        assert_eq!(lists(&input), bitsets(&input), "the engines disagree");
+
+        group.bench_with_input(BenchmarkId::new("lists", size), &input, |b, input| {
+            b.iter(|| lists(black_box(input)))
+        });
+        group.bench_with_input(BenchmarkId::new("bitsets", size), &input, |b, input| {
+            b.iter(|| bitsets(black_box(input)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, rucksacks);
+criterion_main!(benches);
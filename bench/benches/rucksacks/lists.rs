@@ -0,0 +1,194 @@
+//! Day 3 as it was before item sets, kept to benchmark them against
+//!
+//! Items are one-letter `&str`s split off the line, compartments are searched
+//! item by item and group badges are found with `array_tool`.
+
+use array_tool::vec::Intersect;
+use common::{column_of, Located, Location};
+use day3::ParseError;
+
+/// A rucksack, with the first half of its items in one compartment and the rest in the other
+#[derive(Debug, PartialEq, Clone)]
+pub struct Rucksack<'a> {
+    cpt1: Compartment<'a>,
+    cpt2: Compartment<'a>,
+}
+
+impl<'a> Rucksack<'a> {
+    /// The item types found in both compartments
+    pub fn find_common_item(&self) -> Vec<&SupplyItem<'a>> {
+        let mut common: Vec<&SupplyItem<'a>> = self
+            .cpt1
+            .items
+            .iter()
+            .filter_map(|itm| self.cpt2.items.iter().find(|&i| i == itm))
+            .collect();
+        common.dedup();
+        common
+    }
+
+    /// Every item in the rucksack, the first compartment first
+    pub fn merge_compartments(&self) -> Vec<&SupplyItem<'a>> {
+        self.cpt1
+            .items
+            .iter()
+            .chain(self.cpt2.items.iter())
+            .collect()
+    }
+}
+impl<'a> TryFrom<&'a str> for Rucksack<'a> {
+    type Error = ParseError;
+
+    fn try_from(from: &'a str) -> Result<Rucksack<'a>, ParseError> {
+        // Read every item first, then hand the second half over to the other compartment
+        let mut cpt1 = Compartment::try_from(from)?;
+        if cpt1.items.len() % 2 != 0 {
+            return Err(ParseError::OddItemCount {
+                location: Location::column(1),
+                text: from.to_string(),
+            });
+        }
+        let cpt2 = Compartment {
+            items: cpt1.items.split_off(cpt1.items.len() / 2),
+        };
+        Ok(Rucksack { cpt1, cpt2 })
+    }
+}
+
+/// One half of a rucksack
+#[derive(Debug, PartialEq, Clone)]
+pub struct Compartment<'a> {
+    items: Vec<SupplyItem<'a>>,
+}
+
+impl<'a> TryFrom<&'a str> for Compartment<'a> {
+    type Error = ParseError;
+
+    fn try_from(from: &'a str) -> Result<Compartment<'a>, ParseError> {
+        Ok(Compartment {
+            items: from
+                .split("")
+                .filter(|c| c != &"")
+                .map(|c| SupplyItem::try_from(c).map_err(|err| err.shifted(column_of(from, c) - 1)))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+/// An item, which is a single letter
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct SupplyItem<'a> {
+    itemtype: Itemtype<'a>,
+    priority: Priority,
+}
+
+const PRIOLIST: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+impl<'a> TryFrom<&'a str> for SupplyItem<'a> {
+    type Error = ParseError;
+
+    fn try_from(from: &'a str) -> Result<SupplyItem<'a>, ParseError> {
+        let prio = match PRIOLIST.find(from) {
+            Some(prio) if from.len() == 1 => prio,
+            _ => {
+                return Err(ParseError::InvalidItem {
+                    location: Location::column(1),
+                    text: from.to_string(),
+                })
+            }
+        };
+        Ok(SupplyItem {
+            itemtype: Itemtype(from),
+            priority: Priority(prio + 1),
+        })
+    }
+}
+
+impl<'a> SupplyItem<'a> {
+    /// 1 to 26 for `a` to `z`, 27 to 52 for `A` to `Z`
+    pub fn priority(&self) -> usize {
+        *self.priority
+    }
+}
+
+/// The letter of an item
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Itemtype<'a>(&'a str);
+
+/// How important it is to rearrange an item
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Priority(usize);
+
+impl std::ops::Deref for Priority {
+    type Target = usize;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// A group of three bags
+#[derive(Debug, PartialEq)]
+pub struct Group<'a> {
+    bags: Vec<&'a Rucksack<'a>>,
+}
+
+impl<'a> Group<'a> {
+    /// A group carrying `bags`
+    pub fn new(bags: Vec<&'a Rucksack<'a>>) -> Self {
+        Group { bags }
+    }
+
+    /// The items of every bag, compartments merged
+    pub fn get_bag_contents_merged(&self) -> Vec<Vec<&SupplyItem<'a>>> {
+        self.bags
+            .clone()
+            .into_iter()
+            .map(|bag| bag.merge_compartments())
+            .collect()
+    }
+
+    /// The item types every bag in the group has, which is the badge
+    pub fn get_common_item(&self) -> Vec<&SupplyItem<'a>> {
+        let merged = self.get_bag_contents_merged();
+        let mut intersect = Vec::new();
+        for bag in merged {
+            if intersect.is_empty() {
+                intersect = bag;
+            } else {
+                intersect = intersect.intersect(bag);
+            }
+        }
+        intersect
+    }
+}
+
+/// Find the item type that appears in both compartments of each rucksack and sum their priorities
+pub fn sum_common_priorities(sacks: &[Rucksack]) -> usize {
+    sacks
+        .iter()
+        .map(|sack| {
+            sack.find_common_item()
+                .iter()
+                .map(|item| item.priority())
+                .sum::<usize>()
+        })
+        .sum()
+}
+
+/// Find the badge shared by every group of three rucksacks and sum their priorities
+pub fn sum_badge_priorities(sacks: &[Rucksack]) -> usize {
+    let groups: Vec<Group> = sacks
+        .chunks(3)
+        .map(|sacks| Group::new(vec![&sacks[0], &sacks[1], &sacks[2]]))
+        .collect();
+
+    groups
+        .iter()
+        .map(|group| {
+            group
+                .get_common_item()
+                .iter()
+                .map(|item| item.priority())
+                .sum::<usize>()
+        })
+        .sum()
+}
@@ -0,0 +1,226 @@
+//! Synthetic inputs for benchmarking, made by scaling up a real puzzle input
+//!
+//! Run the benchmarks with `cargo bench -p bench`. To compare before and after
+//! a change, save a baseline first and compare against it afterwards:
+//!
+//! ```text
+//! cargo bench -p bench -- --save-baseline before
+//! cargo bench -p bench -- --baseline before
+//! ```
+//!
+//! Every input is still a valid input for its day, so the scaled benchmarks
+//! run the same code paths as the real ones, just with more work to do.
+
+/// `input` made `factor` times bigger, keeping it valid for `day`
+///
+/// Returns `None` when there is no solution for `day`. A factor of 1 returns
+/// the input unchanged.
+pub fn scaled(day: u8, input: &str, factor: usize) -> Option<String> {
+    assert!(factor > 0, "cannot scale an input by zero");
+    let scaled = match day {
+        // Separate the copies with a blank line so elves are not merged
+        1 => vec![input.trim_end(); factor].join("\n\n") + "\n",
+        2 | 3 | 4 | 9 | 10 => repeat_lines(input, factor),
+        5 => day5(input, factor),
+        6 => day6(input, factor),
+        7 => day7(input, factor),
+        8 => day8(input, factor),
+        _ => return None,
+    };
+    Some(scaled)
+}
+
+/// Repeat the whole input, which keeps the groups of three in day 3 intact
+fn repeat_lines(input: &str, factor: usize) -> String {
+    let mut lines = input.trim_end().to_string();
+    lines.push('\n');
+    lines.repeat(factor)
+}
+
+/// Follow the moves with the same moves undone, so the stacks are never
+/// emptied and the answers stay the same
+fn day5(input: &str, factor: usize) -> String {
+    let (dock, moves) = input
+        .split_once("\n\n")
+        .expect("day 5 input has the dock and the moves separated by a blank line");
+    let moves: Vec<&str> = moves.lines().filter(|line| !line.is_empty()).collect();
+    let undo: Vec<String> = moves
+        .iter()
+        .rev()
+        .map(
+            |line| match line.split_whitespace().collect::<Vec<_>>()[..] {
+                ["move", count, "from", from, "to", to] => {
+                    format!("move {count} from {to} to {from}")
+                }
+                _ => panic!("`{line}` is not a move"),
+            },
+        )
+        .collect();
+
+    let mut out = format!("{dock}\n\n");
+    for _ in 1..factor {
+        for line in &moves {
+            out.push_str(line);
+            out.push('\n');
+        }
+        for line in &undo {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    for line in &moves {
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Put a stretch of noise without any marker in front of the signal
+///
+/// The noise repeats the first character of the signal, so no window that
+/// starts in it holds distinct characters and the answers move by its length.
+fn day6(input: &str, factor: usize) -> String {
+    let signal = input.trim_end();
+    let first = signal.chars().next().expect("day 6 input is not empty");
+    let noise = first.to_string().repeat((factor - 1) * signal.len());
+    format!("{noise}{signal}\n")
+}
+
+/// Copy the whole file system into new directories under the root
+///
+/// The disk is a fixed 70000000 in size, so file sizes are divided by the
+/// factor to keep the used space about the same as in the original input.
+fn day7(input: &str, factor: usize) -> String {
+    let lines: Vec<String> = input
+        .lines()
+        .map(|line| match line.split_once(' ') {
+            Some((size, name)) if size.bytes().all(|b| b.is_ascii_digit()) => {
+                let size: usize = size.parse().expect("file sizes fit in a usize");
+                format!("{} {name}", size / factor)
+            }
+            _ => line.to_string(),
+        })
+        .collect();
+    assert_eq!(
+        ["$ cd /", "$ ls"],
+        lines[..2],
+        "day 7 input starts by listing the root"
+    );
+    assert!(
+        !lines[1..].iter().any(|line| line == "$ cd /"),
+        "day 7 input only moves to the root at the start"
+    );
+
+    let mut out: Vec<String> = lines[..2].to_vec();
+    out.extend((1..factor).map(|copy| format!("dir {}", copy_name(copy))));
+    out.extend_from_slice(&lines[2..]);
+    for copy in 1..factor {
+        out.push(String::from("$ cd /"));
+        out.push(format!("$ cd {}", copy_name(copy)));
+        out.extend_from_slice(&lines[1..]);
+    }
+    out.join("\n") + "\n"
+}
+
+/// Directory names can only hold letters, so copies are numbered in base 26
+fn copy_name(mut copy: usize) -> String {
+    let mut name = Vec::new();
+    while copy > 0 {
+        name.push(b'a' + (copy % 26) as u8);
+        copy /= 26;
+    }
+    name.reverse();
+    format!("copy{}", String::from_utf8(name).unwrap())
+}
+
+/// Tile the forest `factor` times in both directions
+fn day8(input: &str, factor: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..factor {
+        for row in input.lines().filter(|line| !line.is_empty()) {
+            out.push_str(&row.repeat(factor));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    fn answers<S: Solution>(input: &str) -> (String, String) {
+        let parsed = S::parse(input).unwrap();
+        (
+            S::part_one(&parsed).unwrap().to_string(),
+            S::part_two(&parsed).unwrap().to_string(),
+        )
+    }
+
+    #[test]
+    fn factor_one_keeps_the_answers() {
+        for day in 1..=10 {
+            let input = input::load(day).unwrap();
+            let scaled = scaled(day, &input, 1).unwrap();
+            let same = match day {
+                1 => answers::<day1::Day1>(&input) == answers::<day1::Day1>(&scaled),
+                2 => answers::<day2::Day2>(&input) == answers::<day2::Day2>(&scaled),
+                3 => answers::<day3::Day3>(&input) == answers::<day3::Day3>(&scaled),
+                4 => answers::<day4::Day4>(&input) == answers::<day4::Day4>(&scaled),
+                5 => answers::<day5::Day5>(&input) == answers::<day5::Day5>(&scaled),
+                6 => answers::<day6::Day6>(&input) == answers::<day6::Day6>(&scaled),
+                7 => answers::<day7::Day7>(&input) == answers::<day7::Day7>(&scaled),
+                8 => answers::<day8::Day8>(&input) == answers::<day8::Day8>(&scaled),
+                9 => answers::<day9::Day9>(&input) == answers::<day9::Day9>(&scaled),
+                _ => answers::<day10::Day10>(&input) == answers::<day10::Day10>(&scaled),
+            };
+            assert!(same, "day {day}");
+        }
+    }
+
+    #[test]
+    fn scaled_day5_keeps_the_answers() {
+        let input = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2\n";
+        let scaled = scaled(5, input, 3).unwrap();
+
+        assert_eq!(4 * 5 + 5, scaled.lines().count());
+        assert_eq!(
+            (String::from("CMZ"), String::from("MCD")),
+            answers::<day5::Day5>(&scaled)
+        );
+    }
+
+    #[test]
+    fn scaled_day6_moves_the_markers() {
+        let scaled = scaled(6, "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n", 2).unwrap();
+
+        assert_eq!(
+            (String::from("37"), String::from("49")),
+            answers::<day6::Day6>(&scaled)
+        );
+    }
+
+    #[test]
+    fn scaled_day7_copies_the_file_system() {
+        let input = "$ cd /\n$ ls\ndir a\n14848514 b.txt\n8504156 c.dat\ndir d\n$ cd a\n$ ls\ndir e\n29116 f\n2557 g\n62596 h.lst\n$ cd e\n$ ls\n584 i\n$ cd ..\n$ cd ..\n$ cd d\n$ ls\n4060174 j\n8033020 d.log\n5626152 d.ext\n7214296 k\n";
+        let scaled = scaled(7, input, 3).unwrap();
+        let dirs = |input: &str| input.lines().filter(|l| l.starts_with("dir ")).count();
+
+        assert_eq!(3 * dirs(input) + 2, dirs(&scaled));
+        assert!(scaled.contains("\n4949504 b.txt\n"));
+        assert_eq!(
+            (String::from("95430"), String::from("16127051")),
+            answers::<day7::Day7>(&scaled)
+        );
+    }
+
+    #[test]
+    fn scaled_day8_tiles_the_forest() {
+        let scaled = scaled(8, "30373\n25512\n65332\n33549\n35390\n", 2).unwrap();
+
+        assert_eq!(10, scaled.lines().count());
+        assert!(scaled.lines().all(|row| row.len() == 10));
+        day8::Day8::parse(&scaled).unwrap();
+    }
+}
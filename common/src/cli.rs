@@ -0,0 +1,54 @@
+use crate::report::{self, Part};
+use crate::Solution;
+use clap::{Args, Parser, ValueEnum};
+
+/// How a binary writes its answers
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Sentences meant for people
+    #[default]
+    Text,
+    /// A JSON array with a report per part
+    Json,
+}
+
+/// Options every binary takes for its output
+#[derive(Args, Debug, Clone)]
+pub struct OutputArgs {
+    /// Format to write the answers in
+    #[arg(long, value_enum, default_value_t)]
+    pub format: OutputFormat,
+}
+
+/// Command line of a day's binary
+#[derive(Parser, Debug)]
+pub struct DayArgs {
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+impl DayArgs {
+    /// Read the options from the command line, exiting with usage on a bad one
+    pub fn from_env() -> Self {
+        DayArgs::parse()
+    }
+}
+
+/// Print the answers for `input` in `format`
+///
+/// Text output is left to `text`, which gets the parsed input and words the
+/// answers the way the day's binary always has.
+pub fn print_answers<S: Solution>(
+    format: OutputFormat,
+    input: &str,
+    text: impl FnOnce(&S::Parsed<'_>) -> Result<(), S::Error>,
+) -> Result<(), S::Error> {
+    match format {
+        OutputFormat::Text => text(&S::parse(input)?)?,
+        OutputFormat::Json => {
+            let reports = report::solve::<S>(input, &Part::BOTH)?;
+            println!("{}", report::to_json(&reports));
+        }
+    }
+    Ok(())
+}
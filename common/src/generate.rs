@@ -0,0 +1,61 @@
+use crate::report::{self, Answer, Part};
+use crate::Solution;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// The random number generator inputs are generated with
+///
+/// The same seed gives the same input on every platform and every run.
+pub type Rng = ChaCha8Rng;
+
+/// A generator seeded with `seed`, for inputs that come out the same every time
+pub fn rng(seed: u64) -> Rng {
+    Rng::seed_from_u64(seed)
+}
+
+/// A generated puzzle input with the answers it must produce
+///
+/// Generators work the answers out from how they built the input, not by
+/// running the solution, so the two can be checked against each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    /// The puzzle input
+    pub input: String,
+    /// What the first part must come to
+    pub part_one: Answer,
+    /// What the second part must come to
+    pub part_two: Answer,
+}
+
+impl Generated {
+    /// An input with the answers it was built to have
+    pub fn new(
+        input: String,
+        part_one: impl std::fmt::Display,
+        part_two: impl std::fmt::Display,
+    ) -> Self {
+        Generated {
+            input,
+            part_one: Answer::new(part_one),
+            part_two: Answer::new(part_two),
+        }
+    }
+
+    /// The answers `S` gives for the generated input
+    pub fn solve<S: Solution>(&self) -> Result<(Answer, Answer), S::Error> {
+        let mut reports = report::solve::<S>(&self.input, &Part::BOTH)?.into_iter();
+        let mut answer = || reports.next().expect("a report per part").answer;
+        Ok((answer(), answer()))
+    }
+
+    /// Both answers the input was built to have
+    pub fn answers(&self) -> (Answer, Answer) {
+        (self.part_one.clone(), self.part_two.clone())
+    }
+}
+
+/// A solution that can make up valid inputs of its own
+pub trait Generate: Solution {
+    /// An input of about `size` items, what an item is depends on the day
+    fn generate(seed: u64, size: usize) -> Generated;
+}
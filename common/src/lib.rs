@@ -0,0 +1,38 @@
+//! Pieces shared by every day's puzzle crate
+
+mod cli;
+pub mod generate;
+mod location;
+pub mod report;
+
+pub use cli::{print_answers, DayArgs, OutputArgs, OutputFormat};
+pub use generate::{Generate, Generated};
+pub use location::{column_of, parse_lines, Located, Location};
+pub use report::{Answer, Part, Report};
+
+use std::fmt::Display;
+
+/// A puzzle solution, split into a parse step and one step per part
+///
+/// The parsed input may borrow from the raw puzzle input, and both parts are
+/// solved from the same parsed value.
+pub trait Solution {
+    /// Day of the advent calendar this solves
+    const DAY: u8;
+
+    /// The puzzle input after parsing
+    type Parsed<'a>;
+    /// Answer to the first part of the puzzle
+    type PartOne: Display;
+    /// Answer to the second part of the puzzle
+    type PartTwo: Display;
+    /// Why the puzzle input could not be parsed, or a part not solved
+    type Error: std::error::Error + 'static;
+
+    /// Read the raw puzzle input
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, Self::Error>;
+    /// Solve the first part of the puzzle
+    fn part_one(parsed: &Self::Parsed<'_>) -> Result<Self::PartOne, Self::Error>;
+    /// Solve the second part of the puzzle
+    fn part_two(parsed: &Self::Parsed<'_>) -> Result<Self::PartTwo, Self::Error>;
+}
@@ -0,0 +1,119 @@
+use std::fmt;
+
+/// A position in the puzzle input, both line and column start at 1
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    pub fn new(line: usize, column: usize) -> Self {
+        Location { line, column }
+    }
+
+    /// A column on the first line, for errors found while parsing a single line
+    pub fn column(column: usize) -> Self {
+        Location { line: 1, column }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// An error that knows where in the input it happened
+///
+/// Parsers for a single line or token report positions relative to what they
+/// were given; callers that know more use `on_line` and `shifted` to turn that
+/// into a position in the whole input.
+pub trait Located: Sized {
+    fn location_mut(&mut self) -> &mut Location;
+
+    /// Move the error to `line` of the input
+    fn on_line(mut self, line: usize) -> Self {
+        self.location_mut().line = line;
+        self
+    }
+
+    /// Move the error `columns` further to the right
+    fn shifted(mut self, columns: usize) -> Self {
+        self.location_mut().column += columns;
+        self
+    }
+}
+
+/// Column of `token` within `line`, when `token` is a slice of `line`
+pub fn column_of(line: &str, token: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let offset = (token.as_ptr() as usize).wrapping_sub(start);
+    if offset
+        .checked_add(token.len())
+        .is_some_and(|end| end <= line.len())
+    {
+        offset + 1
+    } else {
+        1
+    }
+}
+
+/// Parse every non-empty line of `input`, reporting errors at their line number
+pub fn parse_lines<'a, T>(input: &'a str) -> Result<Vec<T>, T::Error>
+where
+    T: TryFrom<&'a str>,
+    T::Error: Located,
+{
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(idx, line)| T::try_from(line).map_err(|err| err.on_line(idx + 1)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct NotEven(Location);
+
+    impl Located for NotEven {
+        fn location_mut(&mut self) -> &mut Location {
+            &mut self.0
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Even(u8);
+
+    impl TryFrom<&str> for Even {
+        type Error = NotEven;
+
+        fn try_from(from: &str) -> Result<Self, Self::Error> {
+            match from.parse::<u8>() {
+                Ok(num) if num % 2 == 0 => Ok(Even(num)),
+                _ => Err(NotEven(Location::column(3))),
+            }
+        }
+    }
+
+    #[test]
+    fn parse_lines_reports_line() {
+        assert_eq!(Ok(vec![Even(2), Even(4)]), parse_lines::<Even>("2\n\n4\n"));
+        assert_eq!(
+            Err(NotEven(Location::new(3, 3))),
+            parse_lines::<Even>("2\n4\n5\n6")
+        );
+    }
+
+    #[test]
+    fn column_of_subslice() {
+        let line = "move 1 from 2";
+        assert_eq!(1, column_of(line, &line[..4]));
+        assert_eq!(6, column_of(line, &line[5..6]));
+        assert_eq!(1, column_of(line, "elsewhere"));
+    }
+}
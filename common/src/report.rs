@@ -0,0 +1,203 @@
+use crate::Solution;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fmt::{self, Display};
+use std::time::{Duration, Instant};
+
+/// Which half of a puzzle to solve
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// An answer as it is reported, kept apart by kind so readers do not have to guess
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", content = "value", rename_all = "lowercase")]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    /// Answers drawn on a screen, one string per row
+    Lines(Vec<String>),
+}
+
+impl Answer {
+    pub fn new(answer: impl Display) -> Self {
+        let text = answer.to_string();
+        if text.contains('\n') {
+            Answer::Lines(text.lines().map(String::from).collect())
+        } else if let Ok(number) = text.parse() {
+            Answer::Number(number)
+        } else {
+            Answer::Text(text)
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{number}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Lines(lines) => write!(f, "{}", lines.join("\n")),
+        }
+    }
+}
+
+/// The answer to one part, with what it took to get there
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Report {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Answer,
+    /// `sha256:` followed by the hex digest of the puzzle input
+    pub input_checksum: String,
+    /// Time spent parsing the input, shared by both parts
+    pub parse_ns: u64,
+    pub solve_ns: u64,
+}
+
+/// The checksum of a puzzle input, as it is written in a `Report`
+pub fn checksum(input: &str) -> String {
+    let digest = Sha256::digest(input.as_bytes());
+    let hex: String = digest.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("sha256:{hex}")
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+/// Parse the input once and solve each of `parts`, timing every step
+pub fn solve<S: Solution>(input: &str, parts: &[Part]) -> Result<Vec<Report>, S::Error> {
+    solve_with(S::DAY, input, parts, S::parse, |part, parsed| {
+        Ok(match part {
+            Part::One => Answer::new(S::part_one(parsed)?),
+            Part::Two => Answer::new(S::part_two(parsed)?),
+        })
+    })
+}
+
+/// Like `solve`, for days whose binaries read or solve the input in a way the
+/// `Solution` does not, such as under other rules
+pub fn solve_with<'a, P, E>(
+    day: u8,
+    input: &'a str,
+    parts: &[Part],
+    parse: impl FnOnce(&'a str) -> Result<P, E>,
+    solve: impl Fn(Part, &P) -> Result<Answer, E>,
+) -> Result<Vec<Report>, E> {
+    let input_checksum = checksum(input);
+    let start = Instant::now();
+    let parsed = parse(input)?;
+    let parse_ns = nanos(start.elapsed());
+
+    parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = solve(part, &parsed)?;
+            Ok(Report {
+                day,
+                part: part.number(),
+                answer,
+                input_checksum: input_checksum.clone(),
+                parse_ns,
+                solve_ns: nanos(start.elapsed()),
+            })
+        })
+        .collect()
+}
+
+/// Reports as a JSON array, the format dashboards read
+pub fn to_json(reports: &[Report]) -> String {
+    serde_json::to_string_pretty(reports).expect("reports always serialize")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+
+    struct Lengths;
+
+    impl Solution for Lengths {
+        const DAY: u8 = 99;
+        type Parsed<'a> = Vec<&'a str>;
+        type PartOne = usize;
+        type PartTwo = String;
+        type Error = Infallible;
+
+        fn parse(input: &str) -> Result<Vec<&str>, Infallible> {
+            Ok(input.lines().collect())
+        }
+
+        fn part_one(lines: &Vec<&str>) -> Result<usize, Infallible> {
+            Ok(lines.len())
+        }
+
+        fn part_two(lines: &Vec<&str>) -> Result<String, Infallible> {
+            Ok(lines.join("\n"))
+        }
+    }
+
+    #[test]
+    fn answers_by_kind() {
+        assert_eq!(Answer::Number(-42), Answer::new(-42));
+        assert_eq!(Answer::Text(String::from("CMZ")), Answer::new("CMZ"));
+        assert_eq!(
+            Answer::Lines(vec![String::from("#."), String::from(".#")]),
+            Answer::new("#.\n.#")
+        );
+        assert_eq!("#.\n.#", Answer::new("#.\n.#").to_string());
+    }
+
+    #[test]
+    fn json_report() {
+        let reports = solve::<Lengths>("ab\ncd\n", &[Part::Two]).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&to_json(&reports)).unwrap();
+
+        assert_eq!(99, json[0]["day"]);
+        assert_eq!(2, json[0]["part"]);
+        assert_eq!("lines", json[0]["answer"]["kind"]);
+        assert_eq!(serde_json::json!(["ab", "cd"]), json[0]["answer"]["value"]);
+        assert_eq!(checksum("ab\ncd\n"), json[0]["input_checksum"]);
+        assert!(json[0]["parse_ns"].is_u64());
+        assert!(json[0]["solve_ns"].is_u64());
+    }
+
+    #[test]
+    fn solved_another_way() {
+        let reports = solve_with(
+            99,
+            "ab\ncd\n",
+            &Part::BOTH,
+            |input| Ok::<_, Infallible>(input.len()),
+            |part, &bytes| Ok(Answer::new(bytes * part.number() as usize)),
+        )
+        .unwrap();
+
+        assert_eq!(Answer::Number(6), reports[0].answer);
+        assert_eq!(Answer::Number(12), reports[1].answer);
+        assert_eq!(2, reports[1].part);
+    }
+
+    #[test]
+    fn checksum_is_sha256() {
+        assert_eq!(
+            "sha256:e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            checksum("")
+        );
+    }
+}
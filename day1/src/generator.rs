@@ -0,0 +1,55 @@
+//! Calorie lists with known answers
+
+use crate::Day1;
+use common::generate::{rng, Generate, Generated};
+use rand::Rng;
+
+/// A list of `elves` elves, at least three so there is a top three
+///
+/// The elves are separated by a blank line, with none after the last.
+pub fn generate(seed: u64, elves: usize) -> Generated {
+    let mut rng = rng(seed);
+    let mut input = String::new();
+    let mut totals = Vec::new();
+    for elf in 0..elves.max(3) {
+        if elf > 0 {
+            input.push('\n');
+        }
+        let mut total = 0;
+        for _ in 0..rng.random_range(1..=15) {
+            let calories: u64 = rng.random_range(1000..=20000);
+            input.push_str(&format!("{calories}\n"));
+            total += calories;
+        }
+        totals.push(total);
+    }
+
+    totals.sort_unstable();
+    let top: Vec<u64> = totals.iter().rev().take(3).copied().collect();
+    Generated::new(input, top[0], top.iter().sum::<u64>())
+}
+
+impl Generate for Day1 {
+    fn generate(seed: u64, size: usize) -> Generated {
+        generate(seed, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solution_agrees() {
+        for seed in 0..20 {
+            let generated = generate(seed, 50);
+            assert_eq!(generated.answers(), generated.solve::<Day1>().unwrap());
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(generate(7, 10), generate(7, 10));
+        assert_ne!(generate(7, 10), generate(8, 10));
+    }
+}
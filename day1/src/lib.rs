@@ -0,0 +1,317 @@
+//! Day 1: Calorie Counting, finding the elves carrying the most calories
+#![warn(missing_docs)]
+
+pub mod generator;
+pub mod parallel;
+pub mod rebalance;
+pub mod stats;
+pub mod top;
+
+use common::{column_of, Located, Location, Solution};
+use parallel::{default_threads, parse_inventory_parallel};
+use std::num::IntErrorKind;
+use thiserror::Error;
+
+/// An elf and the calories of all the food it carries
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Elf {
+    total: i64,
+    items: Vec<i64>,
+}
+
+impl Elf {
+    /// An elf carrying nothing yet
+    pub fn new() -> Self {
+        Elf {
+            total: 0,
+            items: Vec::new(),
+        }
+    }
+
+    /// Add a food item of `calories` to what the elf carries
+    pub fn add_cals(&mut self, calories: i64) {
+        self.total += calories;
+        self.items.push(calories);
+    }
+
+    /// Calories carried in total
+    pub fn total(&self) -> i64 {
+        self.total
+    }
+
+    /// Calories of every food item, in the order they are listed
+    pub fn items(&self) -> &[i64] {
+        &self.items
+    }
+
+    /// Calories of the largest food item, unless the elf carries nothing
+    pub fn largest_item(&self) -> Option<i64> {
+        self.items.iter().copied().max()
+    }
+}
+
+impl Default for Elf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Why the inventory could not be read
+///
+/// Every variant holds the `location` of the problem and the `text` found there.
+#[allow(missing_docs)]
+#[derive(Debug, PartialEq, Error)]
+pub enum ParseError {
+    /// A line that is neither a number nor blank
+    #[error("{location}: `{text}` is not a number of calories")]
+    InvalidCalories { location: Location, text: String },
+    /// An item, or the total of an elf, too large to count
+    #[error("{location}: `{text}` calories are too many to count")]
+    Overflow { location: Location, text: String },
+    /// A blank line that does not end an elf's items
+    #[error("{location}: blank line after a group of no items")]
+    EmptyGroup { location: Location, text: String },
+}
+
+impl Located for ParseError {
+    fn location_mut(&mut self) -> &mut Location {
+        match self {
+            ParseError::InvalidCalories { location, .. }
+            | ParseError::Overflow { location, .. }
+            | ParseError::EmptyGroup { location, .. } => location,
+        }
+    }
+}
+
+/// What to do with lines that are not part of a well formed inventory
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Mode {
+    /// Fail on the first one
+    #[default]
+    Strict,
+    /// Skip it, keeping the error to look at later
+    Lenient,
+}
+
+/// The elves in the order they are listed
+#[derive(Debug, Default, PartialEq)]
+pub struct Inventory {
+    /// Every elf carrying at least one item
+    pub elves: Vec<Elf>,
+    /// What was skipped in lenient mode, in the order it was found
+    pub skipped: Vec<ParseError>,
+}
+
+/// Groups the lines of an inventory into elves, one line at a time
+///
+/// Lines may end in CRLF and numbers may be padded with whitespace. A line
+/// holding only whitespace is blank, and the last elf needs no blank line
+/// after it.
+#[derive(Debug)]
+pub(crate) struct Grouper {
+    mode: Mode,
+    line: usize,
+    elf: Option<Elf>,
+    skipped: Vec<ParseError>,
+}
+
+impl Grouper {
+    pub(crate) fn new(mode: Mode) -> Self {
+        Grouper {
+            mode,
+            line: 0,
+            elf: None,
+            skipped: Vec::new(),
+        }
+    }
+
+    /// Read the next line, returning the elf it ends if it is blank
+    pub(crate) fn feed(&mut self, line: &str) -> Result<Option<Elf>, ParseError> {
+        self.line += 1;
+        let line = line.trim_end_matches(['\n', '\r']);
+        let text = line.trim();
+        if text.is_empty() {
+            let elf = self.elf.take();
+            if elf.is_none() {
+                self.problem(ParseError::EmptyGroup {
+                    location: Location::new(self.line, 1),
+                    text: line.to_string(),
+                })?;
+            }
+            return Ok(elf);
+        }
+
+        let location = Location::new(self.line, column_of(line, text));
+        let calories = match text.parse::<i64>() {
+            Ok(calories) if calories >= 0 => calories,
+            Err(err) if matches!(err.kind(), IntErrorKind::PosOverflow) => {
+                return self.overflow(location, text).map(|()| None);
+            }
+            _ => {
+                self.problem(ParseError::InvalidCalories {
+                    location,
+                    text: text.to_string(),
+                })?;
+                return Ok(None);
+            }
+        };
+        let elf = self.elf.get_or_insert_with(Elf::new);
+        if elf.total.checked_add(calories).is_none() {
+            return self.overflow(location, text).map(|()| None);
+        }
+        elf.add_cals(calories);
+        Ok(None)
+    }
+
+    /// Drop what was skipped so far, for inventories too large to keep it for
+    pub(crate) fn forget_skipped(&mut self) {
+        self.skipped.clear();
+    }
+
+    /// The last elf, if the inventory does not end with a blank line, and what was skipped
+    pub(crate) fn finish(self) -> (Option<Elf>, Vec<ParseError>) {
+        (self.elf, self.skipped)
+    }
+
+    fn overflow(&mut self, location: Location, text: &str) -> Result<(), ParseError> {
+        self.problem(ParseError::Overflow {
+            location,
+            text: text.to_string(),
+        })
+    }
+
+    /// Fail with `err` in strict mode, or keep it and carry on
+    fn problem(&mut self, err: ParseError) -> Result<(), ParseError> {
+        match self.mode {
+            Mode::Strict => Err(err),
+            Mode::Lenient => {
+                self.skipped.push(err);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Read the whole inventory
+pub fn parse_inventory(input: &str, mode: Mode) -> Result<Inventory, ParseError> {
+    let mut grouper = Grouper::new(mode);
+    let mut elves = Vec::new();
+    for line in input.lines() {
+        elves.extend(grouper.feed(line)?);
+    }
+    let (last, skipped) = grouper.finish();
+    elves.extend(last);
+    Ok(Inventory { elves, skipped })
+}
+
+fn sort_by_total(elves: &mut [Elf]) {
+    elves.sort_by_key(|elf| std::cmp::Reverse(elf.total));
+}
+
+/// Calorie Counting
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Parsed<'a> = Vec<Elf>;
+    type PartOne = i64;
+    type PartTwo = i64;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Vec<Elf>, ParseError> {
+        let mut elves = parse_inventory_parallel(input, Mode::Strict, default_threads())?.elves;
+        sort_by_total(&mut elves);
+        Ok(elves)
+    }
+
+    /// Calories carried by the elf carrying the most
+    fn part_one(elves: &Vec<Elf>) -> Result<i64, ParseError> {
+        Ok(elves.first().map_or(0, |elf| elf.total))
+    }
+
+    /// Calories carried by the top three elves together
+    fn part_two(elves: &Vec<Elf>) -> Result<i64, ParseError> {
+        Ok(elves.iter().take(3).map(|elf| elf.total).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn totals(inventory: &Inventory) -> Vec<i64> {
+        inventory.elves.iter().map(Elf::total).collect()
+    }
+
+    #[test]
+    fn keeps_the_items() {
+        let inventory = parse_inventory("3\n9\n1\n\n4\n", Mode::Strict).unwrap();
+        assert_eq!(inventory.elves[0].items(), [3, 9, 1]);
+        assert_eq!(inventory.elves[0].largest_item(), Some(9));
+        assert_eq!(inventory.elves[1].items(), [4]);
+        assert_eq!(Elf::new().largest_item(), None);
+    }
+
+    #[test]
+    fn keeps_the_last_elf() {
+        let inventory = parse_inventory("1\n2\n\n3\n4", Mode::Strict).unwrap();
+        assert_eq!(totals(&inventory), [3, 7]);
+        let inventory = parse_inventory("1\n2\n\n3\n4\n\n", Mode::Strict).unwrap();
+        assert_eq!(totals(&inventory), [3, 7]);
+    }
+
+    #[test]
+    fn crlf_and_padding() {
+        let inventory = parse_inventory(" 100 \r\n\t20\r\n  \r\n3\r\n", Mode::Strict).unwrap();
+        assert_eq!(totals(&inventory), [120, 3]);
+    }
+
+    #[test]
+    fn strict_errors() {
+        assert_eq!(
+            parse_inventory("1\n\n 2x\n", Mode::Strict),
+            Err(ParseError::InvalidCalories {
+                location: Location::new(3, 2),
+                text: String::from("2x"),
+            })
+        );
+        assert_eq!(
+            parse_inventory("1\n99999999999999999999\n", Mode::Strict),
+            Err(ParseError::Overflow {
+                location: Location::new(2, 1),
+                text: String::from("99999999999999999999"),
+            })
+        );
+        assert_eq!(
+            parse_inventory("9223372036854775807\n1\n", Mode::Strict),
+            Err(ParseError::Overflow {
+                location: Location::new(2, 1),
+                text: String::from("1"),
+            })
+        );
+        assert_eq!(
+            parse_inventory("1\n\n\n2\n", Mode::Strict),
+            Err(ParseError::EmptyGroup {
+                location: Location::new(3, 1),
+                text: String::new(),
+            })
+        );
+        assert!(matches!(
+            parse_inventory("\n1\n", Mode::Strict),
+            Err(ParseError::EmptyGroup { .. })
+        ));
+    }
+
+    #[test]
+    fn lenient_keeps_what_it_skips() {
+        let inventory = parse_inventory("1\nfour\n\n\n-2\n5\n", Mode::Lenient).unwrap();
+        assert_eq!(totals(&inventory), [1, 5]);
+        let lines: Vec<usize> = inventory
+            .skipped
+            .into_iter()
+            .map(|mut err| err.location_mut().line)
+            .collect();
+        assert_eq!(lines, [2, 4, 5]);
+    }
+}
@@ -1,40 +1,122 @@
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
-struct Elf {
-    total: i64,
+use clap::builder::RangedU64ValueParser;
+use clap::Parser;
+use common::{print_answers, OutputArgs, OutputFormat, Solution};
+use day1::parallel::top_elves_parallel;
+use day1::rebalance;
+use day1::stats::Report;
+use day1::top::top_elves;
+use day1::{parse_inventory, Day1, Mode};
+use std::fs::{self, File};
+use std::io::{self, BufReader};
+use std::path::PathBuf;
+
+/// Command line of the day 1 binary
+#[derive(Parser, Debug)]
+struct Args {
+    #[command(flatten)]
+    output: OutputArgs,
+    /// Stream the inventory from this file, or `-` for stdin, and list the top elves
+    #[arg(long)]
+    stream: Option<PathBuf>,
+    /// How many elves to list when streaming
+    #[arg(long, default_value_t = 3, requires = "stream")]
+    top: usize,
+    /// Skip lines that are not part of a well formed inventory when streaming
+    #[arg(long, requires = "stream")]
+    lenient: bool,
+    /// Read the whole inventory first and find the top elves on this many threads
+    #[arg(long, value_parser = RangedU64ValueParser::<usize>::new().range(1..), requires = "stream")]
+    threads: Option<usize>,
+    /// Report statistics over the inventory instead of the answers
+    #[arg(long, conflicts_with = "stream")]
+    report: bool,
+    /// Plan moving at most this many items so the heaviest load is as light as can be
+    #[arg(long, value_name = "MAX_TRANSFERS", conflicts_with_all = ["stream", "report"])]
+    rebalance: Option<usize>,
+    /// Percentiles of the totals to report
+    #[arg(long = "percentile", value_name = "PERCENT", default_values_t = [25.0, 50.0, 75.0, 90.0], requires = "report")]
+    percentiles: Vec<f64>,
+    /// Width in calories of the buckets of the histogram of totals
+    #[arg(long, default_value_t = 10_000, value_parser = clap::value_parser!(i64).range(1..), requires = "report")]
+    bucket: i64,
+    /// Also list the elves carrying any item of more than this many calories
+    #[arg(long, value_name = "CALORIES", requires = "report")]
+    item_over: Option<i64>,
 }
 
-impl Elf {
-    fn new() -> Self {
-        Elf { total: 0 }
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    if let Some(path) = &args.stream {
+        let mode = if args.lenient {
+            Mode::Lenient
+        } else {
+            Mode::Strict
+        };
+        let top = match args.threads {
+            Some(threads) => {
+                let input = if path.as_os_str() == "-" {
+                    io::read_to_string(io::stdin().lock())?
+                } else {
+                    fs::read_to_string(path)?
+                };
+                top_elves_parallel(&input, args.top, mode, threads)?
+            }
+            None if path.as_os_str() == "-" => top_elves(io::stdin().lock(), args.top, mode)?,
+            None => top_elves(BufReader::new(File::open(path)?), args.top, mode)?,
+        };
+        match args.output.format {
+            OutputFormat::Text => {
+                for (rank, elf) in top.iter().enumerate() {
+                    println!(
+                        "#{}: elf {} is carrying {} calories",
+                        rank + 1,
+                        elf.index,
+                        elf.total
+                    );
+                }
+            }
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&top)?),
+        }
+        return Ok(());
     }
 
-    fn add_cals(&mut self, calories: i64) {
-        self.total += calories;
+    let input = input::load(Day1::DAY)?;
+    if args.report {
+        let report = Report::new(
+            &parse_inventory(&input, Mode::Strict)?,
+            &args.percentiles,
+            args.bucket,
+            args.item_over,
+        );
+        match args.output.format {
+            OutputFormat::Text => print!("{report}"),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        }
+        return Ok(());
     }
-}
 
-fn main() -> Result<(), std::io::Error> {
-    let input = std::fs::read_to_string("input.txt")?;
-    let mut elf = Elf::new();
-    let mut elves: Vec<Elf> = Vec::new();
-    for line in input.split('\n') {
-        if line.is_empty() {
-            elves.push(elf);
-            elf = Elf::new();
-            continue;
-        }
-        if let Ok(calories) = line.parse::<i64>() {
-            elf.add_cals(calories);
+    if let Some(max_transfers) = args.rebalance {
+        let inventory = parse_inventory(&input, Mode::Strict)?;
+        let plan = rebalance::plan(&inventory.elves, max_transfers);
+        match args.output.format {
+            OutputFormat::Text => print!("{plan}"),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&plan)?),
         }
+        return Ok(());
     }
-    elves.sort_by(|a, b| b.total.cmp(&a.total));
-    println!(
-        "The elf carrying the most calories is carrying {} calories",
-        elves[0].total
-    );
 
-    let top_3: i64 = elves.iter().take(3).map(|elf| elf.total).sum();
-    println!("The top 3 elves are totally carrying {} calories", top_3);
+    print_answers::<Day1>(args.output.format, &input, |elves| {
+        println!(
+            "The elf carrying the most calories is carrying {} calories",
+            Day1::part_one(elves)?
+        );
+
+        println!(
+            "The top 3 elves are totally carrying {} calories",
+            Day1::part_two(elves)?
+        );
+        Ok(())
+    })?;
 
     Ok(())
 }
@@ -0,0 +1,293 @@
+//! Reading large inventories on several threads at once
+//!
+//! The inventory is cut into chunks just after blank lines, so every chunk
+//! holds whole elves and reads exactly as it would as part of the whole. The
+//! line and elf counts of the chunks before put the results of each chunk back
+//! in place.
+
+use crate::top::{Ranked, TopElves};
+use crate::{parse_inventory, Elf, Grouper, Inventory, Mode, ParseError};
+use common::Located;
+use std::thread;
+
+/// Inventories are not cut into chunks any smaller than this many bytes
+pub const MIN_CHUNK_LEN: usize = 1 << 16;
+
+/// Number of threads to read on when none is asked for
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, usize::from)
+}
+
+/// Cut `input` into at most `count` chunks of about the same length
+///
+/// Every chunk but the last ends just after a blank line, so no elf is split
+/// between two chunks.
+pub fn split_chunks(input: &str, count: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = input;
+    for left in (2..=count).rev() {
+        let Some(end) = end_of_group_after(rest, rest.len() / left) else {
+            break;
+        };
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    if !rest.is_empty() || chunks.is_empty() {
+        chunks.push(rest);
+    }
+    chunks
+}
+
+/// Offset just past the first blank line that starts at or after `from`
+fn end_of_group_after(input: &str, from: usize) -> Option<usize> {
+    let bytes = input.as_bytes();
+    let mut start = match from {
+        0 => 0,
+        _ => from + bytes[from - 1..].iter().position(|&byte| byte == b'\n')?,
+    };
+    loop {
+        let end = start + bytes[start..].iter().position(|&byte| byte == b'\n')?;
+        if input[start..end].trim().is_empty() {
+            return Some(end + 1);
+        }
+        start = end + 1;
+    }
+}
+
+/// Chunks to cut `input` into to read it on `threads` threads
+fn chunks_for(input: &str, threads: usize) -> Vec<&str> {
+    split_chunks(input, threads.min(input.len() / MIN_CHUNK_LEN).max(1))
+}
+
+/// What reading a single chunk turned up, with lines and elves counted from its start
+struct Chunk<T> {
+    lines: usize,
+    elves: usize,
+    found: T,
+    skipped: Vec<ParseError>,
+}
+
+/// Read every elf of `chunk` into a fresh `T`
+fn read_chunk<T>(
+    chunk: &str,
+    mode: Mode,
+    mut found: T,
+    mut add: impl FnMut(&mut T, usize, Elf),
+) -> Result<Chunk<T>, ParseError> {
+    let mut grouper = Grouper::new(mode);
+    let mut lines = 0;
+    let mut elves = 0;
+    for line in chunk.lines() {
+        lines += 1;
+        if let Some(elf) = grouper.feed(line)? {
+            add(&mut found, elves, elf);
+            elves += 1;
+        }
+    }
+    let (last, skipped) = grouper.finish();
+    if let Some(elf) = last {
+        add(&mut found, elves, elf);
+        elves += 1;
+    }
+    Ok(Chunk {
+        lines,
+        elves,
+        found,
+        skipped,
+    })
+}
+
+/// Read every chunk on a thread of its own, then hand them back in order
+///
+/// Errors are moved to their line in the whole input, and in strict mode the
+/// first of them is returned, as reading the chunks in turn would.
+fn read_chunks<T: Send>(
+    chunks: &[&str],
+    read: impl Fn(&str) -> Result<Chunk<T>, ParseError> + Sync,
+) -> Result<Vec<(usize, Chunk<T>)>, ParseError> {
+    let results: Vec<Result<Chunk<T>, ParseError>> = thread::scope(|scope| {
+        let read = &read;
+        let handles: Vec<_> = chunks
+            .iter()
+            .map(|&chunk| scope.spawn(move || read(chunk)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("reading a chunk never panics"))
+            .collect()
+    });
+
+    let mut lines = 0;
+    let mut elves = 0;
+    let mut placed = Vec::with_capacity(results.len());
+    for result in results {
+        let mut chunk = result.map_err(|err| moved(err, lines))?;
+        chunk.skipped = chunk
+            .skipped
+            .into_iter()
+            .map(|err| moved(err, lines))
+            .collect();
+        lines += chunk.lines;
+        let first_elf = elves;
+        elves += chunk.elves;
+        placed.push((first_elf, chunk));
+    }
+    Ok(placed)
+}
+
+/// Move an error found `lines` lines into the input
+fn moved(mut err: ParseError, lines: usize) -> ParseError {
+    let line = err.location_mut().line;
+    err.on_line(line + lines)
+}
+
+/// Read the whole inventory on up to `threads` threads
+///
+/// Gives the same inventory as `parse_inventory`, which reads it on this
+/// thread when it is too small to split or there is only one thread.
+pub fn parse_inventory_parallel(
+    input: &str,
+    mode: Mode,
+    threads: usize,
+) -> Result<Inventory, ParseError> {
+    match chunks_for(input, threads)[..] {
+        [_] => parse_inventory(input, mode),
+        ref chunks => inventory_of_chunks(chunks, mode),
+    }
+}
+
+fn inventory_of_chunks(chunks: &[&str], mode: Mode) -> Result<Inventory, ParseError> {
+    let read = read_chunks(chunks, |chunk| {
+        read_chunk(chunk, mode, Vec::new(), |elves, _, elf| elves.push(elf))
+    })?;
+    let mut inventory = Inventory::default();
+    for (_, chunk) in read {
+        inventory.elves.extend(chunk.found);
+        inventory.skipped.extend(chunk.skipped);
+    }
+    Ok(inventory)
+}
+
+/// The top `k` elves of the inventory, read on up to `threads` threads
+///
+/// Every chunk keeps its own top `k`, which are merged keeping the positions of
+/// the elves in the whole inventory. Gives the same elves as `top_elves`.
+pub fn top_elves_parallel(
+    input: &str,
+    k: usize,
+    mode: Mode,
+    threads: usize,
+) -> Result<Vec<Ranked>, ParseError> {
+    top_of_chunks(&chunks_for(input, threads), k, mode)
+}
+
+fn top_of_chunks(chunks: &[&str], k: usize, mode: Mode) -> Result<Vec<Ranked>, ParseError> {
+    let read = read_chunks(chunks, |chunk| {
+        read_chunk(chunk, mode, TopElves::new(k), |top, index, elf| {
+            top.push(index, &elf)
+        })
+    })?;
+    let mut top = TopElves::new(k);
+    for (first_elf, chunk) in read {
+        for ranked in chunk.found.into_sorted() {
+            top.push_ranked(Ranked {
+                index: first_elf + ranked.index,
+                ..ranked
+            });
+        }
+    }
+    Ok(top.into_sorted())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::generate;
+    use crate::top::top_elves;
+    use common::Location;
+
+    #[test]
+    fn chunks_end_after_blank_lines() {
+        let input = "1\n2\n\n3\n\n4\n5\n\n6\n";
+        let chunks = split_chunks(input, 3);
+        assert_eq!(chunks, ["1\n2\n\n3\n\n", "4\n5\n\n", "6\n"]);
+        assert_eq!(split_chunks(input, 1), [input]);
+        assert_eq!(split_chunks("1\n2\n", 4), ["1\n2\n"]);
+        assert_eq!(split_chunks("", 4), [""]);
+        assert_eq!(split_chunks("1\r\n \r\n2", 2), ["1\r\n \r\n", "2"]);
+    }
+
+    #[test]
+    fn same_as_sequential() {
+        for seed in 0..20 {
+            let input = generate(seed, 300).input;
+            for count in [1, 2, 3, 7, 16] {
+                let chunks = split_chunks(&input, count);
+                assert_eq!(chunks.concat(), input);
+                assert_eq!(
+                    inventory_of_chunks(&chunks, Mode::Strict).unwrap(),
+                    parse_inventory(&input, Mode::Strict).unwrap()
+                );
+                assert_eq!(
+                    top_of_chunks(&chunks, 5, Mode::Strict).unwrap(),
+                    top_elves(input.as_bytes(), 5, Mode::Strict).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn ties_keep_the_first_listed() {
+        let input = "7\n\n5\n\n7\n\n5\n\n7\n\n";
+        for count in 1..=5 {
+            assert_eq!(
+                top_of_chunks(&split_chunks(input, count), 2, Mode::Strict).unwrap(),
+                top_elves(input.as_bytes(), 2, Mode::Strict).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn errors_on_the_whole_input_line() {
+        let input = "1\n\n2\n\n3\nx\n\n4\n\ny\n";
+        let chunks = split_chunks(input, 4);
+        assert!(chunks.len() > 2);
+        assert_eq!(
+            inventory_of_chunks(&chunks, Mode::Strict),
+            Err(ParseError::InvalidCalories {
+                location: Location::new(6, 1),
+                text: String::from("x"),
+            })
+        );
+        assert_eq!(
+            inventory_of_chunks(&chunks, Mode::Lenient).unwrap(),
+            parse_inventory(input, Mode::Lenient).unwrap()
+        );
+    }
+
+    #[test]
+    fn top_elves_across_threads() {
+        let input = generate(5, 20_000).input;
+        assert!(chunks_for(&input, 4).len() > 1);
+        for mode in [Mode::Strict, Mode::Lenient] {
+            let top = top_elves_parallel(&input, 10, mode, 4).unwrap();
+            // The same elves at the same positions, not just the same totals
+            assert_eq!(top, top_elves(input.as_bytes(), 10, mode).unwrap());
+        }
+    }
+
+    #[test]
+    fn small_inputs_stay_whole() {
+        let input = generate(3, 100).input;
+        assert_eq!(chunks_for(&input, 8), [input.as_str()]);
+        assert_eq!(
+            top_elves_parallel(&input, 3, Mode::Strict, 8).unwrap(),
+            top_elves(input.as_bytes(), 3, Mode::Strict).unwrap()
+        );
+        assert_eq!(
+            parse_inventory_parallel(&input, Mode::Strict, 8),
+            parse_inventory(&input, Mode::Strict)
+        );
+    }
+}
@@ -0,0 +1,406 @@
+//! Plans for moving food between elves so that no elf carries too much
+//!
+//! Spreading the items so the heaviest load is as light as possible is the
+//! multiway number partitioning problem, here with a limit on how many items
+//! may change hands. Small inventories are searched exhaustively, larger ones
+//! get a greedy plan.
+
+use crate::Elf;
+use serde::Serialize;
+use std::fmt;
+
+/// Largest number of items the exact search is allowed to place, over all assignments it considers
+pub const EXACT_SEARCH_LIMIT: u64 = 1 << 22;
+
+/// How a plan was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Method {
+    /// Every assignment within the limit was considered, the plan is the best there is
+    Exact,
+    /// Items were moved one at a time from the heaviest elf to the lightest
+    Heuristic,
+}
+
+/// A food item handed from one elf to another
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Transfer {
+    /// Position of the elf carrying the item now
+    pub from: usize,
+    /// Position of the item in the list of the elf carrying it now
+    pub item: usize,
+    /// Calories of the item
+    pub calories: i64,
+    /// Position of the elf to carry it
+    pub to: usize,
+}
+
+/// The heaviest and the lightest load
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Spread {
+    /// Calories carried by the elf carrying the most
+    pub heaviest: i64,
+    /// Calories carried by the elf carrying the least
+    pub lightest: i64,
+}
+
+impl Spread {
+    fn of(loads: &[i64]) -> Self {
+        Spread {
+            heaviest: loads.iter().copied().max().unwrap_or(0),
+            lightest: loads.iter().copied().min().unwrap_or(0),
+        }
+    }
+
+    /// How much more the heaviest load is than the lightest
+    pub fn spread(&self) -> i64 {
+        self.heaviest - self.lightest
+    }
+}
+
+/// The items to move, and the loads before and after moving them
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Plan {
+    /// How the plan was found
+    pub method: Method,
+    /// Every item that changes hands, by the elf carrying it now
+    pub transfers: Vec<Transfer>,
+    /// The loads as they are
+    pub before: Spread,
+    /// The loads once every transfer is made
+    pub after: Spread,
+}
+
+/// The plan as lines of text, every transfer and then the loads before and after
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let method = match self.method {
+            Method::Exact => "the best possible",
+            Method::Heuristic => "found greedily",
+        };
+        writeln!(f, "Moving {} items, {method}:", self.transfers.len())?;
+        for transfer in &self.transfers {
+            writeln!(
+                f,
+                "  item {} of elf {} ({} calories) to elf {}",
+                transfer.item, transfer.from, transfer.calories, transfer.to
+            )?;
+        }
+        for (when, spread) in [("Before", self.before), ("After", self.after)] {
+            writeln!(
+                f,
+                "{when}: heaviest {}, lightest {}, spread {}",
+                spread.heaviest,
+                spread.lightest,
+                spread.spread()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A food item and the elf carrying it now
+#[derive(Debug, Clone, Copy)]
+struct Item {
+    owner: usize,
+    index: usize,
+    calories: i64,
+}
+
+/// Every item of every elf, the largest first
+fn items(elves: &[Elf]) -> Vec<Item> {
+    let mut items: Vec<Item> = elves
+        .iter()
+        .enumerate()
+        .flat_map(|(owner, elf)| {
+            elf.items()
+                .iter()
+                .enumerate()
+                .map(move |(index, &calories)| Item {
+                    owner,
+                    index,
+                    calories,
+                })
+        })
+        .collect();
+    items.sort_by_key(|item| std::cmp::Reverse(item.calories));
+    items
+}
+
+/// The plan that moves every item in `items` to the elf in `carriers`
+fn plan_for(method: Method, elves: &[Elf], items: &[Item], carriers: &[usize]) -> Plan {
+    let before: Vec<i64> = elves.iter().map(Elf::total).collect();
+    let mut after = vec![0; elves.len()];
+    let mut transfers = Vec::new();
+    for (item, &carrier) in items.iter().zip(carriers) {
+        after[carrier] += item.calories;
+        if carrier != item.owner {
+            transfers.push(Transfer {
+                from: item.owner,
+                item: item.index,
+                calories: item.calories,
+                to: carrier,
+            });
+        }
+    }
+    transfers.sort_by_key(|transfer| (transfer.from, transfer.item));
+    Plan {
+        method,
+        transfers,
+        before: Spread::of(&before),
+        after: Spread::of(&after),
+    }
+}
+
+/// Number of assignments with at most `max_transfers` items changing hands
+fn search_size(elves: usize, items: usize, max_transfers: usize) -> u64 {
+    let others = elves.saturating_sub(1) as u64;
+    let mut size: u64 = 0;
+    // Ways to pick `moved` items, times the ways to hand each to another elf
+    let mut ways: u64 = 1;
+    for moved in 0..=max_transfers.min(items) {
+        size = size.saturating_add(ways);
+        ways = ways
+            .saturating_mul((items - moved) as u64)
+            .saturating_mul(others)
+            / (moved as u64 + 1);
+    }
+    size
+}
+
+/// Plan with the exact search when it is small enough, and greedily otherwise
+pub fn plan(elves: &[Elf], max_transfers: usize) -> Plan {
+    let items = elves.iter().map(|elf| elf.items().len()).sum();
+    let placements = search_size(elves.len(), items, max_transfers).saturating_mul(items as u64);
+    if placements <= EXACT_SEARCH_LIMIT {
+        exact(elves, max_transfers)
+    } else {
+        heuristic(elves, max_transfers)
+    }
+}
+
+/// The plan with the lightest heaviest load, and the fewest transfers among those
+///
+/// Considers every way to move at most `max_transfers` items, so it is only
+/// fit for small inventories.
+pub fn exact(elves: &[Elf], max_transfers: usize) -> Plan {
+    let items = items(elves);
+    let carriers: Vec<usize> = items.iter().map(|item| item.owner).collect();
+    let mut search = Search {
+        items: &items,
+        loads: vec![0; elves.len()],
+        carriers: carriers.clone(),
+        moved: 0,
+        max_transfers,
+        best: (elves.iter().map(Elf::total).max().unwrap_or(0), 0, carriers),
+    };
+    search.assign(0);
+    plan_for(Method::Exact, elves, &items, &search.best.2)
+}
+
+/// Branch and bound over the elf to carry each item
+struct Search<'a> {
+    items: &'a [Item],
+    loads: Vec<i64>,
+    carriers: Vec<usize>,
+    moved: usize,
+    max_transfers: usize,
+    /// The heaviest load and number of transfers of the best plan so far, and its carriers
+    best: (i64, usize, Vec<usize>),
+}
+
+impl Search<'_> {
+    fn assign(&mut self, next: usize) {
+        let heaviest = self.loads.iter().copied().max().unwrap_or(0);
+        // Placing more items can only make the loads heavier and the transfers more
+        if (heaviest, self.moved) >= (self.best.0, self.best.1) {
+            return;
+        }
+        let Some(item) = self.items.get(next) else {
+            self.best = (heaviest, self.moved, self.carriers.clone());
+            return;
+        };
+
+        // Leaving the item where it is first finds plans with few transfers early
+        let elves = self.loads.len();
+        let carriers =
+            std::iter::once(item.owner).chain((0..elves).filter(|&elf| elf != item.owner));
+        for carrier in carriers {
+            let transfer = usize::from(carrier != item.owner);
+            if self.moved + transfer > self.max_transfers {
+                continue;
+            }
+            self.loads[carrier] += item.calories;
+            self.carriers[next] = carrier;
+            self.moved += transfer;
+            self.assign(next + 1);
+            self.moved -= transfer;
+            self.loads[carrier] -= item.calories;
+        }
+    }
+}
+
+/// A plan made by moving one item at a time from the heaviest elf to the lightest
+///
+/// Each move takes the item that brings the two loads closest together, and
+/// moving stops once no item makes the heavier of the two lighter or
+/// `max_transfers` items have changed hands.
+pub fn heuristic(elves: &[Elf], max_transfers: usize) -> Plan {
+    let items = items(elves);
+    let mut carriers: Vec<usize> = items.iter().map(|item| item.owner).collect();
+    let mut loads: Vec<i64> = elves.iter().map(Elf::total).collect();
+    let mut moved = 0;
+    while let (Some(heaviest), Some(lightest)) = (
+        (0..loads.len()).max_by_key(|&elf| (loads[elf], std::cmp::Reverse(elf))),
+        (0..loads.len()).min_by_key(|&elf| (loads[elf], elf)),
+    ) {
+        let gap = loads[heaviest] - loads[lightest];
+        let best = (0..items.len())
+            .filter(|&item| carriers[item] == heaviest)
+            .filter(|&item| 0 < items[item].calories && items[item].calories < gap)
+            .filter(|&item| {
+                // Handing an item back to its owner undoes a transfer
+                let transfer = items[item].owner != lightest;
+                let undone = items[item].owner != heaviest;
+                !transfer || undone || moved < max_transfers
+            })
+            .min_by_key(|&item| (gap - 2 * items[item].calories).abs());
+        let Some(item) = best else {
+            break;
+        };
+        if items[item].owner == heaviest {
+            moved += 1;
+        } else if items[item].owner == lightest {
+            moved -= 1;
+        }
+        carriers[item] = lightest;
+        loads[heaviest] -= items[item].calories;
+        loads[lightest] += items[item].calories;
+    }
+    plan_for(Method::Heuristic, elves, &items, &carriers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::generate::rng;
+    use rand::Rng;
+
+    fn elves(items: &[&[i64]]) -> Vec<Elf> {
+        items
+            .iter()
+            .map(|items| {
+                let mut elf = Elf::new();
+                for &calories in *items {
+                    elf.add_cals(calories);
+                }
+                elf
+            })
+            .collect()
+    }
+
+    #[test]
+    fn exact_balances() {
+        let elves = elves(&[&[8, 7, 6], &[5], &[4]]);
+        let plan = exact(&elves, 3);
+        assert_eq!(plan.method, Method::Exact);
+        assert_eq!(
+            plan.before,
+            Spread {
+                heaviest: 21,
+                lightest: 4
+            }
+        );
+        assert_eq!(plan.after.heaviest, 11);
+        assert_eq!(plan.transfers.len(), 2);
+    }
+
+    #[test]
+    fn as_text() {
+        let plan = exact(&elves(&[&[8, 7, 6], &[5], &[4]]), 1);
+        assert_eq!(
+            plan.to_string(),
+            "Moving 1 items, the best possible:
+  item 0 of elf 0 (8 calories) to elf 1
+Before: heaviest 21, lightest 4, spread 17
+After: heaviest 13, lightest 4, spread 9
+"
+        );
+    }
+
+    #[test]
+    fn exact_respects_the_limit() {
+        let elves = elves(&[&[8, 7, 6], &[5], &[4]]);
+        let plan = exact(&elves, 1);
+        assert_eq!(
+            plan.transfers,
+            [Transfer {
+                from: 0,
+                item: 0,
+                calories: 8,
+                to: 1
+            }]
+        );
+        assert_eq!(plan.after.heaviest, 13);
+
+        let plan = exact(&elves, 0);
+        assert!(plan.transfers.is_empty());
+        assert_eq!(plan.after, plan.before);
+    }
+
+    #[test]
+    fn nothing_to_move() {
+        let plan = exact(&elves(&[&[5], &[5]]), 4);
+        assert!(plan.transfers.is_empty());
+        assert_eq!(plan.after.spread(), 0);
+        assert_eq!(heuristic(&[], 4).after.spread(), 0);
+    }
+
+    #[test]
+    fn heuristic_moves_towards_the_lightest() {
+        let elves = elves(&[&[8, 7, 6], &[5], &[4]]);
+        let plan = heuristic(&elves, 5);
+        assert_eq!(plan.method, Method::Heuristic);
+        assert_eq!(plan.after.heaviest, 11);
+
+        let plan = heuristic(&elves, 1);
+        assert_eq!(plan.transfers.len(), 1);
+        assert!(plan.after.heaviest < plan.before.heaviest);
+    }
+
+    #[test]
+    fn exact_is_never_beaten() {
+        let mut rng = rng(14);
+        for _ in 0..50 {
+            let items: Vec<Vec<i64>> = (0..rng.random_range(2..=4))
+                .map(|_| {
+                    (0..rng.random_range(1..=3))
+                        .map(|_| rng.random_range(1..=20))
+                        .collect()
+                })
+                .collect();
+            let items: Vec<&[i64]> = items.iter().map(Vec::as_slice).collect();
+            let elves = elves(&items);
+            let limit = rng.random_range(0..=4);
+
+            let exact = exact(&elves, limit);
+            let heuristic = heuristic(&elves, limit);
+            assert!(exact.transfers.len() <= limit);
+            assert!(heuristic.transfers.len() <= limit);
+            assert!(exact.after.heaviest <= heuristic.after.heaviest);
+            assert!(heuristic.after.heaviest <= heuristic.before.heaviest);
+        }
+    }
+
+    #[test]
+    fn large_inventories_are_planned_greedily() {
+        let items: Vec<Vec<i64>> = (1..=40).map(|elf| vec![elf * 100; 5]).collect();
+        let items: Vec<&[i64]> = items.iter().map(Vec::as_slice).collect();
+        let elves = elves(&items);
+        let plan = plan(&elves, 30);
+        assert_eq!(plan.method, Method::Heuristic);
+        assert!(plan.transfers.len() <= 30);
+        assert!(plan.after.spread() < plan.before.spread());
+        assert_eq!(super::plan(&elves[..2], 2).method, Method::Exact);
+    }
+}
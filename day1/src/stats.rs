@@ -0,0 +1,349 @@
+//! Statistics over the inventory and ways to pick elves out of it
+
+use crate::{Elf, Inventory};
+use serde::Serialize;
+use std::fmt;
+
+/// The totals of a group of elves, sorted so they can be ranked
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    totals: Vec<i64>,
+}
+
+/// Elves whose total falls in `start..end`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Bucket {
+    /// Lowest total in the bucket
+    pub start: i64,
+    /// Lowest total of the next bucket
+    pub end: i64,
+    /// Number of elves in the bucket
+    pub count: usize,
+}
+
+impl Stats {
+    /// Statistics over the totals of `elves`
+    pub fn new<'a>(elves: impl IntoIterator<Item = &'a Elf>) -> Self {
+        let mut totals: Vec<i64> = elves.into_iter().map(Elf::total).collect();
+        totals.sort_unstable();
+        Stats { totals }
+    }
+
+    /// Number of elves
+    pub fn len(&self) -> usize {
+        self.totals.len()
+    }
+
+    /// Whether there are no elves at all
+    pub fn is_empty(&self) -> bool {
+        self.totals.is_empty()
+    }
+
+    /// Average total
+    pub fn mean(&self) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+        let sum: i128 = self.totals.iter().map(|&total| i128::from(total)).sum();
+        Some(sum as f64 / self.len() as f64)
+    }
+
+    /// Total in the middle, or halfway between the two in the middle
+    pub fn median(&self) -> Option<f64> {
+        self.percentile(50.0)
+    }
+
+    /// Total that `percent` of the elves carry no more than
+    ///
+    /// Falls between the totals of two elves unless it lands on one, so the
+    /// 0th is the smallest total and the 100th the largest.
+    pub fn percentile(&self, percent: f64) -> Option<f64> {
+        if self.is_empty() || !(0.0..=100.0).contains(&percent) {
+            return None;
+        }
+        let rank = percent / 100.0 * (self.len() - 1) as f64;
+        let below = self.totals[rank.floor() as usize] as f64;
+        let above = self.totals[rank.ceil() as usize] as f64;
+        Some(below + (above - below) * rank.fract())
+    }
+
+    /// Number of elves per `width` calories of total, from the smallest total to the largest
+    ///
+    /// Buckets start at a multiple of `width`. Empty buckets between two
+    /// others are kept so the histogram has no gaps.
+    pub fn histogram(&self, width: i64) -> Vec<Bucket> {
+        assert!(
+            width > 0,
+            "a histogram needs buckets at least a calorie wide"
+        );
+        let (Some(&smallest), Some(&largest)) = (self.totals.first(), self.totals.last()) else {
+            return Vec::new();
+        };
+        let first = smallest.div_euclid(width);
+        let mut buckets: Vec<Bucket> = (first..=largest.div_euclid(width))
+            .map(|bucket| Bucket {
+                start: bucket * width,
+                end: (bucket + 1) * width,
+                count: 0,
+            })
+            .collect();
+        for total in &self.totals {
+            buckets[(total.div_euclid(width) - first) as usize].count += 1;
+        }
+        buckets
+    }
+}
+
+impl Inventory {
+    /// Statistics over the totals of every elf
+    pub fn stats(&self) -> Stats {
+        Stats::new(&self.elves)
+    }
+
+    /// The elves `keep` holds for, with their position in the inventory
+    pub fn filter<'a>(
+        &'a self,
+        keep: impl Fn(&Elf) -> bool + 'a,
+    ) -> impl Iterator<Item = (usize, &'a Elf)> + 'a {
+        self.elves
+            .iter()
+            .enumerate()
+            .filter(move |(_, elf)| keep(elf))
+    }
+
+    /// The elves carrying any item of more than `calories`
+    pub fn carrying_item_over(&self, calories: i64) -> impl Iterator<Item = (usize, &Elf)> {
+        self.filter(move |elf| elf.items().iter().any(|&item| item > calories))
+    }
+
+    /// The largest item of every elf, with its position in the inventory
+    pub fn largest_items(&self) -> impl Iterator<Item = (usize, Option<i64>)> + '_ {
+        self.elves.iter().map(Elf::largest_item).enumerate()
+    }
+}
+
+/// A percentile of the totals
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Percentile {
+    /// Share of the elves, from 0 to 100
+    pub percent: f64,
+    /// Total that share of the elves carry no more than
+    pub total: Option<f64>,
+}
+
+/// The elves carrying any item of more than `calories`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ItemOver {
+    /// Calories an item has to be over
+    pub calories: i64,
+    /// Positions of the elves in the inventory
+    pub elves: Vec<usize>,
+}
+
+/// Everything the report on an inventory shows
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    /// Number of elves
+    pub elves: usize,
+    /// Average total
+    pub mean: Option<f64>,
+    /// Total in the middle
+    pub median: Option<f64>,
+    /// The percentiles asked for
+    pub percentiles: Vec<Percentile>,
+    /// Number of elves per bucket of totals
+    pub histogram: Vec<Bucket>,
+    /// Largest item of every elf
+    pub largest_items: Vec<Option<i64>>,
+    /// The elves carrying a large item, if asked for
+    pub item_over: Option<ItemOver>,
+}
+
+impl Report {
+    /// Report on `inventory` with `percentiles`, a histogram of buckets
+    /// `bucket` calories wide, and the elves with any item over `item_over`
+    pub fn new(
+        inventory: &Inventory,
+        percentiles: &[f64],
+        bucket: i64,
+        item_over: Option<i64>,
+    ) -> Self {
+        let stats = inventory.stats();
+        Report {
+            elves: stats.len(),
+            mean: stats.mean(),
+            median: stats.median(),
+            percentiles: percentiles
+                .iter()
+                .map(|&percent| Percentile {
+                    percent,
+                    total: stats.percentile(percent),
+                })
+                .collect(),
+            histogram: stats.histogram(bucket),
+            largest_items: inventory.largest_items().map(|(_, item)| item).collect(),
+            item_over: item_over.map(|calories| ItemOver {
+                calories,
+                elves: inventory
+                    .carrying_item_over(calories)
+                    .map(|(index, _)| index)
+                    .collect(),
+            }),
+        }
+    }
+}
+
+/// The report as lines of text, with the histogram drawn in bars
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let or_none =
+            |value: Option<f64>| value.map_or(String::from("-"), |value| format!("{value:.1}"));
+        writeln!(f, "Elves: {}", self.elves)?;
+        writeln!(f, "Mean total: {}", or_none(self.mean))?;
+        writeln!(f, "Median total: {}", or_none(self.median))?;
+        for percentile in &self.percentiles {
+            writeln!(
+                f,
+                "Percentile {}: {}",
+                percentile.percent,
+                or_none(percentile.total)
+            )?;
+        }
+        writeln!(f, "Totals:")?;
+        let widest = self
+            .histogram
+            .iter()
+            .map(|bucket| bucket.count)
+            .max()
+            .unwrap_or(0);
+        for bucket in &self.histogram {
+            let bar = "#".repeat((bucket.count * 50).div_ceil(widest.max(1)));
+            writeln!(
+                f,
+                "{:>8} - {:<8} {:>6} {bar}",
+                bucket.start, bucket.end, bucket.count
+            )?;
+        }
+        writeln!(f, "Largest item per elf:")?;
+        for (index, item) in self.largest_items.iter().enumerate() {
+            let item = item.map_or(String::from("-"), |item| item.to_string());
+            writeln!(f, "  elf {index}: {item}")?;
+        }
+        if let Some(item_over) = &self.item_over {
+            writeln!(
+                f,
+                "Elves carrying an item of more than {} calories: {}",
+                item_over.calories,
+                item_over.elves.len()
+            )?;
+            for index in &item_over.elves {
+                writeln!(f, "  elf {index}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_inventory, Mode};
+
+    const EXAMPLE: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000
+";
+
+    fn example() -> Inventory {
+        parse_inventory(EXAMPLE, Mode::Strict).unwrap()
+    }
+
+    #[test]
+    fn averages() {
+        let stats = example().stats();
+        assert_eq!(stats.len(), 5);
+        assert_eq!(stats.mean(), Some(11000.0));
+        assert_eq!(stats.median(), Some(10000.0));
+
+        let even = Stats::new(&example().elves[..4]);
+        assert_eq!(even.median(), Some(8500.0));
+    }
+
+    #[test]
+    fn percentiles() {
+        let stats = example().stats();
+        assert_eq!(stats.percentile(0.0), Some(4000.0));
+        assert_eq!(stats.percentile(100.0), Some(24000.0));
+        assert_eq!(stats.percentile(75.0), Some(11000.0));
+        assert_eq!(stats.percentile(87.5), Some(17500.0));
+        assert_eq!(stats.percentile(101.0), None);
+        assert_eq!(Inventory::default().stats().percentile(50.0), None);
+    }
+
+    #[test]
+    fn histogram() {
+        let bucket = |start, count| Bucket {
+            start,
+            end: start + 5000,
+            count,
+        };
+        assert_eq!(
+            example().stats().histogram(5000),
+            [
+                bucket(0, 1),
+                bucket(5000, 1),
+                bucket(10000, 2),
+                bucket(15000, 0),
+                bucket(20000, 1)
+            ]
+        );
+        assert_eq!(Inventory::default().stats().histogram(10), []);
+    }
+
+    #[test]
+    fn queries() {
+        let inventory = example();
+        let over: Vec<usize> = inventory
+            .carrying_item_over(5000)
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(over, [2, 3, 4]);
+
+        let largest: Vec<Option<i64>> = inventory.largest_items().map(|(_, item)| item).collect();
+        assert_eq!(
+            largest,
+            [Some(3000), Some(4000), Some(6000), Some(9000), Some(10000)]
+        );
+
+        let single: Vec<usize> = inventory
+            .filter(|elf| elf.items().len() == 1)
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(single, [1, 4]);
+    }
+
+    #[test]
+    fn report() {
+        let report = Report::new(&example(), &[50.0], 10_000, Some(9000));
+        assert_eq!(report.elves, 5);
+        assert_eq!(report.percentiles[0].total, Some(10000.0));
+        assert_eq!(report.item_over.as_ref().unwrap().elves, [4]);
+
+        let text = report.to_string();
+        assert!(text.starts_with("Elves: 5\nMean total: 11000.0\n"));
+        assert!(text.contains("Percentile 50: 10000.0\n"));
+        assert!(text.contains("   10000 - 20000         2 "));
+        assert!(text.ends_with("Elves carrying an item of more than 9000 calories: 1\n  elf 4\n"));
+    }
+}
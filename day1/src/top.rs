@@ -0,0 +1,217 @@
+//! The elves carrying the most, found while streaming the inventory
+
+use crate::{Elf, Grouper, Mode, ParseError};
+use serde::Serialize;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::io::{self, BufRead};
+use thiserror::Error;
+
+/// An elf among the top ones and where it stood in the inventory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Ranked {
+    /// Position of the elf in the inventory, counting from 0
+    pub index: usize,
+    /// Calories carried in total
+    pub total: i64,
+}
+
+impl Ord for Ranked {
+    /// More calories rank higher, and of two elves carrying the same the one listed first does
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.total
+            .cmp(&other.total)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl PartialOrd for Ranked {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Keeps the `k` elves carrying the most out of all the elves it is shown
+///
+/// Only `k` elves are held at any time, however many are pushed.
+#[derive(Debug, Clone)]
+pub struct TopElves {
+    k: usize,
+    /// The lowest ranked of the kept elves on top, to be pushed out first
+    heap: BinaryHeap<Reverse<Ranked>>,
+}
+
+impl TopElves {
+    /// Keep the top `k` elves
+    pub fn new(k: usize) -> Self {
+        TopElves {
+            k,
+            heap: BinaryHeap::with_capacity(k + 1),
+        }
+    }
+
+    /// Consider the elf at `index` in the inventory
+    pub fn push(&mut self, index: usize, elf: &Elf) {
+        self.push_ranked(Ranked {
+            index,
+            total: elf.total(),
+        });
+    }
+
+    /// Consider an elf already ranked elsewhere, such as in another part of the inventory
+    pub fn push_ranked(&mut self, ranked: Ranked) {
+        if self.heap.len() < self.k {
+            self.heap.push(Reverse(ranked));
+        } else if self
+            .heap
+            .peek()
+            .is_some_and(|Reverse(lowest)| ranked > *lowest)
+        {
+            self.heap.pop();
+            self.heap.push(Reverse(ranked));
+        }
+    }
+
+    /// The kept elves, the one carrying the most first
+    pub fn into_sorted(self) -> Vec<Ranked> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(ranked)| ranked)
+            .collect()
+    }
+}
+
+/// Why the inventory could not be streamed
+#[derive(Debug, Error)]
+pub enum StreamError {
+    /// Reading failed
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// A line that is not part of a well formed inventory, in strict mode
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+}
+
+/// Stream the inventory from `reader` and return the top `k` elves, the one carrying the most first
+///
+/// Only the elf being read and the top `k` are held in memory. In lenient mode
+/// whatever is skipped is not kept either.
+pub fn top_elves(
+    mut reader: impl BufRead,
+    k: usize,
+    mode: Mode,
+) -> Result<Vec<Ranked>, StreamError> {
+    let mut top = TopElves::new(k);
+    let mut grouper = Grouper::new(mode);
+    let mut line = String::new();
+    let mut index = 0;
+    let mut push = |elf: Elf| {
+        top.push(index, &elf);
+        index += 1;
+    };
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        if let Some(elf) = grouper.feed(&line)? {
+            push(elf);
+        }
+        if mode == Mode::Lenient {
+            grouper.forget_skipped();
+        }
+    }
+    if let (Some(elf), _) = grouper.finish() {
+        push(elf);
+    }
+    Ok(top.into_sorted())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::generate;
+    use common::Answer;
+
+    const EXAMPLE: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000
+";
+
+    fn ranked(index: usize, total: i64) -> Ranked {
+        Ranked { index, total }
+    }
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            top_elves(EXAMPLE.as_bytes(), 3, Mode::Strict).unwrap(),
+            [ranked(3, 24000), ranked(2, 11000), ranked(4, 10000)]
+        );
+    }
+
+    #[test]
+    fn any_k() {
+        assert_eq!(top_elves(EXAMPLE.as_bytes(), 0, Mode::Strict).unwrap(), []);
+        assert_eq!(
+            top_elves(EXAMPLE.as_bytes(), 1, Mode::Strict).unwrap(),
+            [ranked(3, 24000)]
+        );
+        let all = top_elves(EXAMPLE.as_bytes(), 10, Mode::Strict).unwrap();
+        assert_eq!(all.len(), 5);
+        assert_eq!(all.last(), Some(&ranked(1, 4000)));
+    }
+
+    #[test]
+    fn ties_keep_the_first_listed() {
+        let input = "5\n\n7\n\n5\n\n7\n\n5\n";
+        assert_eq!(
+            top_elves(input.as_bytes(), 3, Mode::Strict).unwrap(),
+            [ranked(1, 7), ranked(3, 7), ranked(0, 5)]
+        );
+    }
+
+    #[test]
+    fn without_trailing_newline() {
+        assert_eq!(
+            top_elves("1\n\r\n 2\r\n3".as_bytes(), 2, Mode::Strict).unwrap(),
+            [ranked(1, 5), ranked(0, 1)]
+        );
+    }
+
+    #[test]
+    fn strict_or_lenient() {
+        let input = "1\n\nten\n\n\n2\n";
+        assert!(matches!(
+            top_elves(input.as_bytes(), 3, Mode::Strict),
+            Err(StreamError::Parse(ParseError::InvalidCalories { .. }))
+        ));
+        assert_eq!(
+            top_elves(input.as_bytes(), 3, Mode::Lenient).unwrap(),
+            [ranked(1, 2), ranked(0, 1)]
+        );
+    }
+
+    #[test]
+    fn agrees_with_generated() {
+        for seed in 0..20 {
+            let generated = generate(seed, 200);
+            let top = top_elves(generated.input.as_bytes(), 3, Mode::Strict).unwrap();
+            let totals: Vec<i64> = top.iter().map(|ranked| ranked.total).collect();
+            assert_eq!(generated.part_one, Answer::Number(totals[0]));
+            assert_eq!(generated.part_two, Answer::Number(totals.iter().sum()));
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! `addx`/`noop` programs with known answers
+
+use crate::Day10;
+use common::generate::{rng, Generate, Generated};
+use rand::Rng;
+
+const WIDTH: usize = 40;
+const PIXELS: usize = 240;
+
+/// A program of at least `instructions` instructions, and at least enough to
+/// keep the CPU busy while all 240 pixels are drawn
+pub fn generate(seed: u64, instructions: usize) -> Generated {
+    let mut rng = rng(seed);
+    let mut input = String::new();
+
+    // The value of X during every cycle, starting with the first
+    let mut during = Vec::new();
+    let mut x: i64 = 1;
+    let mut count = 0;
+    while count < instructions || during.len() < PIXELS {
+        count += 1;
+        if rng.random_bool(0.3) {
+            input.push_str("noop\n");
+            during.push(x);
+        } else {
+            // Keep the sprite on or just around the screen
+            let value = rng.random_range(-3 - x..=43 - x);
+            input.push_str(&format!("addx {value}\n"));
+            during.extend([x, x]);
+            x += value;
+        }
+    }
+
+    let strength: i64 = [20, 60, 100, 140, 180, 220]
+        .iter()
+        .map(|&cycle| cycle as i64 * during[cycle - 1])
+        .sum();
+    let rows: Vec<String> = during[..PIXELS]
+        .chunks(WIDTH)
+        .map(|row| {
+            row.iter()
+                .enumerate()
+                .map(|(column, x)| {
+                    if (x - column as i64).abs() <= 1 {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    Generated::new(input, strength, rows.join("\n"))
+}
+
+impl Generate for Day10 {
+    fn generate(seed: u64, size: usize) -> Generated {
+        generate(seed, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solution_agrees() {
+        for seed in 0..20 {
+            let generated = generate(seed, 0);
+            assert_eq!(generated.answers(), generated.solve::<Day10>().unwrap());
+        }
+    }
+
+    #[test]
+    fn longer_programs() {
+        let generated = generate(4, 1000);
+        assert_eq!(1000, generated.input.lines().count());
+        assert_eq!(generated.answers(), generated.solve::<Day10>().unwrap());
+    }
+}
@@ -0,0 +1,587 @@
+//! Day 10: Cathode-Ray Tube, running a tiny CPU that drives a CRT
+#![warn(missing_docs)]
+
+pub mod generator;
+
+use common::{parse_lines, Located, Location, Solution};
+use grid::{Grid, Point};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{BTreeMap, VecDeque};
+use thiserror::Error;
+
+static NOOP_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^noop$").unwrap());
+static ADDX_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^addx\s(\S*)$").unwrap());
+
+/// Why a line of the program could not be read
+///
+/// Every variant holds the `location` of the problem and the `text` found there.
+#[allow(missing_docs)]
+#[derive(Debug, PartialEq, Error)]
+pub enum ParseError {
+    /// A line that is neither `noop` nor `addx`
+    #[error("{location}: `{text}` is not an instruction")]
+    InvalidInstruction { location: Location, text: String },
+    /// An `addx` without a whole number to add
+    #[error("{location}: `{text}` is not a value for addx")]
+    InvalidOperand { location: Location, text: String },
+}
+
+impl Located for ParseError {
+    fn location_mut(&mut self) -> &mut Location {
+        match self {
+            ParseError::InvalidInstruction { location, .. }
+            | ParseError::InvalidOperand { location, .. } => location,
+        }
+    }
+}
+
+/// The CPU of the device, with the single register `X` starting at 1
+pub struct Cpu<'a> {
+    queue: VecDeque<(usize, Instruction)>,
+    cycle: usize,
+    registers: BTreeMap<&'a str, i64>,
+}
+
+impl<'a> Cpu<'a> {
+    /// Run a single cycle, finishing the current instruction if it has taken all its cycles
+    pub fn exec_cycle(&mut self) {
+        let mut done = false;
+        if let Some(instr) = self.queue.get_mut(0) {
+            match instr {
+                (cycles_left, Instruction::Addx(value)) => {
+                    // Takes two cycles
+                    if cycles_left > &mut 1 {
+                        instr.0 -= 1;
+                    } else {
+                        done = true;
+                        if let Some(x) = self.registers.get_mut("X") {
+                            *x += *value;
+                        }
+                    }
+                }
+                (_, Instruction::Noop) => {
+                    // Takes one cycle
+                    done = true;
+                }
+            }
+        }
+        if done {
+            self.queue.pop_front();
+        }
+        self.cycle += 1;
+    }
+
+    /// Replace whatever is left of the current program with `instructions`
+    pub fn load_program(&mut self, instructions: Vec<Instruction>) {
+        let mut queue = VecDeque::with_capacity(instructions.len());
+
+        for instr in instructions {
+            match instr {
+                Instruction::Addx(_value) => {
+                    queue.push_back((2, instr));
+                }
+                Instruction::Noop => {
+                    queue.push_back((1, instr));
+                }
+            }
+        }
+
+        self.queue = queue;
+    }
+
+    /// The value of `register`, if the CPU has one by that name
+    pub fn instrument(&self, register: &str) -> Option<i64> {
+        self.registers.get(register).copied()
+    }
+}
+impl<'a> Default for Cpu<'a> {
+    fn default() -> Self {
+        let mut reg = BTreeMap::new();
+        reg.insert("X", 1);
+
+        Cpu {
+            queue: VecDeque::new(),
+            cycle: 1,
+            registers: reg,
+        }
+    }
+}
+
+/// An instruction of the program
+#[derive(Debug, Copy, Clone)]
+pub enum Instruction {
+    /// Add to `X`, taking two cycles
+    Addx(i64),
+    /// Do nothing for a cycle
+    Noop,
+}
+
+/// Width and height of the CRT in pixels
+const CRT_WIDTH: usize = 40;
+const CRT_HEIGHT: usize = 6;
+
+/// The screen of the device
+#[derive(Debug)]
+pub struct Crt<'a> {
+    /// A single string char per pixel
+    pixels: Grid<&'a str>,
+}
+impl<'a> Crt<'a> {
+    /// Draw the pixel the beam is on during `cycle`, cycles past the last pixel draw nothing
+    pub fn draw(&mut self, cycle: usize, symbol: &'a str) {
+        let Some(pixel) = cycle.checked_sub(1) else {
+            return;
+        };
+        let at = Point::new((pixel % CRT_WIDTH) as isize, (pixel / CRT_WIDTH) as isize);
+        if let Some(pixel) = self.pixels.get_mut(at) {
+            *pixel = symbol;
+        }
+    }
+
+    /// What the screen shows, one string per row
+    pub fn take_screenshot(&self) -> Vec<String> {
+        self.pixels.rows().map(|row| row.concat()).collect()
+    }
+}
+
+impl<'a> Default for Crt<'a> {
+    fn default() -> Self {
+        Crt {
+            pixels: Grid::new(CRT_WIDTH, CRT_HEIGHT, "!"),
+        }
+    }
+}
+
+/// The handheld device, a CPU driving a CRT
+#[derive(Default)]
+pub struct Device<'a> {
+    cpu: Cpu<'a>,
+    monitor: Crt<'a>,
+}
+impl<'a> Device<'a> {
+    /// Run the CPU, drawing a pixel during every cycle before the `cycles`th
+    pub fn run(&mut self, cycles: usize) {
+        for _cycle in 1..cycles {
+            let sprite_center = self.cpu.instrument("X").unwrap();
+            // Position of the beam within its row, counting from 1
+            let cycle_pos = ((self.cpu.cycle - 1) % 40 + 1) as i64;
+            let sprite = Device::get_sprite_for_register(sprite_center, cycle_pos);
+            self.monitor.draw(self.cpu.cycle, sprite);
+            self.cpu.exec_cycle();
+        }
+    }
+
+    /// Load `instructions` into the CPU
+    pub fn load_program(&mut self, instructions: Vec<Instruction>) {
+        self.cpu.load_program(instructions);
+    }
+
+    /// What the CRT shows, one string per row
+    pub fn take_screenshot(&self) -> Vec<String> {
+        self.monitor.take_screenshot()
+    }
+
+    fn get_sprite_for_register(sprite_center: i64, cpu_cycle: i64) -> &'a str {
+        let mut symbol = ".";
+
+        let sprite_start = sprite_center - 1;
+        let sprite_end = sprite_center + 1;
+        if cpu_cycle > sprite_start && cpu_cycle - 1 <= sprite_end {
+            symbol = "#";
+        }
+        symbol
+    }
+}
+impl TryFrom<&str> for Instruction {
+    type Error = ParseError;
+
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        if NOOP_REGEX.is_match(line) {
+            return Ok(Instruction::Noop);
+        }
+        if let Some(captures) = ADDX_REGEX.captures(line) {
+            let value = captures.get(1).unwrap();
+            return value
+                .as_str()
+                .parse::<i64>()
+                .map(Instruction::Addx)
+                .map_err(|_| ParseError::InvalidOperand {
+                    location: Location::column(value.start() + 1),
+                    text: value.as_str().to_string(),
+                });
+        }
+        Err(ParseError::InvalidInstruction {
+            location: Location::column(1),
+            text: line.to_string(),
+        })
+    }
+}
+
+/// Parse a program, one instruction per line
+pub fn asm_from_str(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse_lines(input)
+}
+
+/// Sum of the signal strengths during the 20th, 60th, 100th, 140th, 180th and 220th cycles
+pub fn sum_signal_strengths(program: Vec<Instruction>) -> i64 {
+    let mut cpu = Cpu::default();
+    let mut signal_strengths = Vec::new();
+    cpu.load_program(program);
+
+    for _cycle in 1..220 {
+        cpu.exec_cycle();
+        match cpu.cycle {
+            // During the 20th cycle
+            20 => {
+                signal_strengths.push(cpu.cycle as i64 * cpu.instrument("X").unwrap());
+            }
+            60 => {
+                signal_strengths.push(cpu.cycle as i64 * cpu.instrument("X").unwrap());
+            }
+            100 => {
+                signal_strengths.push(cpu.cycle as i64 * cpu.instrument("X").unwrap());
+            }
+            140 => {
+                signal_strengths.push(cpu.cycle as i64 * cpu.instrument("X").unwrap());
+            }
+            180 => {
+                signal_strengths.push(cpu.cycle as i64 * cpu.instrument("X").unwrap());
+            }
+            220 => {
+                signal_strengths.push(cpu.cycle as i64 * cpu.instrument("X").unwrap());
+            }
+            _ => {}
+        }
+    }
+
+    signal_strengths.iter().sum::<i64>()
+}
+
+/// Cathode-Ray Tube
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Parsed<'a> = Vec<Instruction>;
+    type PartOne = i64;
+    type PartTwo = String;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+        asm_from_str(input)
+    }
+
+    /// Sum of the signal strengths during the 20th, 60th, 100th, 140th, 180th and 220th cycles
+    fn part_one(program: &Vec<Instruction>) -> Result<i64, ParseError> {
+        Ok(sum_signal_strengths(program.clone()))
+    }
+
+    /// What the CRT shows once the program has run, one line per row
+    fn part_two(program: &Vec<Instruction>) -> Result<String, ParseError> {
+        let mut device = Device::default();
+        device.load_program(program.clone());
+        // `run` stops one cycle short of its argument, so ask for 241 to draw all 240 pixels
+        device.run(241);
+        Ok(device.take_screenshot().join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crt_drawing() {
+        let mut device = Device::default();
+        let program = asm_from_str(get_lines()).unwrap();
+        device.cpu.load_program(program);
+        device.run(250);
+
+        let out = device.monitor.take_screenshot();
+        assert_eq!(
+            String::from("##..##..##..##..##..##..##..##..##..##.."),
+            out[0]
+        );
+        assert_eq!(
+            String::from("###...###...###...###...###...###...###."),
+            out[1]
+        );
+        assert_eq!(
+            String::from("####....####....####....####....####...."),
+            out[2]
+        );
+        assert_eq!(
+            String::from("#####.....#####.....#####.....#####....."),
+            out[3]
+        );
+        assert_eq!(
+            String::from("#######.......#######.......#######....."),
+            out[5]
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            ParseError::InvalidOperand {
+                location: Location::new(2, 6),
+                text: String::from("1O"),
+            },
+            asm_from_str("noop\naddx 1O\n").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::InvalidInstruction {
+                location: Location::new(3, 1),
+                text: String::from("subx 4"),
+            },
+            asm_from_str("noop\naddx 1\nsubx 4\n").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn symbol() {
+        assert_eq!("#", Device::get_sprite_for_register(1, 1));
+        assert_eq!("#", Device::get_sprite_for_register(1, 2));
+
+        assert_eq!(".", Device::get_sprite_for_register(16, 3));
+        assert_eq!(".", Device::get_sprite_for_register(16, 4));
+
+        assert_eq!("#", Device::get_sprite_for_register(5, 5));
+        assert_eq!("#", Device::get_sprite_for_register(5, 6));
+
+        assert_eq!(".", Device::get_sprite_for_register(11, 7));
+        assert_eq!(".", Device::get_sprite_for_register(11, 8));
+
+        assert_eq!("#", Device::get_sprite_for_register(8, 9));
+        assert_eq!("#", Device::get_sprite_for_register(8, 10));
+    }
+
+    #[test]
+    fn test_cpu() {
+        let program = vec![
+            Instruction::Noop,
+            Instruction::Addx(3),
+            Instruction::Addx(-5),
+        ];
+
+        let mut cpu = Cpu::default();
+        cpu.load_program(program);
+        assert_eq!(1, cpu.instrument("X").unwrap());
+
+        // At the start of the first cycle, the noop instruction begins execution.
+        // During the first cycle, X is 1. After the first cycle, the noop instruction finishes execution, doing nothing.
+        cpu.exec_cycle();
+        assert_eq!(1, cpu.instrument("X").unwrap());
+
+        // At the start of the second cycle, the addx 3 instruction begins execution. During the second cycle, X is still 1.
+        cpu.exec_cycle();
+        assert_eq!(1, cpu.instrument("X").unwrap());
+
+        // During the third cycle, X is still 1. After the third cycle, the addx 3 instruction finishes execution, setting X to 4.
+        cpu.exec_cycle();
+        assert_eq!(4, cpu.instrument("X").unwrap());
+
+        // At the start of the fourth cycle, the addx -5 instruction begins execution. During the fourth cycle, X is still 4
+        cpu.exec_cycle();
+        assert_eq!(4, cpu.instrument("X").unwrap());
+
+        // During the fifth cycle, X is still 4. After the fifth cycle, the addx -5 instruction finishes execution, setting X to -1.
+        cpu.exec_cycle();
+        assert_eq!(-1, cpu.instrument("X").unwrap());
+    }
+
+    #[test]
+    fn test_instrumentation() {
+        let program = asm_from_str(get_lines()).unwrap();
+
+        let mut signal_strengths = Vec::new();
+        let mut cpu = Cpu::default();
+        cpu.load_program(program);
+
+        for _cycle in 0..220 {
+            cpu.exec_cycle();
+            match cpu.cycle {
+                // During the 20th cycle
+                20 => {
+                    assert_eq!(21, cpu.instrument("X").unwrap());
+                    signal_strengths.push(cpu.cycle as i64 * cpu.instrument("X").unwrap());
+                }
+
+                60 => {
+                    assert_eq!(19, cpu.instrument("X").unwrap());
+                    signal_strengths.push(cpu.cycle as i64 * cpu.instrument("X").unwrap());
+                }
+
+                100 => {
+                    assert_eq!(18, cpu.instrument("X").unwrap());
+                    signal_strengths.push(cpu.cycle as i64 * cpu.instrument("X").unwrap());
+                }
+                140 => {
+                    assert_eq!(21, cpu.instrument("X").unwrap());
+                    signal_strengths.push(cpu.cycle as i64 * cpu.instrument("X").unwrap());
+                }
+                180 => {
+                    assert_eq!(16, cpu.instrument("X").unwrap());
+                    signal_strengths.push(cpu.cycle as i64 * cpu.instrument("X").unwrap());
+                }
+                220 => {
+                    assert_eq!(18, cpu.instrument("X").unwrap());
+                    signal_strengths.push(cpu.cycle as i64 * cpu.instrument("X").unwrap());
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(13140, signal_strengths.iter().sum::<i64>());
+    }
+
+    fn get_lines() -> &'static str {
+        "addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop"
+    }
+}
@@ -0,0 +1,40 @@
+//! Use the CPU and the device from outside of the crate
+
+use day10::{asm_from_str, Cpu, Device, Instruction, ParseError};
+
+#[test]
+fn cpu_cycles() {
+    let mut cpu = Cpu::default();
+    cpu.load_program(asm_from_str("noop\naddx 3\naddx -5\n").unwrap());
+
+    let mut x = Vec::new();
+    for _ in 0..5 {
+        cpu.exec_cycle();
+        x.push(cpu.instrument("X").unwrap());
+    }
+    assert_eq!(x, [1, 1, 4, 4, -1]);
+    assert_eq!(cpu.instrument("Y"), None);
+}
+
+#[test]
+fn device_screenshot() {
+    let mut device = Device::default();
+    device.load_program(vec![Instruction::Noop; 240]);
+    device.run(241);
+
+    let screen = device.take_screenshot();
+    assert_eq!(screen.len(), 6);
+    assert_eq!(screen[0], format!("###{}", ".".repeat(37)));
+}
+
+#[test]
+fn parse_errors() {
+    assert!(matches!(
+        asm_from_str("noop\njump 3\n"),
+        Err(ParseError::InvalidInstruction { .. })
+    ));
+    assert!(matches!(
+        asm_from_str("addx three\n"),
+        Err(ParseError::InvalidOperand { .. })
+    ));
+}
@@ -0,0 +1,301 @@
+//! A breakdown of how a guide plays out, under both readings side by side
+//!
+//! Besides the totals, the breakdown counts the outcomes and the shapes both
+//! players throw, splits the points by shape and outcome, finds the longest
+//! runs of wins and losses, and follows the total round by round.
+
+use crate::rules::Rules;
+use crate::{MatchPair, Outcome, Round};
+use serde::Serialize;
+use std::fmt::{self, Write};
+
+/// Outcomes in the order the breakdown lists them
+pub const OUTCOMES: [Outcome; 3] = [Outcome::Win, Outcome::Draw, Outcome::Lose];
+
+/// Place of `outcome` in `OUTCOMES`
+pub(crate) fn place(outcome: Outcome) -> usize {
+    match outcome {
+        Outcome::Win => 0,
+        Outcome::Draw => 1,
+        Outcome::Lose => 2,
+    }
+}
+
+/// How the rounds of a guide went when played one way
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Breakdown {
+    /// Rounds won, drawn and lost
+    pub outcomes: [usize; 3],
+    /// Times I threw each shape
+    pub mine: Vec<usize>,
+    /// Times the opponent threw each shape
+    pub theirs: Vec<usize>,
+    /// Points from rounds where I threw each shape, won, drawn and lost
+    pub points: Vec<[usize; 3]>,
+    /// Points for the shapes I threw
+    pub shape_points: usize,
+    /// Points for how the rounds ended
+    pub outcome_points: usize,
+    /// Most rounds won in a row
+    pub longest_win_streak: usize,
+    /// Most rounds lost in a row
+    pub longest_loss_streak: usize,
+    /// Total score after each round
+    pub cumulative: Vec<usize>,
+}
+
+impl Breakdown {
+    /// Go through `pairs` in a game of `rules`
+    pub fn new<'r>(rules: &Rules, pairs: impl IntoIterator<Item = MatchPair<'r>>) -> Self {
+        let mut breakdown = Breakdown {
+            mine: vec![0; rules.len()],
+            theirs: vec![0; rules.len()],
+            points: vec![[0; 3]; rules.len()],
+            ..Breakdown::default()
+        };
+        let (mut wins, mut losses) = (0, 0);
+        let mut total = 0;
+        for pair in pairs {
+            let outcome = pair.me.wins_over(&pair.opponent);
+            let score = pair.get_score();
+            breakdown.outcomes[place(outcome)] += 1;
+            breakdown.mine[pair.me.index()] += 1;
+            breakdown.theirs[pair.opponent.index()] += 1;
+            breakdown.points[pair.me.index()][place(outcome)] += score;
+            breakdown.shape_points += pair.me.score();
+            breakdown.outcome_points += score - pair.me.score();
+
+            (wins, losses) = match outcome {
+                Outcome::Win => (wins + 1, 0),
+                Outcome::Lose => (0, losses + 1),
+                Outcome::Draw => (0, 0),
+            };
+            breakdown.longest_win_streak = breakdown.longest_win_streak.max(wins);
+            breakdown.longest_loss_streak = breakdown.longest_loss_streak.max(losses);
+
+            total += score;
+            breakdown.cumulative.push(total);
+        }
+        breakdown
+    }
+
+    /// Total score over every round
+    pub fn total(&self) -> usize {
+        self.shape_points + self.outcome_points
+    }
+}
+
+/// Both readings of a guide, broken down
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Analysis {
+    /// Names of the shapes, in the order the breakdowns count them
+    pub shapes: Vec<String>,
+    /// The second column read as the throw to play
+    pub as_throws: Breakdown,
+    /// The second column read as how the round should end
+    pub as_outcomes: Breakdown,
+}
+
+impl Analysis {
+    /// Break down `rounds` of a game of `rules` both ways
+    pub fn new(rules: &Rules, rounds: &[Round]) -> Self {
+        Analysis {
+            shapes: rules
+                .throws()
+                .map(|throw| throw.name().to_string())
+                .collect(),
+            as_throws: Breakdown::new(rules, rounds.iter().map(|Round(pair, _)| *pair)),
+            as_outcomes: Breakdown::new(
+                rules,
+                rounds
+                    .iter()
+                    .map(|Round(_, strategy)| MatchPair::from(*strategy)),
+            ),
+        }
+    }
+
+    /// Every figure of both breakdowns, a row each
+    ///
+    /// The columns are `section,item,as_throws,as_outcomes`, with one section
+    /// per kind of figure and the total after each round under `cumulative`.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("section,item,as_throws,as_outcomes\n");
+        let mut row = |section: &str, item: &str, figure: &dyn Fn(&Breakdown) -> usize| {
+            let _ = writeln!(
+                csv,
+                "{section},{},{},{}",
+                field(item),
+                figure(&self.as_throws),
+                figure(&self.as_outcomes)
+            );
+        };
+        for (outcome, kind) in ["win", "draw", "lose"].into_iter().enumerate() {
+            row("outcomes", kind, &|breakdown| breakdown.outcomes[outcome]);
+        }
+        for (shape, name) in self.shapes.iter().enumerate() {
+            row("mine", name, &|breakdown| breakdown.mine[shape]);
+        }
+        for (shape, name) in self.shapes.iter().enumerate() {
+            row("theirs", name, &|breakdown| breakdown.theirs[shape]);
+        }
+        for (shape, name) in self.shapes.iter().enumerate() {
+            for (outcome, kind) in ["win", "draw", "lose"].into_iter().enumerate() {
+                row("points", &format!("{name} {kind}"), &|breakdown| {
+                    breakdown.points[shape][outcome]
+                });
+            }
+        }
+        row("points", "shape", &|breakdown| breakdown.shape_points);
+        row("points", "outcome", &|breakdown| breakdown.outcome_points);
+        row("points", "total", &Breakdown::total);
+        row("streaks", "longest_win", &|breakdown| {
+            breakdown.longest_win_streak
+        });
+        row("streaks", "longest_loss", &|breakdown| {
+            breakdown.longest_loss_streak
+        });
+        for round in 0..self.as_throws.cumulative.len() {
+            row("cumulative", &(round + 1).to_string(), &|breakdown| {
+                breakdown.cumulative[round]
+            });
+        }
+        csv
+    }
+}
+
+/// The two breakdowns next to each other, a figure to a line
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let row =
+            |f: &mut fmt::Formatter<'_>, label: &str, figure: &dyn Fn(&Breakdown) -> usize| {
+                writeln!(
+                    f,
+                    "{label:<24} {:>12} {:>12}",
+                    figure(&self.as_throws),
+                    figure(&self.as_outcomes)
+                )
+            };
+        writeln!(f, "{:<24} {:>12} {:>12}", "", "As throws", "As outcomes")?;
+        for (outcome, label) in ["Won", "Drawn", "Lost"].into_iter().enumerate() {
+            row(f, label, &|breakdown| breakdown.outcomes[outcome])?;
+        }
+        for (shape, name) in self.shapes.iter().enumerate() {
+            row(f, &format!("I threw {name}"), &|breakdown| {
+                breakdown.mine[shape]
+            })?;
+        }
+        for (shape, name) in self.shapes.iter().enumerate() {
+            row(f, &format!("They threw {name}"), &|breakdown| {
+                breakdown.theirs[shape]
+            })?;
+        }
+        for (shape, name) in self.shapes.iter().enumerate() {
+            for (outcome, kind) in ["win", "draw", "loss"].into_iter().enumerate() {
+                row(f, &format!("Points, {name} {kind}"), &|breakdown| {
+                    breakdown.points[shape][outcome]
+                })?;
+            }
+        }
+        row(f, "Points for shapes", &|breakdown| breakdown.shape_points)?;
+        row(f, "Points for outcomes", &|breakdown| {
+            breakdown.outcome_points
+        })?;
+        row(f, "Total", &Breakdown::total)?;
+        row(f, "Longest winning streak", &|breakdown| {
+            breakdown.longest_win_streak
+        })?;
+        row(f, "Longest losing streak", &|breakdown| {
+            breakdown.longest_loss_streak
+        })?;
+        // A tenth of the way at a time is enough to see the curves bend
+        let rounds = self.as_throws.cumulative.len();
+        for tenth in 1..=10 {
+            let round = rounds * tenth / 10;
+            if round > 0 {
+                row(f, &format!("Total after round {round}"), &|breakdown| {
+                    breakdown.cumulative[round - 1]
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `text` as a CSV field, quoted when it has to be
+fn field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::CLASSIC;
+    use crate::Day2;
+    use common::Solution;
+
+    fn sample() -> Analysis {
+        Analysis::new(&CLASSIC, &Day2::parse("A Y\nB X\nC Z\nA Y\n").unwrap())
+    }
+
+    #[test]
+    fn both_readings() {
+        let analysis = sample();
+        let throws = &analysis.as_throws;
+        assert_eq!(throws.outcomes, [2, 1, 1]);
+        assert_eq!(throws.mine, [1, 2, 1]);
+        assert_eq!(throws.theirs, [2, 1, 1]);
+        assert_eq!(throws.points[1], [16, 0, 0]);
+        assert_eq!((throws.shape_points, throws.outcome_points), (8, 15));
+        assert_eq!(throws.cumulative, [8, 9, 15, 23]);
+        assert_eq!(throws.longest_win_streak, 1);
+        assert_eq!(throws.longest_loss_streak, 1);
+
+        let outcomes = &analysis.as_outcomes;
+        assert_eq!(outcomes.outcomes, [1, 2, 1]);
+        assert_eq!(outcomes.mine, [4, 0, 0]);
+        assert_eq!(outcomes.cumulative, [4, 5, 12, 16]);
+        assert_eq!(outcomes.total(), 16);
+    }
+
+    #[test]
+    fn streaks() {
+        let rounds = Day2::parse("C X\nC X\nB Y\nA Z\nA Z\nA Z\n").unwrap();
+        let breakdown = Analysis::new(&CLASSIC, &rounds).as_throws;
+        assert_eq!(breakdown.longest_win_streak, 2);
+        assert_eq!(breakdown.longest_loss_streak, 3);
+        assert_eq!(Analysis::new(&CLASSIC, &[]).as_throws.longest_win_streak, 0);
+    }
+
+    #[test]
+    fn csv() {
+        let csv = sample().to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "section,item,as_throws,as_outcomes");
+        assert!(lines.contains(&"outcomes,win,2,1"));
+        assert!(lines.contains(&"mine,Rock,1,4"));
+        assert!(lines.contains(&"points,Paper win,16,0"));
+        assert!(lines.contains(&"points,total,23,16"));
+        assert_eq!(lines.last(), Some(&"cumulative,4,23,16"));
+        // 3 outcomes, 3 shapes each way and 9 by shape, 3 points, 2 streaks and 4 rounds
+        assert_eq!(lines.len(), 1 + 3 + 6 + 9 + 3 + 2 + 4);
+        assert_eq!(field("a,b"), "\"a,b\"");
+    }
+
+    #[test]
+    fn as_text() {
+        let text = sample().to_string();
+        let row = |label, throws, outcomes| format!("{label:<24} {throws:>12} {outcomes:>12}");
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], row("", "As throws", "As outcomes"));
+        assert!(lines.contains(&row("Total", "23", "16").as_str()));
+        // The curve ends on the total after the last round
+        assert_eq!(
+            lines.last(),
+            Some(&row("Total after round 4", "23", "16").as_str())
+        );
+    }
+}
@@ -0,0 +1,253 @@
+//! Players that pick their own throws, to pit strategies against each other
+//!
+//! A bot sees every round played so far in the match and picks its next throw
+//! from that alone. Bots that counter a prediction play the shape worth the
+//! most that beats it.
+
+use crate::rules::Rules;
+use crate::{Outcome, Round, StrategyChoice, Throw};
+use common::generate::Rng;
+use rand::Rng as _;
+
+/// A round of a match as one of its players saw it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Played<'r> {
+    /// What the bot threw
+    pub mine: Throw<'r>,
+    /// What the other bot threw
+    pub theirs: Throw<'r>,
+}
+
+/// A strategy for playing a match
+pub trait Bot {
+    /// What the bot is called in score tables
+    fn name(&self) -> &str;
+
+    /// The throw for the next round, after the rounds in `history`
+    fn throw<'r>(&mut self, rules: &'r Rules, history: &[Played<'r>], rng: &mut Rng) -> Throw<'r>;
+}
+
+/// Any throw of the game, each as likely
+fn any<'r>(rules: &'r Rules, rng: &mut Rng) -> Throw<'r> {
+    Throw::new(rules, rng.random_range(0..rules.len()))
+}
+
+/// The throw beating `predicted` that is worth the most
+fn counter<'r>(predicted: Throw<'r>) -> Throw<'r> {
+    predicted.get_desired(StrategyChoice::Win)
+}
+
+/// Plays the throws of a guide in order, starting over once it runs out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedGuide {
+    name: String,
+    shapes: Vec<usize>,
+}
+
+impl FixedGuide {
+    /// Plays the shapes at `shapes` in the list of the rules
+    ///
+    /// Panics if `shapes` is empty.
+    pub fn new(name: impl Into<String>, shapes: Vec<usize>) -> Self {
+        assert!(!shapes.is_empty(), "a guide needs at least one throw");
+        FixedGuide {
+            name: name.into(),
+            shapes,
+        }
+    }
+
+    /// Plays the second column of a strategy guide, read as the throws to make
+    pub fn from_rounds(name: impl Into<String>, rounds: &[Round]) -> Self {
+        Self::new(
+            name,
+            rounds
+                .iter()
+                .map(|Round(pair, _)| pair.me.index())
+                .collect(),
+        )
+    }
+}
+
+impl Bot for FixedGuide {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn throw<'r>(&mut self, rules: &'r Rules, history: &[Played<'r>], _: &mut Rng) -> Throw<'r> {
+        Throw::new(
+            rules,
+            self.shapes[history.len() % self.shapes.len()] % rules.len(),
+        )
+    }
+}
+
+/// Throws any shape, each as likely
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Random;
+
+impl Bot for Random {
+    fn name(&self) -> &str {
+        "random"
+    }
+
+    fn throw<'r>(&mut self, rules: &'r Rules, _: &[Played<'r>], rng: &mut Rng) -> Throw<'r> {
+        any(rules, rng)
+    }
+}
+
+/// Counters the shape the other bot has thrown most
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Frequency;
+
+impl Bot for Frequency {
+    fn name(&self) -> &str {
+        "frequency"
+    }
+
+    fn throw<'r>(&mut self, rules: &'r Rules, history: &[Played<'r>], rng: &mut Rng) -> Throw<'r> {
+        let mut counts = vec![0usize; rules.len()];
+        for played in history {
+            counts[played.theirs.index()] += 1;
+        }
+        // The first shape wins a tie, so the bot is as predictable as its history
+        match (0..rules.len()).rev().max_by_key(|&shape| counts[shape]) {
+            Some(shape) if !history.is_empty() => counter(Throw::new(rules, shape)),
+            _ => any(rules, rng),
+        }
+    }
+}
+
+/// Counters the shape the other bot has most often thrown after its last one
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Markov;
+
+impl Bot for Markov {
+    fn name(&self) -> &str {
+        "markov"
+    }
+
+    fn throw<'r>(&mut self, rules: &'r Rules, history: &[Played<'r>], rng: &mut Rng) -> Throw<'r> {
+        let Some(last) = history.last() else {
+            return any(rules, rng);
+        };
+        let mut counts = vec![0usize; rules.len()];
+        for pair in history.windows(2) {
+            if pair[0].theirs == last.theirs {
+                counts[pair[1].theirs.index()] += 1;
+            }
+        }
+        match (0..rules.len()).rev().max_by_key(|&shape| counts[shape]) {
+            Some(shape) if counts[shape] > 0 => counter(Throw::new(rules, shape)),
+            _ => any(rules, rng),
+        }
+    }
+}
+
+/// Throws the same shape again after a win, and otherwise what would have beaten the other bot
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WinStayLoseShift;
+
+impl Bot for WinStayLoseShift {
+    fn name(&self) -> &str {
+        "win-stay-lose-shift"
+    }
+
+    fn throw<'r>(&mut self, rules: &'r Rules, history: &[Played<'r>], rng: &mut Rng) -> Throw<'r> {
+        match history.last() {
+            Some(last) if last.mine.wins_over(&last.theirs) == Outcome::Win => last.mine,
+            Some(last) => counter(last.theirs),
+            None => any(rules, rng),
+        }
+    }
+}
+
+/// One of each bot, the fixed guide playing `guide`
+pub fn every_bot(guide: FixedGuide) -> Vec<Box<dyn Bot>> {
+    vec![
+        Box::new(guide),
+        Box::new(Random),
+        Box::new(Frequency),
+        Box::new(Markov),
+        Box::new(WinStayLoseShift),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::CLASSIC;
+    use common::generate::rng;
+
+    /// `history` as seen by a bot always throwing rock
+    fn against(theirs: &[Throw<'static>]) -> Vec<Played<'static>> {
+        theirs
+            .iter()
+            .map(|&theirs| Played {
+                mine: Throw::ROCK,
+                theirs,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fixed_guide_repeats() {
+        let mut bot = FixedGuide::new("guide", vec![0, 2]);
+        let history = against(&[Throw::ROCK; 3]);
+        let throws: Vec<Throw> = (0..4)
+            .map(|played| bot.throw(&CLASSIC, &history[..played.min(3)], &mut rng(0)))
+            .collect();
+        assert_eq!(
+            throws,
+            [Throw::ROCK, Throw::SCISSORS, Throw::ROCK, Throw::SCISSORS]
+        );
+    }
+
+    #[test]
+    fn frequency_counters_the_favourite() {
+        let history = against(&[Throw::PAPER, Throw::ROCK, Throw::PAPER]);
+        assert_eq!(
+            Frequency.throw(&CLASSIC, &history, &mut rng(0)),
+            Throw::SCISSORS
+        );
+    }
+
+    #[test]
+    fn markov_counters_what_follows() {
+        // Rock has always been followed by scissors
+        let history = against(&[
+            Throw::ROCK,
+            Throw::SCISSORS,
+            Throw::PAPER,
+            Throw::ROCK,
+            Throw::SCISSORS,
+            Throw::ROCK,
+        ]);
+        assert_eq!(Markov.throw(&CLASSIC, &history, &mut rng(0)), Throw::ROCK);
+    }
+
+    #[test]
+    fn win_stay_lose_shift() {
+        let won = against(&[Throw::SCISSORS]);
+        assert_eq!(
+            WinStayLoseShift.throw(&CLASSIC, &won, &mut rng(0)),
+            Throw::ROCK
+        );
+        let lost = against(&[Throw::PAPER]);
+        assert_eq!(
+            WinStayLoseShift.throw(&CLASSIC, &lost, &mut rng(0)),
+            Throw::SCISSORS
+        );
+    }
+
+    #[test]
+    fn random_is_seeded() {
+        let throws = |seed| -> Vec<Throw> {
+            let mut rng = rng(seed);
+            (0..20)
+                .map(|_| Random.throw(&CLASSIC, &[], &mut rng))
+                .collect()
+        };
+        assert_eq!(throws(4), throws(4));
+        assert_ne!(throws(4), throws(5));
+    }
+}
@@ -0,0 +1,278 @@
+//! Every way the second column of the guide could be meant
+//!
+//! The elf never said what X, Y and Z stand for. Read as throws they could be
+//! any of the 6 orders of the three shapes, and read as outcomes any of the 6
+//! orders of lose, draw and win. Scoring the guide under all 12 shows how much
+//! hangs on the reading.
+
+use crate::rules::Rules;
+use crate::{MatchPair, MatchStrategy, Round, StrategyChoice, Throw};
+use serde::Serialize;
+use std::fmt;
+
+/// Every order of three things
+const ORDERS: [[usize; 3]; 6] = [
+    [0, 1, 2],
+    [0, 2, 1],
+    [1, 0, 2],
+    [1, 2, 0],
+    [2, 0, 1],
+    [2, 1, 0],
+];
+
+/// What each of the three symbols of the second column stands for, in order
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Reading<'r> {
+    /// The throw to play
+    Throws([Throw<'r>; 3]),
+    /// How the round should end
+    Outcomes([StrategyChoice; 3]),
+}
+
+impl fmt::Display for Reading<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reading::Throws(throws) => {
+                write!(f, "throws")?;
+                for throw in throws {
+                    write!(f, " {}", throw.name())?;
+                }
+            }
+            Reading::Outcomes(choices) => {
+                write!(f, "outcomes")?;
+                for choice in choices {
+                    write!(f, " {}", format!("{choice:?}").to_lowercase())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A reading of the guide and the total score it comes to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interpretation<'r> {
+    /// What the symbols stand for
+    pub reading: Reading<'r>,
+    /// Total score of the guide read this way
+    pub total: usize,
+}
+
+/// Every reading of the guide, the highest scoring first
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decoding<'r> {
+    ranked: Vec<Interpretation<'r>>,
+}
+
+impl<'r> Decoding<'r> {
+    /// Every reading, the highest total first, ties in the order throws then outcomes
+    pub fn ranked(&self) -> &[Interpretation<'r>] {
+        &self.ranked
+    }
+
+    /// The reading scoring the most
+    pub fn best(&self) -> &Interpretation<'r> {
+        &self.ranked[0]
+    }
+
+    /// The reading scoring the least
+    pub fn worst(&self) -> &Interpretation<'r> {
+        &self.ranked[self.ranked.len() - 1]
+    }
+
+    /// Average total, the score to expect when every reading is as likely
+    pub fn expected(&self) -> f64 {
+        let sum: usize = self.ranked.iter().map(|found| found.total).sum();
+        sum as f64 / self.ranked.len() as f64
+    }
+}
+
+/// Score `rounds` of a game of `rules` under every reading of the second column
+///
+/// The symbols of the second column are told apart by the throw each was read
+/// as in `rounds`, so for the puzzle guide they are X, Y and Z in that order.
+/// Only games of three shapes, one for each outcome, can be read both ways.
+pub fn decode<'r>(rules: &'r Rules, rounds: &[Round<'r>]) -> Option<Decoding<'r>> {
+    if rules.len() != 3 {
+        return None;
+    }
+    // How often each symbol is played against each shape
+    let mut counts = [[0usize; 3]; 3];
+    for Round(pair, _) in rounds {
+        counts[pair.opponent.index()][pair.me.index()] += 1;
+    }
+    let throw = |shape| Throw::new(rules, shape);
+    let total = |score: &dyn Fn(Throw<'r>, usize) -> usize| {
+        (0..3)
+            .flat_map(|theirs| (0..3).map(move |symbol| (theirs, symbol)))
+            .map(|(theirs, symbol)| counts[theirs][symbol] * score(throw(theirs), symbol))
+            .sum()
+    };
+
+    let as_throws = ORDERS.iter().map(|order| {
+        let throws = order.map(throw);
+        Interpretation {
+            reading: Reading::Throws(throws),
+            total: total(&|theirs, symbol| MatchPair::new(theirs, throws[symbol]).get_score()),
+        }
+    });
+    let choices = [
+        StrategyChoice::Lose,
+        StrategyChoice::Draw,
+        StrategyChoice::Win,
+    ];
+    let as_outcomes = ORDERS.iter().map(|order| {
+        let choices = order.map(|choice| choices[choice]);
+        Interpretation {
+            reading: Reading::Outcomes(choices),
+            total: total(&|theirs, symbol| {
+                MatchPair::from(MatchStrategy::new(theirs, choices[symbol])).get_score()
+            }),
+        }
+    });
+
+    let mut ranked: Vec<Interpretation> = as_throws.chain(as_outcomes).collect();
+    ranked.sort_by_key(|found| std::cmp::Reverse(found.total));
+    Some(Decoding { ranked })
+}
+
+/// A reading of the second column and its total, as the report shows it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Ranked {
+    /// What the symbols stand for, in words
+    pub reading: String,
+    /// Total score of the guide read this way
+    pub total: usize,
+}
+
+impl From<&Interpretation<'_>> for Ranked {
+    fn from(from: &Interpretation<'_>) -> Self {
+        Ranked {
+            reading: from.reading.to_string(),
+            total: from.total,
+        }
+    }
+}
+
+/// What the decode mode shows about the guide
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    /// The reading scoring the most
+    pub best: Ranked,
+    /// The reading scoring the least
+    pub worst: Ranked,
+    /// Average total over every reading
+    pub expected: f64,
+    /// Every reading, the highest total first
+    pub ranked: Vec<Ranked>,
+}
+
+impl From<&Decoding<'_>> for Report {
+    fn from(decoding: &Decoding<'_>) -> Self {
+        Report {
+            best: decoding.best().into(),
+            worst: decoding.worst().into(),
+            expected: decoding.expected(),
+            ranked: decoding.ranked().iter().map(Ranked::from).collect(),
+        }
+    }
+}
+
+/// The best and worst readings, then every reading by rank
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Best: {} scores {}", self.best.reading, self.best.total)?;
+        writeln!(
+            f,
+            "Worst: {} scores {}",
+            self.worst.reading, self.worst.total
+        )?;
+        writeln!(f, "Expected: {:.1}", self.expected)?;
+        for (rank, ranked) in self.ranked.iter().enumerate() {
+            writeln!(f, "#{:<2} {:>6} {}", rank + 1, ranked.total, ranked.reading)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::generate;
+    use crate::rules::CLASSIC;
+    use crate::Day2;
+    use common::Solution;
+
+    #[test]
+    fn sample() {
+        let rounds = Day2::parse("A Y\nB X\nC Z\n").unwrap();
+        let decoding = decode(&CLASSIC, &rounds).unwrap();
+        assert_eq!(decoding.ranked().len(), 12);
+        let totals: Vec<usize> = decoding.ranked().iter().map(|found| found.total).collect();
+        assert!(totals.windows(2).all(|pair| pair[0] >= pair[1]));
+
+        // The two readings of the puzzle are among them
+        let reading = |reading| {
+            decoding
+                .ranked()
+                .iter()
+                .find(|found| found.reading == reading)
+                .unwrap()
+                .total
+        };
+        let classic = Reading::Throws([Throw::ROCK, Throw::PAPER, Throw::SCISSORS]);
+        assert_eq!(reading(classic), 15);
+        let outcomes = Reading::Outcomes([
+            StrategyChoice::Lose,
+            StrategyChoice::Draw,
+            StrategyChoice::Win,
+        ]);
+        assert_eq!(reading(outcomes), 12);
+        assert_eq!(outcomes.to_string(), "outcomes lose draw win");
+
+        // Winning every round
+        assert_eq!(decoding.best().total, 24);
+        assert_eq!(
+            decoding.best().reading,
+            Reading::Throws([Throw::SCISSORS, Throw::PAPER, Throw::ROCK])
+        );
+        let sum: usize = totals.iter().sum();
+        assert_eq!(decoding.expected(), sum as f64 / 12.0);
+        assert!(decoding.worst().total <= 12);
+    }
+
+    #[test]
+    fn agrees_with_the_generator() {
+        for seed in 0..10 {
+            let generated = generate(seed, 50);
+            let rounds = Day2::parse(&generated.input).unwrap();
+            let decoding = decode(&CLASSIC, &rounds).unwrap();
+            for answer in [&generated.part_one, &generated.part_two] {
+                assert!(decoding
+                    .ranked()
+                    .iter()
+                    .any(|found| found.total.to_string() == answer.to_string()));
+            }
+        }
+    }
+
+    #[test]
+    fn empty_guide() {
+        let decoding = decode(&CLASSIC, &[]).unwrap();
+        assert_eq!(decoding.best().total, 0);
+        assert_eq!(decoding.expected(), 0.0);
+        assert_eq!(decode(&Rules::seven(), &[]), None);
+    }
+
+    #[test]
+    fn report() {
+        let rounds = Day2::parse("A Y\nB X\nC Z\n").unwrap();
+        let report = Report::from(&decode(&CLASSIC, &rounds).unwrap());
+        let text = report.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 3 + 12);
+        assert_eq!(lines[0], "Best: throws Scissors Paper Rock scores 24");
+        assert_eq!(lines[3], "#1      24 throws Scissors Paper Rock");
+        assert_eq!(report.ranked[0], report.best);
+    }
+}
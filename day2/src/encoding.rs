@@ -0,0 +1,662 @@
+//! How a strategy guide writes its columns, read from a rules file
+//!
+//! Guides from different sources use different letters, and tournaments
+//! score rounds differently. A rules file names the game, the points for
+//! every shape and outcome, and what each symbol of either column stands for:
+//!
+//! ```toml
+//! game = "classic"
+//!
+//! [scores]
+//! lose = 0
+//! draw = 3
+//! win = 6
+//!
+//! [opponent]
+//! A = "Rock"
+//! B = "Paper"
+//! C = "Scissors"
+//!
+//! [me.throws]
+//! X = "Rock"
+//! Y = "Paper"
+//! Z = "Scissors"
+//!
+//! [me.outcomes]
+//! X = "lose"
+//! Y = "draw"
+//! Z = "win"
+//! ```
+//!
+//! Instead of a `game`, the file can list its own `shapes`, each beating the
+//! half of the others listed right after it unless `beats` pairs say
+//! otherwise, with a score for every shape under `[scores.shapes]`.
+
+use crate::rules::{OutcomeScores, Rules, RulesError, Shape};
+use crate::{
+    columns, score_as_outcomes, score_as_throws, Day2, MatchPair, MatchStrategy, ParseError, Round,
+    StrategyChoice, Throw,
+};
+use common::report::{self, Part};
+use common::{column_of, Answer, Located, Location, Report, Solution};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Why a rules file does not describe a game and its encoding
+#[derive(Debug, Error)]
+pub enum EncodingError {
+    /// The file could not be read
+    #[error("could not read {}: {source}", path.display())]
+    Io {
+        /// The file
+        path: PathBuf,
+        /// What went wrong
+        source: io::Error,
+    },
+    /// The file is not TOML of the expected shape
+    #[error("invalid rules file: {0}")]
+    Toml(#[from] toml::de::Error),
+    /// The shapes do not make a fair game
+    #[error("invalid game: {0}")]
+    Rules(#[from] RulesError),
+    /// Both a `game` and its `shapes` were given
+    #[error("a rules file names either a `game` or its `shapes`, not both")]
+    GameAndShapes,
+    /// Neither a `game` nor its `shapes` were given
+    #[error("a rules file needs a `game` or a list of `shapes`")]
+    NoGame,
+    /// `beats` pairs for a `game` that already knows which shape beats which
+    #[error("`beats` only goes with a list of `shapes`")]
+    BeatsWithoutShapes,
+    /// A `game` that is not one of the presets
+    #[error("there is no game called `{0}`")]
+    UnknownGame(String),
+    /// A listed shape without a score
+    #[error("no score for `{0}`")]
+    MissingScore(String),
+    /// A symbol that is empty or would be split up when reading a line
+    #[error("{column}: `{symbol}` is empty or holds whitespace")]
+    InvalidSymbol {
+        /// The column the symbol is for
+        column: &'static str,
+        /// The symbol
+        symbol: String,
+    },
+    /// A symbol standing for a shape that is not in the game
+    #[error("{column}: `{symbol}` stands for `{shape}`, which is not a shape of the game")]
+    UnknownShape {
+        /// The column the symbol is for
+        column: &'static str,
+        /// The symbol
+        symbol: String,
+        /// The shape it stands for
+        shape: String,
+    },
+    /// Two symbols of a column standing for the same shape
+    #[error("{column}: both `{first}` and `{second}` stand for `{shape}`")]
+    Conflict {
+        /// The column the symbols are for
+        column: &'static str,
+        /// The first of the symbols
+        first: String,
+        /// The second of the symbols
+        second: String,
+        /// The shape both stand for
+        shape: String,
+    },
+    /// A shape or outcome no symbol of a column stands for
+    #[error("{column}: no symbol stands for `{target}`")]
+    Incomplete {
+        /// The column missing a symbol
+        column: &'static str,
+        /// The shape or outcome left out
+        target: String,
+    },
+    /// A symbol of the second column read as a throw but not as an outcome, or the other way around
+    #[error("`{symbol}` is in `{found}` but not in `{missing}`")]
+    Mismatched {
+        /// The symbol
+        symbol: String,
+        /// The mapping it is in
+        found: &'static str,
+        /// The mapping it is missing from
+        missing: &'static str,
+    },
+}
+
+/// An outcome as a rules file writes it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum OutcomeName {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl From<OutcomeName> for StrategyChoice {
+    fn from(from: OutcomeName) -> Self {
+        match from {
+            OutcomeName::Lose => StrategyChoice::Lose,
+            OutcomeName::Draw => StrategyChoice::Draw,
+            OutcomeName::Win => StrategyChoice::Win,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesFile {
+    game: Option<String>,
+    shapes: Option<Vec<String>>,
+    beats: Option<Vec<(String, String)>>,
+    #[serde(default)]
+    scores: ScoresFile,
+    opponent: BTreeMap<String, String>,
+    me: MeFile,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScoresFile {
+    #[serde(default)]
+    shapes: BTreeMap<String, usize>,
+    lose: Option<usize>,
+    draw: Option<usize>,
+    win: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MeFile {
+    throws: BTreeMap<String, String>,
+    outcomes: BTreeMap<String, OutcomeName>,
+}
+
+/// A game and what the symbols of a strategy guide stand for in it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Encoding {
+    rules: Rules,
+    /// Shape of the game for every symbol of the first column
+    opponent: BTreeMap<String, usize>,
+    /// Shape of the game for every symbol of the second column
+    throws: BTreeMap<String, usize>,
+    /// Outcome for every symbol of the second column
+    outcomes: BTreeMap<String, StrategyChoice>,
+}
+
+impl Encoding {
+    /// The classic game with A, B and C, then X, Y and Z
+    pub fn classic() -> Self {
+        let letters = |letters: [&str; 3]| {
+            letters
+                .iter()
+                .enumerate()
+                .map(|(shape, letter)| (letter.to_string(), shape))
+                .collect()
+        };
+        Encoding {
+            rules: Rules::classic().clone(),
+            opponent: letters(["A", "B", "C"]),
+            throws: letters(["X", "Y", "Z"]),
+            outcomes: [
+                ("X", StrategyChoice::Lose),
+                ("Y", StrategyChoice::Draw),
+                ("Z", StrategyChoice::Win),
+            ]
+            .into_iter()
+            .map(|(letter, choice)| (letter.to_string(), choice))
+            .collect(),
+        }
+    }
+
+    /// Read the rules file at `path`
+    pub fn load(path: &Path) -> Result<Self, EncodingError> {
+        let text = fs::read_to_string(path).map_err(|source| EncodingError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        text.parse()
+    }
+
+    /// The game the guide is played in
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    /// Read a single line of the guide both ways
+    pub fn parse_round(&self, line: &str) -> Result<Round<'_>, ParseError> {
+        let (opponent, me) = columns(line)?;
+        let location = |column: &str| Location::column(column_of(line, column));
+        let invalid = |column: &str| ParseError::InvalidThrow {
+            location: location(column),
+            text: column.to_string(),
+        };
+        let throw = |symbols: &BTreeMap<String, usize>, column: &str| {
+            symbols
+                .get(column)
+                .map(|&shape| Throw::new(&self.rules, shape))
+                .ok_or_else(|| invalid(column))
+        };
+        let opponent = throw(&self.opponent, opponent)?;
+        let choice = self
+            .outcomes
+            .get(me)
+            .ok_or_else(|| ParseError::InvalidChoice {
+                location: location(me),
+                text: me.to_string(),
+            })?;
+        Ok(Round(
+            MatchPair::new(opponent, throw(&self.throws, me)?),
+            MatchStrategy::new(opponent, *choice),
+        ))
+    }
+
+    /// Read every round of the guide, skipping empty lines
+    pub fn parse_guide(&self, input: &str) -> Result<Vec<Round<'_>>, ParseError> {
+        input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(index, line)| self.parse_round(line).map_err(|err| err.on_line(index + 1)))
+            .collect()
+    }
+
+    /// Read the guide with these symbols and score each of `parts` the way `Day2` would
+    pub fn solve(&self, input: &str, parts: &[Part]) -> Result<Vec<Report>, ParseError> {
+        report::solve_with(
+            Day2::DAY,
+            input,
+            parts,
+            |input| self.parse_guide(input),
+            |part, rounds| {
+                Ok(Answer::new(match part {
+                    Part::One => score_as_throws(rounds),
+                    Part::Two => score_as_outcomes(rounds),
+                }))
+            },
+        )
+    }
+
+    /// A line of a guide for `pair`, its second column read as the throw to play
+    ///
+    /// Where a shape has several symbols, the first in order is used.
+    pub fn write_throws(&self, pair: &MatchPair) -> Option<String> {
+        Some(format!(
+            "{} {}",
+            symbol(&self.opponent, &pair.opponent.index())?,
+            symbol(&self.throws, &pair.me.index())?
+        ))
+    }
+
+    /// A line of a guide for `strategy`, its second column read as how the round should end
+    pub fn write_outcomes(&self, strategy: &MatchStrategy) -> Option<String> {
+        Some(format!(
+            "{} {}",
+            symbol(&self.opponent, &strategy.opponent.index())?,
+            symbol(&self.outcomes, &strategy.me)?
+        ))
+    }
+
+    fn from_file(file: RulesFile) -> Result<Self, EncodingError> {
+        let mut rules = match (file.game, file.shapes) {
+            (Some(_), Some(_)) => return Err(EncodingError::GameAndShapes),
+            (None, None) => return Err(EncodingError::NoGame),
+            (Some(game), None) => {
+                if file.beats.is_some() {
+                    return Err(EncodingError::BeatsWithoutShapes);
+                }
+                preset(&game).ok_or(EncodingError::UnknownGame(game))?
+            }
+            (None, Some(names)) => {
+                let shapes = names
+                    .into_iter()
+                    .map(|name| {
+                        let score = *file
+                            .scores
+                            .shapes
+                            .get(&name)
+                            .ok_or_else(|| EncodingError::MissingScore(name.clone()))?;
+                        Ok(Shape::new(name, score))
+                    })
+                    .collect::<Result<Vec<_>, EncodingError>>()?;
+                match &file.beats {
+                    Some(beats) => Rules::new(
+                        shapes,
+                        beats
+                            .iter()
+                            .map(|(winner, loser)| (winner.as_str(), loser.as_str())),
+                    )?,
+                    None => Rules::cyclic(shapes)?,
+                }
+            }
+        };
+        for (name, &score) in &file.scores.shapes {
+            rules = rules.with_shape_score(name, score)?;
+        }
+        let classic = rules.outcome_scores();
+        let rules = rules.with_outcome_scores(OutcomeScores {
+            lose: file.scores.lose.unwrap_or(classic.lose),
+            draw: file.scores.draw.unwrap_or(classic.draw),
+            win: file.scores.win.unwrap_or(classic.win),
+        });
+
+        let opponent = shape_symbols(&rules, "opponent", file.opponent)?;
+        let throws = shape_symbols(&rules, "me.throws", file.me.throws)?;
+        let outcomes = outcome_symbols(file.me.outcomes)?;
+        // Every line is read both ways, so both need the same symbols
+        let mismatched = |symbol: &String, found, missing| EncodingError::Mismatched {
+            symbol: symbol.clone(),
+            found,
+            missing,
+        };
+        if let Some(symbol) = throws.keys().find(|&symbol| !outcomes.contains_key(symbol)) {
+            return Err(mismatched(symbol, "me.throws", "me.outcomes"));
+        }
+        if let Some(symbol) = outcomes.keys().find(|&symbol| !throws.contains_key(symbol)) {
+            return Err(mismatched(symbol, "me.outcomes", "me.throws"));
+        }
+
+        Ok(Encoding {
+            rules,
+            opponent,
+            throws,
+            outcomes,
+        })
+    }
+}
+
+impl Default for Encoding {
+    fn default() -> Self {
+        Encoding::classic()
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = EncodingError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Encoding::from_file(toml::from_str(text)?)
+    }
+}
+
+/// The preset game called `name`
+fn preset(name: &str) -> Option<Rules> {
+    match name {
+        "classic" => Some(Rules::classic().clone()),
+        "rock-paper-scissors-lizard-spock" => Some(Rules::rock_paper_scissors_lizard_spock()),
+        "seven" => Some(Rules::seven()),
+        "nine" => Some(Rules::nine()),
+        _ => None,
+    }
+}
+
+/// The first symbol standing for `meaning`
+fn symbol<'a, T: PartialEq>(symbols: &'a BTreeMap<String, T>, meaning: &T) -> Option<&'a str> {
+    symbols
+        .iter()
+        .find(|(_, other)| *other == meaning)
+        .map(|(symbol, _)| symbol.as_str())
+}
+
+/// Symbols that would survive splitting a line on whitespace
+fn check_symbol(column: &'static str, symbol: &str) -> Result<(), EncodingError> {
+    if symbol.is_empty() || symbol.contains(char::is_whitespace) {
+        return Err(EncodingError::InvalidSymbol {
+            column,
+            symbol: symbol.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// A symbol for each shape of `rules`, and no shape with two
+fn shape_symbols(
+    rules: &Rules,
+    column: &'static str,
+    symbols: BTreeMap<String, String>,
+) -> Result<BTreeMap<String, usize>, EncodingError> {
+    let mut seen: Vec<Option<String>> = vec![None; rules.len()];
+    let mut shapes = BTreeMap::new();
+    for (symbol, name) in symbols {
+        check_symbol(column, &symbol)?;
+        let shape = rules
+            .throw(&name)
+            .ok_or_else(|| EncodingError::UnknownShape {
+                column,
+                symbol: symbol.clone(),
+                shape: name.clone(),
+            })?
+            .index();
+        if let Some(first) = seen[shape].replace(symbol.clone()) {
+            return Err(EncodingError::Conflict {
+                column,
+                first,
+                second: symbol,
+                shape: name,
+            });
+        }
+        shapes.insert(symbol, shape);
+    }
+    if let Some(shape) = seen.iter().position(Option::is_none) {
+        return Err(EncodingError::Incomplete {
+            column,
+            target: rules.shapes()[shape].name().to_string(),
+        });
+    }
+    Ok(shapes)
+}
+
+/// A symbol for each outcome
+///
+/// Games of more than three shapes have more symbols in the second column
+/// than there are outcomes, so an outcome may have several.
+fn outcome_symbols(
+    symbols: BTreeMap<String, OutcomeName>,
+) -> Result<BTreeMap<String, StrategyChoice>, EncodingError> {
+    const COLUMN: &str = "me.outcomes";
+    for symbol in symbols.keys() {
+        check_symbol(COLUMN, symbol)?;
+    }
+    for (outcome, name) in [
+        (OutcomeName::Lose, "lose"),
+        (OutcomeName::Draw, "draw"),
+        (OutcomeName::Win, "win"),
+    ] {
+        if !symbols.values().any(|&found| found == outcome) {
+            return Err(EncodingError::Incomplete {
+                column: COLUMN,
+                target: name.to_string(),
+            });
+        }
+    }
+    Ok(symbols
+        .into_iter()
+        .map(|(symbol, outcome)| (symbol, outcome.into()))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLASSIC: &str = include_str!("../rules/classic.toml");
+    const LIZARD_SPOCK: &str = include_str!("../rules/lizard-spock.toml");
+
+    fn error(text: &str) -> EncodingError {
+        text.parse::<Encoding>().unwrap_err()
+    }
+
+    /// The classic file with `from` replaced by `to`
+    fn classic_with(from: &str, to: &str) -> String {
+        assert!(CLASSIC.contains(from));
+        CLASSIC.replacen(from, to, 1)
+    }
+
+    #[test]
+    fn classic_file() {
+        let encoding: Encoding = CLASSIC.parse().unwrap();
+        assert_eq!(encoding, Encoding::classic());
+        let rounds = encoding.parse_guide("A Y\nB X\n\nC Z\n").unwrap();
+        assert_eq!(score_as_throws(&rounds), 15);
+        assert_eq!(score_as_outcomes(&rounds), 12);
+    }
+
+    #[test]
+    fn solved_as_reports() {
+        let reports = Encoding::classic()
+            .solve(
+                "A Y
+B X
+C Z
+",
+                &Part::BOTH,
+            )
+            .unwrap();
+        assert_eq!(reports[0].answer, Answer::Number(15));
+        assert_eq!(reports[1].answer, Answer::Number(12));
+        assert_eq!(reports[1].day, Day2::DAY);
+    }
+
+    #[test]
+    fn own_symbols_and_scores() {
+        let encoding: Encoding = LIZARD_SPOCK.parse().unwrap();
+        assert_eq!(encoding.rules().len(), 5);
+        // Spock beats rock, and paper beats spock
+        let rounds = encoding.parse_guide("1 >>\n5 <\n").unwrap();
+        assert_eq!(score_as_throws(&rounds), 5 + 2 + 2 + 2);
+        // Spock is the best win against rock, and scissors the best loss against spock
+        assert_eq!(score_as_outcomes(&rounds), 5 + 2 + 3);
+    }
+
+    #[test]
+    fn written_lines_read_back() {
+        let encoding: Encoding = LIZARD_SPOCK.parse().unwrap();
+        for line in ["1 >>", "5 <", "3 ="] {
+            let Round(pair, strategy) = encoding.parse_round(line).unwrap();
+            let read = encoding.parse_round(&encoding.write_throws(&pair).unwrap());
+            assert_eq!(read.unwrap().0, pair);
+            let read = encoding.parse_round(&encoding.write_outcomes(&strategy).unwrap());
+            assert_eq!(read.unwrap().1, strategy);
+        }
+        let classic = Encoding::classic();
+        let Round(pair, strategy) = classic.parse_round("C Z").unwrap();
+        assert_eq!(classic.write_throws(&pair).as_deref(), Some("C Z"));
+        assert_eq!(classic.write_outcomes(&strategy).as_deref(), Some("C Z"));
+    }
+
+    #[test]
+    fn preset_scores_can_be_changed() {
+        let text = CLASSIC.replace("[opponent]", "[scores.shapes]\nRock = 7\n\n[opponent]");
+        let encoding: Encoding = text.parse().unwrap();
+        let rounds = encoding.parse_guide("C X\n").unwrap();
+        assert_eq!(score_as_throws(&rounds), 7 + 6);
+
+        let text = CLASSIC.replace("[opponent]", "[scores.shapes]\nFire = 7\n\n[opponent]");
+        assert!(matches!(
+            error(&text),
+            EncodingError::Rules(RulesError::UnknownShape(shape)) if shape == "Fire"
+        ));
+    }
+
+    #[test]
+    fn guide_errors_point_at_the_symbol() {
+        let encoding: Encoding = LIZARD_SPOCK.parse().unwrap();
+        assert_eq!(
+            encoding.parse_guide("1 >>\n2  <>\n").unwrap_err(),
+            ParseError::InvalidChoice {
+                location: Location::new(2, 4),
+                text: String::from("<>"),
+            }
+        );
+        assert_eq!(
+            encoding.parse_guide("A <\n").unwrap_err(),
+            ParseError::InvalidThrow {
+                location: Location::new(1, 1),
+                text: String::from("A"),
+            }
+        );
+    }
+
+    #[test]
+    fn which_game() {
+        assert!(matches!(
+            error(&classic_with("game = \"classic\"", "")),
+            EncodingError::NoGame
+        ));
+        assert!(matches!(
+            error(&classic_with(
+                "game = \"classic\"",
+                "game = \"classic\"\nshapes = [\"Rock\"]"
+            )),
+            EncodingError::GameAndShapes
+        ));
+        assert!(matches!(
+            error(&classic_with("\"classic\"", "\"eight\"")),
+            EncodingError::UnknownGame(game) if game == "eight"
+        ));
+        assert!(matches!(
+            error(&classic_with(
+                "game = \"classic\"",
+                "game = \"classic\"\nbeats = [[\"Rock\", \"Paper\"]]"
+            )),
+            EncodingError::BeatsWithoutShapes
+        ));
+        assert!(matches!(
+            error(&LIZARD_SPOCK.replacen("Spock = 5\n", "", 1)),
+            EncodingError::MissingScore(shape) if shape == "Spock"
+        ));
+        assert!(matches!(
+            error(&LIZARD_SPOCK.replacen("\"Paper\", \"Spock\"", "\"Paper\"", 1)),
+            EncodingError::Rules(RulesError::EvenShapes(4))
+        ));
+        assert!(matches!(
+            error(&classic_with("[scores]", "[score]")),
+            EncodingError::Toml(_)
+        ));
+    }
+
+    #[test]
+    fn conflicting_symbols() {
+        assert!(matches!(
+            error(&classic_with("C = \"Scissors\"", "C = \"Rock\"")),
+            EncodingError::Conflict { column: "opponent", first, second, shape }
+                if first == "A" && second == "C" && shape == "Rock"
+        ));
+        assert!(matches!(
+            error(&classic_with("C = \"Scissors\"", "C = \"Lizard\"")),
+            EncodingError::UnknownShape { column: "opponent", symbol, shape }
+                if symbol == "C" && shape == "Lizard"
+        ));
+        assert!(matches!(
+            error(&classic_with("X = \"Rock\"", "\"X X\" = \"Rock\"")),
+            EncodingError::InvalidSymbol { column: "me.throws", symbol } if symbol == "X X"
+        ));
+    }
+
+    #[test]
+    fn incomplete_symbols() {
+        assert!(matches!(
+            error(&classic_with("B = \"Paper\"\n", "")),
+            EncodingError::Incomplete { column: "opponent", target } if target == "Paper"
+        ));
+        assert!(matches!(
+            error(&classic_with("Z = \"win\"", "Z = \"draw\"")),
+            EncodingError::Incomplete { column: "me.outcomes", target } if target == "win"
+        ));
+        assert!(matches!(
+            error(&classic_with("Z = \"win\"", "W = \"win\"")),
+            EncodingError::Mismatched { symbol, found: "me.throws", missing: "me.outcomes" }
+                if symbol == "Z"
+        ));
+        assert!(matches!(
+            error(&classic_with("Y = \"draw\"", "Y = \"draw\"\nW = \"draw\"")),
+            EncodingError::Mismatched { symbol, found: "me.outcomes", missing: "me.throws" }
+                if symbol == "W"
+        ));
+    }
+}
@@ -0,0 +1,327 @@
+//! Playing against a strategy guide from the terminal
+//!
+//! The opponent replays the first column of a guide while a person types in
+//! their throws. A session can be saved part way through and picked up again
+//! later from the same round.
+
+use crate::rules::Rules;
+use crate::{MatchPair, Round, Throw};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Why a game could not go on
+#[derive(Debug, Error)]
+pub enum GameError {
+    /// A save file could not be read or written
+    #[error("could not use {}: {source}", path.display())]
+    Io {
+        /// The save file
+        path: PathBuf,
+        /// What went wrong
+        source: io::Error,
+    },
+    /// Reading from or writing to the player failed
+    #[error("terminal: {0}")]
+    Terminal(#[from] io::Error),
+    /// A save file that is not a session
+    #[error("invalid save file: {0}")]
+    Save(#[from] serde_json::Error),
+    /// A saved throw of a shape the game does not have
+    #[error("the session has a `{0}`, which is not a shape of the game")]
+    UnknownShape(String),
+    /// A session with more throws made than the opponent has
+    #[error("the session has {played} throws made for {rounds} rounds")]
+    TooManyThrows {
+        /// Throws made by the player
+        played: usize,
+        /// Rounds the opponent has throws for
+        rounds: usize,
+    },
+}
+
+/// How a call to `play` ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ending {
+    /// Every round was played
+    Finished,
+    /// The player asked to save the session to carry on later
+    Saved,
+    /// The player quit, or the input ran out
+    Quit,
+}
+
+/// The opponent's throws and the player's throws so far, by the names of their shapes
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    opponent: Vec<String>,
+    played: Vec<String>,
+}
+
+/// Points of both sides over the rounds played
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Totals {
+    /// The player's points
+    pub player: usize,
+    /// The opponent's points
+    pub opponent: usize,
+}
+
+impl Totals {
+    fn add(&mut self, pair: MatchPair) {
+        self.player += pair.get_score();
+        self.opponent += MatchPair::new(pair.me, pair.opponent).get_score();
+    }
+}
+
+impl Session {
+    /// A fresh session against an opponent throwing `opponent` in turn
+    pub fn new<'r>(opponent: impl IntoIterator<Item = Throw<'r>>) -> Self {
+        Session {
+            opponent: opponent
+                .into_iter()
+                .map(|throw| throw.name().to_string())
+                .collect(),
+            played: Vec::new(),
+        }
+    }
+
+    /// A fresh session against the first column of a guide
+    pub fn from_rounds(rounds: &[Round]) -> Self {
+        Self::new(rounds.iter().map(|Round(pair, _)| pair.opponent))
+    }
+
+    /// Read a session saved to `path`
+    pub fn load(path: &Path) -> Result<Self, GameError> {
+        let text = fs::read_to_string(path).map_err(|source| GameError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    /// Save the session to `path` to pick up later
+    pub fn save(&self, path: &Path) -> Result<(), GameError> {
+        fs::write(path, serde_json::to_string_pretty(self)?).map_err(|source| GameError::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Rounds in the whole game
+    pub fn rounds(&self) -> usize {
+        self.opponent.len()
+    }
+
+    /// Rounds played so far
+    pub fn played(&self) -> usize {
+        self.played.len()
+    }
+
+    /// Every round played so far in a game of `rules`
+    pub fn pairs<'r>(&self, rules: &'r Rules) -> Result<Vec<MatchPair<'r>>, GameError> {
+        if self.played() > self.rounds() {
+            return Err(GameError::TooManyThrows {
+                played: self.played(),
+                rounds: self.rounds(),
+            });
+        }
+        let throw = |name: &String| {
+            rules
+                .throw(name)
+                .ok_or_else(|| GameError::UnknownShape(name.clone()))
+        };
+        self.opponent
+            .iter()
+            .zip(&self.played)
+            .map(|(opponent, me)| Ok(MatchPair::new(throw(opponent)?, throw(me)?)))
+            .collect()
+    }
+
+    /// Points of both sides over the rounds played so far
+    pub fn totals(&self, rules: &Rules) -> Result<Totals, GameError> {
+        let mut totals = Totals::default();
+        for pair in self.pairs(rules)? {
+            totals.add(pair);
+        }
+        Ok(totals)
+    }
+}
+
+/// The throw the player meant by `text`: a shape's name in any case, or its place in the list
+fn read_throw<'r>(rules: &'r Rules, text: &str) -> Option<Throw<'r>> {
+    if let Ok(place) = text.parse::<usize>() {
+        return rules.throws().nth(place.checked_sub(1)?);
+    }
+    rules
+        .throws()
+        .find(|throw| throw.name().eq_ignore_ascii_case(text))
+}
+
+/// Play the rest of `session` in a game of `rules`, reading throws from `input`
+///
+/// Besides a throw, the player can type `save` to stop so the session can be
+/// saved, or `quit` to stop for good. Every round shows the outcome, the
+/// points it was worth and the totals so far.
+pub fn play(
+    rules: &Rules,
+    session: &mut Session,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> Result<Ending, GameError> {
+    let mut totals = session.totals(rules)?;
+    let names: Vec<&str> = rules.throws().map(|throw| throw.name()).collect();
+    if session.played() > 0 {
+        writeln!(
+            output,
+            "Resuming at round {} of {}, you {} to {}",
+            session.played() + 1,
+            session.rounds(),
+            totals.player,
+            totals.opponent
+        )?;
+    }
+
+    let mut line = String::new();
+    while session.played() < session.rounds() {
+        write!(
+            output,
+            "Round {} of {}, throw {}, save or quit: ",
+            session.played() + 1,
+            session.rounds(),
+            names.join(", ")
+        )?;
+        output.flush()?;
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+            return Ok(Ending::Quit);
+        }
+        let text = line.trim();
+        let me = match text {
+            "save" => return Ok(Ending::Saved),
+            "quit" => return Ok(Ending::Quit),
+            _ => match read_throw(rules, text) {
+                Some(me) => me,
+                None => {
+                    writeln!(output, "`{text}` is not a throw of the game")?;
+                    continue;
+                }
+            },
+        };
+
+        let opponent = rules
+            .throw(&session.opponent[session.played()])
+            .ok_or_else(|| GameError::UnknownShape(session.opponent[session.played()].clone()))?;
+        let pair = MatchPair::new(opponent, me);
+        totals.add(pair);
+        session.played.push(me.name().to_string());
+        writeln!(
+            output,
+            "{} against {}: {:?}, {} points, you {} to {}",
+            me.name(),
+            opponent.name(),
+            me.wins_over(&opponent),
+            pair.get_score(),
+            totals.player,
+            totals.opponent
+        )?;
+    }
+    writeln!(
+        output,
+        "Game over, you {} to {}",
+        totals.player, totals.opponent
+    )?;
+    Ok(Ending::Finished)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::CLASSIC;
+    use crate::Day2;
+    use common::Solution;
+
+    fn sample() -> Session {
+        Session::from_rounds(&Day2::parse("A Y\nB X\nC Z\n").unwrap())
+    }
+
+    /// Play `script` as the player's input, returning how it ended and what was shown
+    fn run(session: &mut Session, script: &str) -> (Ending, String) {
+        let mut output = Vec::new();
+        let ending = play(&CLASSIC, session, script.as_bytes(), &mut output).unwrap();
+        (ending, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn a_whole_game() {
+        let mut session = sample();
+        // The throws of the first part of the puzzle
+        let (ending, shown) = run(&mut session, "paper\nRock\n3\n");
+        assert_eq!(ending, Ending::Finished);
+        assert!(shown.contains("Paper against Rock: Win, 8 points, you 8 to 1"));
+        assert!(shown.contains("Rock against Paper: Lose, 1 points, you 9 to 9"));
+        assert!(shown.ends_with("Game over, you 15 to 15\n"));
+        assert_eq!(
+            session.totals(&CLASSIC).unwrap(),
+            Totals {
+                player: Day2::part_one(&Day2::parse("A Y\nB X\nC Z\n").unwrap()).unwrap(),
+                opponent: 15,
+            }
+        );
+    }
+
+    #[test]
+    fn asks_again_after_a_bad_throw() {
+        let mut session = sample();
+        let (ending, shown) = run(&mut session, "lizard\n0\nscissors\n");
+        assert_eq!(ending, Ending::Quit);
+        assert!(shown.contains("`lizard` is not a throw of the game"));
+        assert!(shown.contains("`0` is not a throw of the game"));
+        assert_eq!(session.played(), 1);
+    }
+
+    #[test]
+    fn save_and_resume() {
+        let mut session = sample();
+        let (ending, _) = run(&mut session, "paper\nsave\nrock\n");
+        assert_eq!(ending, Ending::Saved);
+        assert_eq!(session.played(), 1);
+
+        let path = std::env::temp_dir().join(format!("day2-session-{}.json", std::process::id()));
+        session.save(&path).unwrap();
+        let mut resumed = Session::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(resumed, session);
+
+        let (ending, shown) = run(&mut resumed, "rock\nscissors\n");
+        assert_eq!(ending, Ending::Finished);
+        assert!(shown.starts_with("Resuming at round 2 of 3, you 8 to 1\n"));
+        assert!(shown.ends_with("Game over, you 15 to 15\n"));
+    }
+
+    #[test]
+    fn bad_sessions() {
+        let session: Session =
+            serde_json::from_str(r#"{"opponent": ["Rock"], "played": ["Lizard"]}"#).unwrap();
+        assert!(matches!(
+            session.totals(&CLASSIC),
+            Err(GameError::UnknownShape(shape)) if shape == "Lizard"
+        ));
+        let session: Session =
+            serde_json::from_str(r#"{"opponent": [], "played": ["Rock"]}"#).unwrap();
+        assert!(matches!(
+            session.totals(&CLASSIC),
+            Err(GameError::TooManyThrows {
+                played: 1,
+                rounds: 0
+            })
+        ));
+        assert!(matches!(
+            Session::load(Path::new("/nonexistent/session.json")),
+            Err(GameError::Io { .. })
+        ));
+    }
+}
@@ -0,0 +1,58 @@
+//! Strategy guides with known answers
+
+use crate::Day2;
+use common::generate::{rng, Generate, Generated};
+use rand::Rng;
+
+/// A guide of `rounds` rounds
+pub fn generate(seed: u64, rounds: usize) -> Generated {
+    let mut rng = rng(seed);
+    let mut input = String::new();
+    let (mut as_throw, mut as_outcome) = (0, 0);
+    for _ in 0..rounds {
+        // Rock, paper and scissors are 0, 1 and 2, each beats the one before it
+        let theirs: u32 = rng.random_range(0..3);
+        let column: u32 = rng.random_range(0..3);
+        input.push_str(&format!(
+            "{} {}\n",
+            char::from(b'A' + theirs as u8),
+            char::from(b'X' + column as u8)
+        ));
+
+        // Read as a throw: X, Y and Z are rock, paper and scissors
+        let outcome = (column + 4 - theirs) % 3;
+        as_throw += column + 1 + outcome * 3;
+
+        // Read as an outcome: X, Y and Z are lose, draw and win
+        let mine = (theirs + column + 2) % 3;
+        as_outcome += mine + 1 + column * 3;
+    }
+
+    Generated::new(input, as_throw, as_outcome)
+}
+
+impl Generate for Day2 {
+    fn generate(seed: u64, size: usize) -> Generated {
+        generate(seed, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solution_agrees() {
+        for seed in 0..20 {
+            let generated = generate(seed, 100);
+            assert_eq!(generated.answers(), generated.solve::<Day2>().unwrap());
+        }
+    }
+
+    #[test]
+    fn empty_guide() {
+        let generated = generate(3, 0);
+        assert_eq!("", generated.input);
+        assert_eq!(generated.answers(), generated.solve::<Day2>().unwrap());
+    }
+}
@@ -0,0 +1,536 @@
+//! Day 2: Rock Paper Scissors, scoring a strategy guide for the tournament
+#![warn(missing_docs)]
+
+pub mod analysis;
+pub mod bots;
+pub mod decode;
+pub mod encoding;
+pub mod game;
+pub mod generator;
+pub mod predict;
+pub mod rules;
+pub mod synthesis;
+pub mod tournament;
+
+use common::{column_of, parse_lines, Located, Location, Solution};
+use rules::{Rules, CLASSIC};
+use std::fmt;
+use thiserror::Error;
+
+/// Why a line of the strategy guide could not be read
+///
+/// Every variant holds the `location` of the problem and the `text` found there.
+#[allow(missing_docs)]
+#[derive(Debug, PartialEq, Error)]
+pub enum ParseError {
+    /// A column that should be A, B, C, X, Y or Z
+    #[error("{location}: `{text}` is not a throw")]
+    InvalidThrow { location: Location, text: String },
+    /// A second column that should be X, Y or Z
+    #[error("{location}: `{text}` is not a strategy")]
+    InvalidChoice { location: Location, text: String },
+    /// A line with fewer than two columns
+    #[error("{location}: expected two columns in `{text}`")]
+    MissingColumn { location: Location, text: String },
+    /// A line with more than two columns
+    #[error("{location}: unexpected `{text}` after the second column")]
+    TrailingInput { location: Location, text: String },
+}
+
+impl Located for ParseError {
+    fn location_mut(&mut self) -> &mut Location {
+        match self {
+            ParseError::InvalidThrow { location, .. }
+            | ParseError::InvalidChoice { location, .. }
+            | ParseError::MissingColumn { location, .. }
+            | ParseError::TrailingInput { location, .. } => location,
+        }
+    }
+}
+
+/// Split a line of the guide into its two columns
+fn columns(line: &str) -> Result<(&str, &str), ParseError> {
+    let mut parts = line.split_whitespace();
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(first), Some(second), None) => Ok((first, second)),
+        (_, _, Some(extra)) => Err(ParseError::TrailingInput {
+            location: Location::column(column_of(line, extra)),
+            text: extra.to_string(),
+        }),
+        _ => Err(ParseError::MissingColumn {
+            location: Location::column(line.len() + 1),
+            text: line.to_string(),
+        }),
+    }
+}
+
+/// Parse a single column of `line`, fixing up the column of any error
+fn parse_column<'a, T>(line: &str, column: &'a str) -> Result<T, ParseError>
+where
+    T: TryFrom<&'a str, Error = ParseError>,
+{
+    T::try_from(column).map_err(|err| err.shifted(column_of(line, column) - 1))
+}
+
+/// A hand thrown in a match, one of the shapes of its `Rules`
+#[derive(Copy, Clone)]
+pub struct Throw<'r> {
+    rules: &'r Rules,
+    shape: usize,
+}
+
+impl Throw<'static> {
+    /// A or X, beats scissors
+    pub const ROCK: Throw<'static> = Throw {
+        rules: &CLASSIC,
+        shape: 0,
+    };
+    /// B or Y, beats rock
+    pub const PAPER: Throw<'static> = Throw {
+        rules: &CLASSIC,
+        shape: 1,
+    };
+    /// C or Z, beats paper
+    pub const SCISSORS: Throw<'static> = Throw {
+        rules: &CLASSIC,
+        shape: 2,
+    };
+}
+
+impl PartialEq for Throw<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.shape == other.shape
+            && (std::ptr::eq(self.rules, other.rules) || self.rules == other.rules)
+    }
+}
+
+impl fmt::Debug for Throw<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Throw").field(&self.name()).finish()
+    }
+}
+
+/// Input parsing
+impl TryFrom<&str> for Throw<'static> {
+    type Error = ParseError;
+
+    fn try_from(from: &str) -> Result<Self, Self::Error> {
+        match from {
+            "A" => Ok(Throw::ROCK),
+            "B" => Ok(Throw::PAPER),
+            "C" => Ok(Throw::SCISSORS),
+            "X" => Ok(Throw::ROCK),
+            "Y" => Ok(Throw::PAPER),
+            "Z" => Ok(Throw::SCISSORS),
+            _ => Err(ParseError::InvalidThrow {
+                location: Location::column(1),
+                text: from.to_string(),
+            }),
+        }
+    }
+}
+
+impl<'r> Throw<'r> {
+    /// The shape at `shape` in the list of `rules`
+    ///
+    /// Panics if the game has no such shape.
+    pub fn new(rules: &'r Rules, shape: usize) -> Self {
+        assert!(shape < rules.len(), "there are only {} shapes", rules.len());
+        Throw { rules, shape }
+    }
+
+    /// The rules of the game the throw is made in
+    pub fn rules(&self) -> &'r Rules {
+        self.rules
+    }
+
+    /// Position of the shape in the list of the rules
+    pub fn index(&self) -> usize {
+        self.shape
+    }
+
+    /// What the shape is called
+    pub fn name(&self) -> &'r str {
+        self.rules.shape(self.shape).name()
+    }
+
+    /// Points for throwing the shape
+    pub fn score(&self) -> usize {
+        self.rules.shape(self.shape).score()
+    }
+
+    /// Generate an `Outcome` comparing against another `Throw`
+    pub fn wins_over(&self, other: &Throw) -> Outcome {
+        debug_assert!(self.rules == other.rules, "throws of different games");
+        if self.rules.beats(self.shape, other.shape) {
+            Outcome::Win
+        } else if self.rules.beats(other.shape, self.shape) {
+            Outcome::Lose
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    /// Generate a `Throw` based on what we want to achieve via a `StrategyChoice`
+    ///
+    /// When several shapes would do, as in games of more than three shapes,
+    /// the one worth the most is thrown.
+    pub fn get_desired(&self, desired: StrategyChoice) -> Throw<'r> {
+        let wanted = match desired {
+            StrategyChoice::Lose => Outcome::Lose,
+            StrategyChoice::Draw => Outcome::Draw,
+            StrategyChoice::Win => Outcome::Win,
+        };
+        self.rules
+            .throws()
+            .filter(|mine| mine.wins_over(self) == wanted)
+            .max_by_key(|mine| (mine.score(), std::cmp::Reverse(mine.shape)))
+            .expect("a fair game has a throw for every outcome")
+    }
+}
+
+/// Possible outcomes of a match
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// Worth 6 points in the classic game
+    Win,
+    /// Worth no points in the classic game
+    Lose,
+    /// Worth 3 points in the classic game
+    Draw,
+}
+
+/// A strategy for a match
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MatchStrategy<'r> {
+    opponent: Throw<'r>,
+    me: StrategyChoice,
+}
+
+impl<'r> MatchStrategy<'r> {
+    /// Play for `me` against what the `opponent` throws
+    pub fn new(opponent: Throw<'r>, me: StrategyChoice) -> Self {
+        MatchStrategy { opponent, me }
+    }
+
+    /// Look at what the opponent is playing and generate a `Throw` based on desired outcome
+    pub fn get_choice_based_on_strategy(&self) -> Throw<'r> {
+        self.opponent.get_desired(self.me)
+    }
+}
+
+/// Input parsing
+impl TryFrom<&str> for MatchStrategy<'static> {
+    type Error = ParseError;
+
+    fn try_from(from: &str) -> Result<Self, Self::Error> {
+        let (opponent, me) = columns(from)?;
+        Ok(Self {
+            opponent: parse_column(from, opponent)?,
+            me: parse_column(from, me)?,
+        })
+    }
+}
+
+/// How we would like the game to end
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum StrategyChoice {
+    /// X; we would like to lose
+    Lose,
+    /// Y; we would like to end in a draw
+    Draw,
+    /// Z; we would like ot win
+    Win,
+}
+
+/// input parsing
+impl TryFrom<&str> for StrategyChoice {
+    type Error = ParseError;
+
+    fn try_from(from: &str) -> Result<Self, Self::Error> {
+        match from {
+            "X" => Ok(StrategyChoice::Lose),
+            "Y" => Ok(StrategyChoice::Draw),
+            "Z" => Ok(StrategyChoice::Win),
+            _ => Err(ParseError::InvalidChoice {
+                location: Location::column(1),
+                text: from.to_string(),
+            }),
+        }
+    }
+}
+
+/// A pair of hand-`Throw`s makes up a round/match
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MatchPair<'r> {
+    /// What my adversary is playing
+    opponent: Throw<'r>,
+    /// What I am playing
+    me: Throw<'r>,
+}
+
+impl<'r> MatchPair<'r> {
+    /// A round where I play `me` against what the `opponent` throws
+    pub fn new(opponent: Throw<'r>, me: Throw<'r>) -> Self {
+        MatchPair { opponent, me }
+    }
+
+    /// What the opponent throws
+    pub fn opponent(&self) -> Throw<'r> {
+        self.opponent
+    }
+
+    /// The score for a single round is the score for the shape you selected (1 for Rock, 2 for Paper, and 3 for Scissors)i
+    /// Score for the outcome of the round (0 if you lost, 3 if the round was a draw, and 6 if you won).
+    ///
+    /// Games with rules of their own score shapes and outcomes as those rules say.
+    pub fn get_score(&self) -> usize {
+        let choice_score = self.me.score();
+
+        let outcome = self.me.wins_over(&self.opponent);
+        let match_score = self.me.rules().outcome_scores().score(outcome);
+
+        choice_score + match_score
+    }
+}
+
+/// If we have an opponents hand and a strategy we would want to execute, we can create a `MatchPair`
+impl<'r> From<MatchStrategy<'r>> for MatchPair<'r> {
+    fn from(from: MatchStrategy<'r>) -> Self {
+        Self {
+            opponent: from.opponent,
+            me: from.get_choice_based_on_strategy(),
+        }
+    }
+}
+
+/// Input parsing
+impl TryFrom<&str> for MatchPair<'static> {
+    type Error = ParseError;
+
+    fn try_from(from: &str) -> Result<Self, Self::Error> {
+        let (opponent, me) = columns(from)?;
+        Ok(Self {
+            opponent: parse_column(from, opponent)?,
+            me: parse_column(from, me)?,
+        })
+    }
+}
+
+/// Both readings of a single round of the strategy guide
+#[derive(Debug, Copy, Clone)]
+pub struct Round<'r>(pub MatchPair<'r>, pub MatchStrategy<'r>);
+
+impl TryFrom<&str> for Round<'static> {
+    type Error = ParseError;
+
+    fn try_from(from: &str) -> Result<Self, Self::Error> {
+        Ok(Round(
+            MatchPair::try_from(from)?,
+            MatchStrategy::try_from(from)?,
+        ))
+    }
+}
+
+/// Total score when the second column is the throw to play
+pub fn score_as_throws(rounds: &[Round]) -> usize {
+    rounds.iter().map(|Round(game, _)| game.get_score()).sum()
+}
+
+/// Total score when the second column is how the round should end
+pub fn score_as_outcomes(rounds: &[Round]) -> usize {
+    rounds
+        .iter()
+        .map(|Round(_, strategy)| MatchPair::from(*strategy).get_score())
+        .sum()
+}
+
+/// Rock Paper Scissors
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    /// Every round of the guide, read both as two throws and as a throw and a strategy
+    type Parsed<'a> = Vec<Round<'static>>;
+    type PartOne = usize;
+    type PartTwo = usize;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Vec<Round<'static>>, ParseError> {
+        parse_lines(input)
+    }
+
+    /// Total score when the second column is the throw to play
+    fn part_one(rounds: &Vec<Round<'static>>) -> Result<usize, ParseError> {
+        Ok(score_as_throws(rounds))
+    }
+
+    /// Total score when the second column is how the round should end
+    fn part_two(rounds: &Vec<Round<'static>>) -> Result<usize, ParseError> {
+        Ok(score_as_outcomes(rounds))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn solution_on_sample() {
+        let rounds = Day2::parse("A Y\nB X\nC Z\n").unwrap();
+        assert_eq!(Ok(15), Day2::part_one(&rounds));
+        assert_eq!(Ok(12), Day2::part_two(&rounds));
+    }
+
+    #[test]
+    fn am_i_choosing_correct_strategy_for_a_y() {
+        let matchstr = "A Y";
+        let matchstrat = MatchStrategy::try_from(matchstr).unwrap();
+        let game = MatchPair::from(matchstrat);
+
+        assert_eq!(game.get_score(), 4);
+    }
+
+    #[test]
+    fn am_i_choosing_correct_strategy_for_b_x() {
+        let matchstr = "B X";
+        let matchstrat = MatchStrategy::try_from(matchstr).unwrap();
+        let game = MatchPair::from(matchstrat);
+
+        assert_eq!(game.get_score(), 1);
+    }
+
+    #[test]
+    fn am_i_choosing_correct_strategy_for_c_z() {
+        let matchstr = "C Z";
+        let matchstrat = MatchStrategy::try_from(matchstr).unwrap();
+        let game = MatchPair::from(matchstrat);
+
+        assert_eq!(game.get_score(), 7);
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_column() {
+        assert_eq!(
+            Err(ParseError::InvalidThrow {
+                location: Location::column(3),
+                text: String::from("Q"),
+            }),
+            MatchPair::try_from("A Q")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidChoice {
+                location: Location::column(4),
+                text: String::from("C"),
+            }),
+            MatchStrategy::try_from("B  C")
+        );
+        assert_eq!(
+            Err(ParseError::MissingColumn {
+                location: Location::column(2),
+                text: String::from("A"),
+            }),
+            MatchPair::try_from("A")
+        );
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_line() {
+        let err = Day2::parse("A Y\nB X\nC W\n").err().unwrap();
+        assert_eq!(
+            ParseError::InvalidThrow {
+                location: Location::new(3, 3),
+                text: String::from("W"),
+            },
+            err
+        );
+        assert_eq!("line 3, column 3: `W` is not a throw", err.to_string());
+    }
+
+    #[test]
+    fn am_i_winning_with_rock() {
+        let me = Throw::ROCK;
+        let opponent = Throw::SCISSORS;
+        let game = MatchPair { opponent, me };
+        // I should win
+        assert_eq!(Outcome::Win, me.wins_over(&opponent));
+
+        assert_eq!(game.get_score(), 7);
+    }
+
+    #[test]
+    fn am_i_losing_with_rock() {
+        let me = Throw::ROCK;
+        let opponent = Throw::PAPER;
+        let game = MatchPair { opponent, me };
+        // I should lose
+        assert_eq!(Outcome::Lose, me.wins_over(&opponent));
+
+        // I get 1 since i chose rock
+        assert_eq!(game.get_score(), 1);
+    }
+
+    #[test]
+    fn am_i_drawing_with_paper() {
+        let me = Throw::PAPER;
+        let opponent = Throw::PAPER;
+        let game = MatchPair { opponent, me };
+        // I should lose
+        assert_eq!(Outcome::Draw, me.wins_over(&opponent));
+
+        // I get 5 since i chose paper = 2, and draw = 3
+        assert_eq!(game.get_score(), 5);
+    }
+
+    #[test]
+    fn rock_wins_over_scissors() {
+        let opponent = Throw::ROCK;
+        assert_eq!(Outcome::Win, opponent.wins_over(&Throw::SCISSORS));
+    }
+    #[test]
+    fn rock_draws_against_rock() {
+        let opponent = Throw::ROCK;
+        assert_eq!(Outcome::Draw, opponent.wins_over(&Throw::ROCK));
+    }
+
+    #[test]
+    fn rock_loses_against_paper() {
+        let opponent = Throw::ROCK;
+        assert_eq!(Outcome::Lose, opponent.wins_over(&Throw::PAPER));
+    }
+
+    #[test]
+    fn scissors_wins_over_paper() {
+        let opponent = Throw::SCISSORS;
+        assert_eq!(Outcome::Win, opponent.wins_over(&Throw::PAPER));
+    }
+    #[test]
+    fn scissors_draws_against_scissors() {
+        let opponent = Throw::SCISSORS;
+        assert_eq!(Outcome::Draw, opponent.wins_over(&Throw::SCISSORS));
+    }
+
+    #[test]
+    fn scissors_loses_against_rock() {
+        let opponent = Throw::SCISSORS;
+        assert_eq!(Outcome::Lose, opponent.wins_over(&Throw::ROCK));
+    }
+
+    #[test]
+    fn paper_wins_over_rock() {
+        let opponent = Throw::PAPER;
+        assert_eq!(Outcome::Win, opponent.wins_over(&Throw::ROCK));
+    }
+    #[test]
+    fn paper_draws_against_paper() {
+        let opponent = Throw::PAPER;
+        assert_eq!(Outcome::Draw, opponent.wins_over(&Throw::PAPER));
+    }
+
+    #[test]
+    fn paper_loses_against_sciccors() {
+        let opponent = Throw::PAPER;
+        assert_eq!(Outcome::Lose, opponent.wins_over(&Throw::SCISSORS));
+    }
+}
@@ -1,301 +1,239 @@
-/// Possible hands you can throw in a match
-#[derive(Copy, Clone, Debug)]
-enum Throw {
-    Rock,
-    Paper,
-    Scissors,
+use clap::{Parser, ValueEnum};
+use common::report::{self, Part};
+use common::{print_answers, OutputArgs, OutputFormat, Solution};
+use day2::analysis::Analysis;
+use day2::bots::{every_bot, FixedGuide};
+use day2::decode::{self, decode};
+use day2::encoding::Encoding;
+use day2::game::{self, Ending, Session};
+use day2::generator::generate;
+use day2::predict::{self, evaluate};
+use day2::rules::Rules;
+use day2::synthesis::{parse_bound, Synthesis};
+use day2::tournament::{self, Tournament};
+use day2::{Day2, Round};
+use std::fs;
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+/// Command line of the day 2 binary
+#[derive(Parser, Debug)]
+struct Args {
+    #[command(flatten)]
+    output: OutputArgs,
+    /// Read the guide with the game, scores and symbols of this rules file
+    #[arg(long, value_name = "FILE")]
+    rules: Option<PathBuf>,
+    /// Score the guide under every reading of the second column instead
+    #[arg(long, conflicts_with_all = ["tournament", "predict"])]
+    decode: bool,
+    /// Break down how the guide plays out under both readings instead
+    #[arg(long, conflicts_with_all = ["decode", "predict", "tournament", "play"])]
+    analyse: bool,
+    /// Also write the breakdown to this file as CSV
+    #[arg(long, value_name = "FILE", requires = "analyse")]
+    csv: Option<PathBuf>,
+    /// Play the guide on what an n-gram model of this order learns of the opponent instead
+    #[arg(long, value_name = "ORDER", conflicts_with = "tournament")]
+    predict: Option<usize>,
+    /// List what the predictor suggests for every round
+    #[arg(long, requires = "predict")]
+    suggestions: bool,
+    /// Pit bots against each other, the guide among them, instead
+    #[arg(long, value_enum)]
+    tournament: Option<Format>,
+    /// Rounds in every match of the tournament
+    #[arg(long, default_value_t = 1000, requires = "tournament")]
+    rounds: usize,
+    /// Seed for the bots playing at random
+    #[arg(long, default_value_t = 0, requires = "tournament")]
+    seed: u64,
+    /// Play against the guide's first column from the terminal instead
+    #[arg(long, conflicts_with_all = ["decode", "predict", "tournament"])]
+    play: bool,
+    /// Where a game is saved to and resumed from
+    #[arg(
+        long,
+        value_name = "FILE",
+        default_value = "day2-session.json",
+        requires = "play"
+    )]
+    session: PathBuf,
+    /// Pick up the game saved in the session file
+    #[arg(long, requires = "play")]
+    resume: bool,
+    /// Play against a guide generated from this seed instead of the puzzle input
+    #[arg(long, value_name = "SEED", requires = "play", conflicts_with_all = ["resume", "rules"])]
+    generated: Option<u64>,
+    /// Rounds in a generated or written guide
+    #[arg(long, default_value_t = 10)]
+    length: usize,
+    /// Write a guide scoring exactly this total instead
+    #[arg(long, value_name = "SCORE", conflicts_with_all = ["decode", "analyse", "predict", "tournament", "play"])]
+    target: Option<usize>,
+    /// Write the second column as how each round should end rather than the throw to play
+    #[arg(long, requires = "target")]
+    as_outcomes: bool,
+    /// Play against the first column of the puzzle input, as many rounds as it has
+    #[arg(long, requires = "target")]
+    against_input: bool,
+    /// Rounds the written guide wins, as N, N..M, N.. or ..M
+    #[arg(long, value_name = "RANGE", value_parser = parse_bound, requires = "target")]
+    wins: Option<RangeInclusive<usize>>,
+    /// Rounds the written guide draws, as N, N..M, N.. or ..M
+    #[arg(long, value_name = "RANGE", value_parser = parse_bound, requires = "target")]
+    draws: Option<RangeInclusive<usize>>,
+    /// Rounds the written guide loses, as N, N..M, N.. or ..M
+    #[arg(long, value_name = "RANGE", value_parser = parse_bound, requires = "target")]
+    losses: Option<RangeInclusive<usize>>,
 }
 
-/// Input parsing
-impl From<&str> for Throw {
-    fn from(from: &str) -> Self {
-        match from {
-            "A" => Throw::Rock,
-            "B" => Throw::Paper,
-            "C" => Throw::Scissors,
-            "X" => Throw::Rock,
-            "Y" => Throw::Paper,
-            "Z" => Throw::Scissors,
-            _ => panic!("Impossible choice: {}", &from),
-        }
-    }
+/// How the bots of a tournament meet
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    /// Every bot plays every other once
+    RoundRobin,
+    /// Winners of each stage go on to the next
+    Elimination,
 }
 
-impl Throw {
-    /// Generate an `Outcome` comparing against another `Throw`
-    fn wins_over(&self, other: &Throw) -> Outcome {
-        match (&self, other) {
-            (Throw::Rock, Throw::Paper) => Outcome::Lose,
-            (Throw::Rock, Throw::Scissors) => Outcome::Win,
-            (Throw::Paper, Throw::Rock) => Outcome::Win,
-            (Throw::Paper, Throw::Scissors) => Outcome::Lose,
-            (Throw::Scissors, Throw::Rock) => Outcome::Lose,
-            (Throw::Scissors, Throw::Paper) => Outcome::Win,
-            _ => Outcome::Draw,
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    let encoding = args.rules.as_deref().map(Encoding::load).transpose()?;
+    let rules = encoding.as_ref().map_or(Rules::classic(), Encoding::rules);
+
+    if args.play {
+        let mut session = if args.resume {
+            Session::load(&args.session)?
+        } else if let Some(seed) = args.generated {
+            Session::from_rounds(&Day2::parse(&generate(seed, args.length).input)?)
+        } else {
+            let input = input::load(Day2::DAY)?;
+            match &encoding {
+                Some(encoding) => Session::from_rounds(&encoding.parse_guide(&input)?),
+                None => Session::from_rounds(&Day2::parse(&input)?),
+            }
+        };
+        let ending = game::play(rules, &mut session, io::stdin().lock(), io::stdout())?;
+        if ending == Ending::Saved {
+            session.save(&args.session)?;
+            println!("Saved to {}", args.session.display());
         }
-    }
-
-    /// Generate a `Throw` based on what we want to achieve via a `StrategyChoice`
-    fn get_desired(&self, desired: StrategyChoice) -> Throw {
-        match self {
-            Throw::Rock => match desired {
-                StrategyChoice::Win => Throw::Paper,
-                StrategyChoice::Lose => Throw::Scissors,
-                _ => Throw::Rock,
-            },
-            Throw::Paper => match desired {
-                StrategyChoice::Win => Throw::Scissors,
-                StrategyChoice::Lose => Throw::Rock,
-                _ => Throw::Paper,
-            },
-            Throw::Scissors => match desired {
-                StrategyChoice::Win => Throw::Rock,
-                StrategyChoice::Lose => Throw::Paper,
-                _ => Throw::Scissors,
-            },
+        return Ok(());
+    }
+
+    if let Some(target) = args.target {
+        let encoding = encoding.unwrap_or_else(Encoding::classic);
+        let rules = encoding.rules();
+        let mut synthesis = if args.against_input {
+            let input = input::load(Day2::DAY)?;
+            let opponent: Vec<_> = encoding
+                .parse_guide(&input)?
+                .iter()
+                .map(|Round(pair, _)| pair.opponent())
+                .collect();
+            Synthesis::new(rules, opponent.len(), target).with_opponent(opponent)
+        } else {
+            Synthesis::new(rules, args.length, target)
+        };
+        if let Some(wins) = args.wins {
+            synthesis = synthesis.with_wins(wins);
         }
-    }
-}
-
-/// Possible outcomes of a match
-#[derive(Debug, PartialEq)]
-enum Outcome {
-    Win,
-    Lose,
-    Draw,
-}
-
-/// A strategy for a match
-#[derive(Debug)]
-struct MatchStrategy {
-    opponent: Throw,
-    me: StrategyChoice,
-}
-
-impl MatchStrategy {
-    /// Look at what the opponent is playing and generate a `Throw` based on desired outcome
-    fn get_choice_based_on_strategy(&self) -> Throw {
-        self.opponent.get_desired(self.me)
-    }
-}
-
-/// Input parsing
-impl From<&str> for MatchStrategy {
-    fn from(from: &str) -> Self {
-        let mut parts = from.split_whitespace();
-        let opponent = Throw::from(parts.next().unwrap());
-        let me = StrategyChoice::from(parts.next().unwrap());
-        Self { opponent, me }
-    }
-}
-
-/// How we would like the game to end
-#[derive(Copy, Clone, Debug)]
-enum StrategyChoice {
-    /// X; we would like to lose
-    Lose,
-    /// Y; we would like to end in a draw
-    Draw,
-    /// Z; we would like ot win
-    Win,
-}
-
-/// input parsing
-impl From<&str> for StrategyChoice {
-    fn from(from: &str) -> Self {
-        match from {
-            "X" => StrategyChoice::Lose,
-            "Y" => StrategyChoice::Draw,
-            "Z" => StrategyChoice::Win,
-            _ => panic!("Impossible choice: {}", &from),
+        if let Some(draws) = args.draws {
+            synthesis = synthesis.with_draws(draws);
+        }
+        if let Some(losses) = args.losses {
+            synthesis = synthesis.with_losses(losses);
+        }
+        let written = synthesis.write(&encoding, args.as_outcomes)?;
+        match args.output.format {
+            OutputFormat::Text => print!("{written}"),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&written)?),
         }
+        return Ok(());
     }
-}
-
-/// A pair of hand-`Throw`s makes up a round/match
-#[derive(Debug)]
-struct MatchPair {
-    /// What my adversary is playing
-    opponent: Throw,
-    /// What I am playing
-    me: Throw,
-}
 
-impl MatchPair {
-    /// The score for a single round is the score for the shape you selected (1 for Rock, 2 for Paper, and 3 for Scissors)i
-    /// Score for the outcome of the round (0 if you lost, 3 if the round was a draw, and 6 if you won).
-    fn get_score(&self) -> usize {
-        let choice_score = match self.me {
-            Throw::Rock => 1,
-            Throw::Paper => 2,
-            Throw::Scissors => 3,
-        };
+    let input = input::load(Day2::DAY)?;
 
-        let match_score = match self.me.wins_over(&self.opponent) {
-            Outcome::Win => 6,
-            Outcome::Lose => 0,
-            Outcome::Draw => 3,
+    if args.analyse {
+        let analysis = match &encoding {
+            Some(encoding) => Analysis::new(rules, &encoding.parse_guide(&input)?),
+            None => Analysis::new(rules, &Day2::parse(&input)?),
         };
-
-        choice_score + match_score
-    }
-}
-
-/// If we have an opponents hand and a strategy we would want to execute, we can create a `MatchPair`
-impl From<MatchStrategy> for MatchPair {
-    fn from(from: MatchStrategy) -> Self {
-        Self {
-            opponent: from.opponent,
-            me: from.get_choice_based_on_strategy(),
+        if let Some(path) = &args.csv {
+            fs::write(path, analysis.to_csv())?;
         }
-    }
-}
-
-/// Input parsing
-impl From<&str> for MatchPair {
-    fn from(from: &str) -> Self {
-        let parts: Vec<Throw> = from
-            .split_whitespace()
-            .into_iter()
-            .map(Throw::from)
-            .collect();
-        Self {
-            opponent: parts[0],
-            me: parts[1],
+        match args.output.format {
+            OutputFormat::Text => print!("{analysis}"),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&analysis)?),
         }
+        return Ok(());
     }
-}
 
-fn main() -> Result<(), std::io::Error> {
-    let input = std::fs::read_to_string("input.txt")?;
-    let mut all_games: Vec<MatchPair> = Vec::new();
-    let mut new_strategy: Vec<MatchPair> = Vec::new();
-    for line in input.split('\n') {
-        if !line.is_empty() {
-            all_games.push(MatchPair::from(line));
-            new_strategy.push(MatchPair::from(MatchStrategy::from(line)));
+    if args.decode {
+        let decoding = match &encoding {
+            Some(encoding) => decode(rules, &encoding.parse_guide(&input)?),
+            None => decode(rules, &Day2::parse(&input)?),
+        };
+        let decoding = decoding.ok_or("only games of three shapes can be decoded")?;
+        let report = decode::Report::from(&decoding);
+        match args.output.format {
+            OutputFormat::Text => print!("{report}"),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
         }
-    }
-    let total_score: usize = all_games.iter().map(|x| x.get_score()).sum();
-    let new_score: usize = new_strategy.iter().map(|x| x.get_score()).sum();
-    println!("Total score for first part: {}", total_score);
-    println!("Total score using second part: {}", new_score);
-    Ok(())
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-    #[test]
-    fn am_i_choosing_correct_strategy_for_a_y() {
-        let matchstr = "A Y";
-        let matchstrat = MatchStrategy::from(matchstr);
-        let game = MatchPair::from(matchstrat);
-
-        assert_eq!(game.get_score(), 4);
-    }
-
-    #[test]
-    fn am_i_choosing_correct_strategy_for_b_x() {
-        let matchstr = "B X";
-        let matchstrat = MatchStrategy::from(matchstr);
-        let game = MatchPair::from(matchstrat);
-
-        assert_eq!(game.get_score(), 1);
-    }
-
-    #[test]
-    fn am_i_choosing_correct_strategy_for_c_z() {
-        let matchstr = "C Z";
-        let matchstrat = MatchStrategy::from(matchstr);
-        let game = MatchPair::from(matchstrat);
-
-        assert_eq!(game.get_score(), 7);
-    }
-
-    #[test]
-    fn am_i_winning_with_rock() {
-        let me = Throw::Rock;
-        let opponent = Throw::Scissors;
-        let game = MatchPair { opponent, me };
-        // I should win
-        assert_eq!(Outcome::Win, me.wins_over(&opponent));
-
-        assert_eq!(game.get_score(), 7);
-    }
-
-    #[test]
-    fn am_i_losing_with_rock() {
-        let me = Throw::Rock;
-        let opponent = Throw::Paper;
-        let game = MatchPair { opponent, me };
-        // I should lose
-        assert_eq!(Outcome::Lose, me.wins_over(&opponent));
-
-        // I get 1 since i chose rock
-        assert_eq!(game.get_score(), 1);
+        return Ok(());
     }
 
-    #[test]
-    fn am_i_drawing_with_paper() {
-        let me = Throw::Paper;
-        let opponent = Throw::Paper;
-        let game = MatchPair { opponent, me };
-        // I should lose
-        assert_eq!(Outcome::Draw, me.wins_over(&opponent));
-
-        // I get 5 since i chose paper = 2, and draw = 3
-        assert_eq!(game.get_score(), 5);
-    }
-
-    #[test]
-    fn rock_wins_over_scissors() {
-        let opponent = Throw::Rock;
-        assert_eq!(Outcome::Win, opponent.wins_over(&Throw::Scissors));
-    }
-    #[test]
-    fn rock_draws_against_rock() {
-        let opponent = Throw::Rock;
-        assert_eq!(Outcome::Draw, opponent.wins_over(&Throw::Rock));
-    }
-
-    #[test]
-    fn rock_loses_against_paper() {
-        let opponent = Throw::Rock;
-        assert_eq!(Outcome::Lose, opponent.wins_over(&Throw::Paper));
+    if let Some(order) = args.predict {
+        let evaluation = match &encoding {
+            Some(encoding) => evaluate(rules, &encoding.parse_guide(&input)?, order),
+            None => evaluate(rules, &Day2::parse(&input)?, order),
+        };
+        let report = predict::Report::new(&evaluation, order, args.suggestions);
+        match args.output.format {
+            OutputFormat::Text => print!("{report}"),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        }
+        return Ok(());
     }
 
-    #[test]
-    fn scissors_wins_over_paper() {
-        let opponent = Throw::Scissors;
-        assert_eq!(Outcome::Win, opponent.wins_over(&Throw::Paper));
-    }
-    #[test]
-    fn scissors_draws_against_scissors() {
-        let opponent = Throw::Scissors;
-        assert_eq!(Outcome::Draw, opponent.wins_over(&Throw::Scissors));
+    if let Some(format) = args.tournament {
+        let guide = match &encoding {
+            Some(encoding) => FixedGuide::from_rounds("guide", &encoding.parse_guide(&input)?),
+            None => FixedGuide::from_rounds("guide", &Day2::parse(&input)?),
+        };
+        let mut bots = every_bot(guide);
+        let tournament = Tournament::new(rules, args.rounds, args.seed);
+        let (table, champion) = match format {
+            Format::RoundRobin => (tournament.round_robin(&mut bots), None),
+            Format::Elimination => tournament.elimination(&mut bots),
+        };
+        let report = tournament::Report::new(table, champion, &bots);
+        match args.output.format {
+            OutputFormat::Text => print!("{report}"),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        }
+        return Ok(());
     }
 
-    #[test]
-    fn scissors_loses_against_rock() {
-        let opponent = Throw::Scissors;
-        assert_eq!(Outcome::Lose, opponent.wins_over(&Throw::Rock));
+    if let Some(encoding) = &encoding {
+        let reports = encoding.solve(&input, &Part::BOTH)?;
+        match args.output.format {
+            OutputFormat::Text => {
+                println!("Total score for first part: {}", reports[0].answer);
+                println!("Total score using second part: {}", reports[1].answer);
+            }
+            OutputFormat::Json => println!("{}", report::to_json(&reports)),
+        }
+        return Ok(());
     }
 
-    #[test]
-    fn paper_wins_over_rock() {
-        let opponent = Throw::Paper;
-        assert_eq!(Outcome::Win, opponent.wins_over(&Throw::Rock));
-    }
-    #[test]
-    fn paper_draws_against_paper() {
-        let opponent = Throw::Paper;
-        assert_eq!(Outcome::Draw, opponent.wins_over(&Throw::Paper));
-    }
+    print_answers::<Day2>(args.output.format, &input, |rounds| {
+        println!("Total score for first part: {}", Day2::part_one(rounds)?);
+        println!("Total score using second part: {}", Day2::part_two(rounds)?);
+        Ok(())
+    })?;
 
-    #[test]
-    fn paper_loses_against_sciccors() {
-        let opponent = Throw::Paper;
-        assert_eq!(Outcome::Lose, opponent.wins_over(&Throw::Scissors));
-    }
+    Ok(())
 }
@@ -0,0 +1,292 @@
+//! Predicting the opponent's next throw from the throws before it
+//!
+//! An n-gram model counts which throw followed every run of `order` throws so
+//! far. Order 0 is plain frequency counting and order 1 a Markov chain. When
+//! the latest run has never been seen before, the prediction backs off to
+//! shorter runs until one has.
+
+use crate::rules::Rules;
+use crate::{MatchPair, Round, StrategyChoice, Throw};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Learns the opponent's habits one throw at a time
+#[derive(Debug, Clone)]
+pub struct Predictor<'r> {
+    rules: &'r Rules,
+    order: usize,
+    /// How often each shape followed each run of up to `order` shapes
+    counts: HashMap<Vec<usize>, Vec<usize>>,
+    /// The latest `order` shapes thrown
+    recent: Vec<usize>,
+}
+
+impl<'r> Predictor<'r> {
+    /// A predictor for a game of `rules` looking back `order` throws
+    pub fn new(rules: &'r Rules, order: usize) -> Self {
+        Predictor {
+            rules,
+            order,
+            counts: HashMap::new(),
+            recent: Vec::with_capacity(order),
+        }
+    }
+
+    /// The throw the opponent most likely makes next, if any has been seen
+    ///
+    /// Ties go to the shape listed first in the rules.
+    pub fn predict(&self) -> Option<Throw<'r>> {
+        (0..=self.recent.len()).rev().find_map(|length| {
+            let context = &self.recent[self.recent.len() - length..];
+            let counts = self.counts.get(context)?;
+            let shape = (0..counts.len()).rev().max_by_key(|&shape| counts[shape])?;
+            Some(Throw::new(self.rules, shape))
+        })
+    }
+
+    /// The throw worth the most that beats the predicted one
+    pub fn suggest(&self) -> Option<Throw<'r>> {
+        self.predict()
+            .map(|predicted| predicted.get_desired(StrategyChoice::Win))
+    }
+
+    /// Learn from the opponent having thrown `theirs`
+    pub fn observe(&mut self, theirs: Throw<'r>) {
+        for length in 0..=self.recent.len() {
+            let context = self.recent[self.recent.len() - length..].to_vec();
+            let counts = self
+                .counts
+                .entry(context)
+                .or_insert_with(|| vec![0; self.rules.len()]);
+            counts[theirs.index()] += 1;
+        }
+        if self.order > 0 {
+            if self.recent.len() == self.order {
+                self.recent.remove(0);
+            }
+            self.recent.push(theirs.index());
+        }
+    }
+}
+
+/// What the predictor made of a round of the guide
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Suggestion<'r> {
+    /// The opponent's throw as predicted before the round, if there was anything to go on
+    pub predicted: Option<Throw<'r>>,
+    /// The throw the predictor-driven player makes
+    pub play: Throw<'r>,
+    /// Points it scores against the opponent's actual throw
+    pub score: usize,
+}
+
+/// How a predictor-driven player would have done on a guide, next to the elf's strategy
+#[derive(Debug, Clone, PartialEq)]
+pub struct Evaluation<'r> {
+    /// A suggestion per round
+    pub suggestions: Vec<Suggestion<'r>>,
+    /// Rounds where the opponent's throw was predicted right
+    pub correct: usize,
+    /// Total score of the predictor-driven player
+    pub score: usize,
+    /// Total score following the strategy the elf wrote down
+    pub elf_score: usize,
+}
+
+impl Evaluation<'_> {
+    /// Rounds with a prediction to go on
+    pub fn predicted(&self) -> usize {
+        self.suggestions
+            .iter()
+            .filter(|suggestion| suggestion.predicted.is_some())
+            .count()
+    }
+}
+
+/// Play every round of the guide on what a predictor of `order` learnt from the rounds before
+///
+/// The opponent's throws are the first column. Until there is anything to
+/// predict from, the player follows the elf's strategy for the round.
+pub fn evaluate<'r>(rules: &'r Rules, rounds: &[Round<'r>], order: usize) -> Evaluation<'r> {
+    let mut predictor = Predictor::new(rules, order);
+    let mut evaluation = Evaluation {
+        suggestions: Vec::with_capacity(rounds.len()),
+        correct: 0,
+        score: 0,
+        elf_score: 0,
+    };
+    for Round(_, strategy) in rounds {
+        let theirs = strategy.opponent;
+        let elf = MatchPair::from(*strategy);
+        let predicted = predictor.predict();
+        let play = predictor.suggest().unwrap_or(elf.me);
+        let score = MatchPair::new(theirs, play).get_score();
+
+        evaluation.correct += usize::from(predicted == Some(theirs));
+        evaluation.score += score;
+        evaluation.elf_score += elf.get_score();
+        evaluation.suggestions.push(Suggestion {
+            predicted,
+            play,
+            score,
+        });
+        predictor.observe(theirs);
+    }
+    evaluation
+}
+
+/// What the predictor made of a round, as the report shows it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Suggested {
+    /// Number of the round, from 1
+    pub round: usize,
+    /// Name of the throw expected of the opponent, if there was anything to go on
+    pub predicted: Option<String>,
+    /// Name of the throw to make
+    pub play: String,
+    /// Points it scores
+    pub score: usize,
+}
+
+/// What the predict mode shows
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Report {
+    /// Order of the n-gram model
+    pub order: usize,
+    /// Rounds in the guide
+    pub rounds: usize,
+    /// Rounds with a prediction to go on
+    pub predicted: usize,
+    /// Rounds where the opponent's throw was predicted right
+    pub correct: usize,
+    /// Total score of the predictor-driven player
+    pub score: usize,
+    /// Total score following the strategy the elf wrote down
+    pub elf_score: usize,
+    /// Every round, when they were asked for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestions: Option<Vec<Suggested>>,
+}
+
+impl Report {
+    /// Report on `evaluation` by a model of `order`, listing every round if `suggestions`
+    pub fn new(evaluation: &Evaluation, order: usize, suggestions: bool) -> Self {
+        Report {
+            order,
+            rounds: evaluation.suggestions.len(),
+            predicted: evaluation.predicted(),
+            correct: evaluation.correct,
+            score: evaluation.score,
+            elf_score: evaluation.elf_score,
+            suggestions: suggestions.then(|| {
+                (1..)
+                    .zip(&evaluation.suggestions)
+                    .map(|(round, suggestion)| Suggested {
+                        round,
+                        predicted: suggestion.predicted.map(|throw| throw.name().to_string()),
+                        play: suggestion.play.name().to_string(),
+                        score: suggestion.score,
+                    })
+                    .collect()
+            }),
+        }
+    }
+}
+
+/// Every suggestion if there are any, then how the predictions did
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for suggested in self.suggestions.iter().flatten() {
+            match &suggested.predicted {
+                Some(predicted) => writeln!(
+                    f,
+                    "Round {}: expecting {predicted}, play {} for {}",
+                    suggested.round, suggested.play, suggested.score
+                )?,
+                None => writeln!(
+                    f,
+                    "Round {}: nothing to go on, play {} for {}",
+                    suggested.round, suggested.play, suggested.score
+                )?,
+            }
+        }
+        writeln!(
+            f,
+            "Predicted {} of {} rounds right with an order {} model",
+            self.correct, self.rounds, self.order
+        )?;
+        writeln!(f, "Total score playing the predictions: {}", self.score)?;
+        writeln!(f, "Total score following the elf: {}", self.elf_score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::CLASSIC;
+    use crate::Day2;
+    use common::Solution;
+
+    #[test]
+    fn learns_a_cycle() {
+        let mut predictor = Predictor::new(&CLASSIC, 1);
+        assert_eq!(predictor.predict(), None);
+        for throw in [Throw::ROCK, Throw::PAPER, Throw::SCISSORS].repeat(2) {
+            predictor.observe(throw);
+        }
+        // Rock has always followed scissors
+        assert_eq!(predictor.predict(), Some(Throw::ROCK));
+        assert_eq!(predictor.suggest(), Some(Throw::PAPER));
+    }
+
+    #[test]
+    fn backs_off_to_shorter_runs() {
+        let mut predictor = Predictor::new(&CLASSIC, 2);
+        for throw in [Throw::PAPER, Throw::PAPER, Throw::ROCK] {
+            predictor.observe(throw);
+        }
+        // Nothing has followed paper then rock, or rock, so the most thrown shape is next
+        assert_eq!(predictor.predict(), Some(Throw::PAPER));
+
+        let mut frequency = Predictor::new(&CLASSIC, 0);
+        for throw in [Throw::SCISSORS, Throw::ROCK, Throw::SCISSORS] {
+            frequency.observe(throw);
+        }
+        assert_eq!(frequency.predict(), Some(Throw::SCISSORS));
+    }
+
+    #[test]
+    fn beats_a_predictable_guide() {
+        let guide = "A Y\nB X\nC Z\n".repeat(20);
+        let rounds = Day2::parse(&guide).unwrap();
+        let evaluation = evaluate(&CLASSIC, &rounds, 2);
+        assert_eq!(evaluation.suggestions.len(), 60);
+        assert_eq!(evaluation.predicted(), 59);
+        assert_eq!(
+            evaluation.elf_score,
+            20 * Day2::part_two(&Day2::parse("A Y\nB X\nC Z\n").unwrap()).unwrap()
+        );
+        assert!(evaluation.correct >= 55);
+        assert!(evaluation.score > evaluation.elf_score);
+        // The first round has nothing to go on and follows the elf
+        assert_eq!(evaluation.suggestions[0].play, Throw::ROCK);
+    }
+
+    #[test]
+    fn report() {
+        let rounds = Day2::parse("A Y\nA Y\n").unwrap();
+        let evaluation = evaluate(&CLASSIC, &rounds, 1);
+        let report = Report::new(&evaluation, 1, true);
+        let text = report.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2 + 3);
+        assert!(lines[0].starts_with("Round 1: nothing to go on"));
+        assert!(lines[1].starts_with("Round 2: expecting Rock, play Paper"));
+        assert_eq!(
+            lines[2],
+            "Predicted 1 of 2 rounds right with an order 1 model"
+        );
+        assert_eq!(Report::new(&evaluation, 1, false).suggestions, None);
+    }
+}
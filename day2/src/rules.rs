@@ -0,0 +1,506 @@
+//! The shapes of a hand game, which beats which and what each is worth
+//!
+//! Rock Paper Scissors is the smallest of the cyclic games: with an odd number
+//! of shapes, every shape beats exactly half of the others and loses to the
+//! other half, so there is always a throw to win, draw or lose with.
+
+use crate::{Outcome, Throw};
+use std::borrow::Cow;
+use thiserror::Error;
+
+/// Why a set of rules does not make a fair cyclic game
+#[derive(Debug, PartialEq, Error)]
+pub enum RulesError {
+    /// Fewer than three shapes
+    #[error("a game needs at least 3 shapes, not {0}")]
+    TooFewShapes(usize),
+    /// More shapes than the rules can keep track of
+    #[error("a game can have at most 64 shapes, not {0}")]
+    TooManyShapes(usize),
+    /// An even number of shapes, which cannot all beat as many as they lose to
+    #[error("a game needs an odd number of shapes, not {0}")]
+    EvenShapes(usize),
+    /// Two shapes of the same name
+    #[error("there are two shapes named `{0}`")]
+    DuplicateShape(String),
+    /// A shape that is not in the game
+    #[error("there is no shape named `{0}`")]
+    UnknownShape(String),
+    /// A shape said to beat itself
+    #[error("`{0}` cannot beat itself")]
+    SelfBeat(String),
+    /// Two shapes that beat each other
+    #[error("`{0}` and `{1}` cannot both beat each other")]
+    Mutual(String, String),
+    /// Two shapes where neither beats the other
+    #[error("neither `{0}` nor `{1}` beats the other")]
+    Undecided(String, String),
+    /// A shape that beats more or fewer shapes than it loses to
+    #[error("`{shape}` beats {wins} shapes instead of {expected}")]
+    Unbalanced {
+        /// Name of the shape
+        shape: String,
+        /// Number of shapes it beats
+        wins: usize,
+        /// Number of shapes it should beat, half of the others
+        expected: usize,
+    },
+}
+
+/// A shape a hand can make, and the points for playing it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shape {
+    name: Cow<'static, str>,
+    score: usize,
+}
+
+impl Shape {
+    /// A shape called `name`, worth `score` points whenever it is played
+    pub fn new(name: impl Into<Cow<'static, str>>, score: usize) -> Self {
+        Shape {
+            name: name.into(),
+            score,
+        }
+    }
+
+    /// What the shape is called
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Points for playing the shape, whatever the outcome
+    pub fn score(&self) -> usize {
+        self.score
+    }
+}
+
+/// Points for how a round ends, whatever shape was played
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutcomeScores {
+    /// Points for losing the round
+    pub lose: usize,
+    /// Points for a draw
+    pub draw: usize,
+    /// Points for winning the round
+    pub win: usize,
+}
+
+impl OutcomeScores {
+    /// Nothing for a loss, 3 for a draw and 6 for a win
+    pub const CLASSIC: OutcomeScores = OutcomeScores {
+        lose: 0,
+        draw: 3,
+        win: 6,
+    };
+
+    /// Points for ending a round with `outcome`
+    pub fn score(&self, outcome: Outcome) -> usize {
+        match outcome {
+            Outcome::Lose => self.lose,
+            Outcome::Draw => self.draw,
+            Outcome::Win => self.win,
+        }
+    }
+}
+
+impl Default for OutcomeScores {
+    fn default() -> Self {
+        OutcomeScores::CLASSIC
+    }
+}
+
+/// The shapes of a game, which beats which and how rounds are scored
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    shapes: Cow<'static, [Shape]>,
+    /// Bit `j` of `beats[i]` is set when shape `i` beats shape `j`
+    beats: Cow<'static, [u64]>,
+    outcomes: OutcomeScores,
+}
+
+/// Rock Paper Scissors as the strategy guide plays it
+pub static CLASSIC: Rules = Rules {
+    shapes: Cow::Borrowed(&[
+        Shape {
+            name: Cow::Borrowed("Rock"),
+            score: 1,
+        },
+        Shape {
+            name: Cow::Borrowed("Paper"),
+            score: 2,
+        },
+        Shape {
+            name: Cow::Borrowed("Scissors"),
+            score: 3,
+        },
+    ]),
+    // Rock beats scissors, paper beats rock and scissors beat paper
+    beats: Cow::Borrowed(&[0b100, 0b001, 0b010]),
+    outcomes: OutcomeScores::CLASSIC,
+};
+
+impl Rules {
+    /// A game of `shapes` where each of the pairs in `beats` is a winner and the shape it beats
+    ///
+    /// Every pair of shapes needs a winner, and every shape has to beat as
+    /// many shapes as it loses to.
+    pub fn new<'a>(
+        shapes: Vec<Shape>,
+        beats: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Self, RulesError> {
+        Self::check_shapes(&shapes)?;
+        let index = |name: &str| {
+            shapes
+                .iter()
+                .position(|shape| shape.name == name)
+                .ok_or_else(|| RulesError::UnknownShape(name.to_string()))
+        };
+        let mut masks = vec![0u64; shapes.len()];
+        for (winner, loser) in beats {
+            let (winner, loser) = (index(winner)?, index(loser)?);
+            masks[winner] |= 1 << loser;
+        }
+        let rules = Rules {
+            shapes: Cow::Owned(shapes),
+            beats: Cow::Owned(masks),
+            outcomes: OutcomeScores::CLASSIC,
+        };
+        rules.check_beats()?;
+        Ok(rules)
+    }
+
+    /// A game where each shape beats the half of the others listed right after it, wrapping around
+    pub fn cyclic(shapes: Vec<Shape>) -> Result<Self, RulesError> {
+        Self::check_shapes(&shapes)?;
+        let count = shapes.len();
+        let masks = (0..count)
+            .map(|winner| {
+                (1..=count / 2).fold(0u64, |mask, ahead| mask | 1 << ((winner + ahead) % count))
+            })
+            .collect();
+        Ok(Rules {
+            shapes: Cow::Owned(shapes),
+            beats: Cow::Owned(masks),
+            outcomes: OutcomeScores::CLASSIC,
+        })
+    }
+
+    /// Rock Paper Scissors as the strategy guide plays it
+    pub fn classic() -> &'static Rules {
+        &CLASSIC
+    }
+
+    /// Rock Paper Scissors Lizard Spock, the new shapes worth 4 and 5 points
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Self::preset(&[
+            ("Rock", 1),
+            ("Scissors", 3),
+            ("Lizard", 4),
+            ("Paper", 2),
+            ("Spock", 5),
+        ])
+    }
+
+    /// The 7 shape game, each shape worth its place in the list
+    pub fn seven() -> Self {
+        Self::preset(&[
+            ("Rock", 1),
+            ("Fire", 2),
+            ("Scissors", 3),
+            ("Sponge", 4),
+            ("Paper", 5),
+            ("Air", 6),
+            ("Water", 7),
+        ])
+    }
+
+    /// The 9 shape game, each shape worth its place in the list
+    pub fn nine() -> Self {
+        Self::preset(&[
+            ("Rock", 1),
+            ("Fire", 2),
+            ("Scissors", 3),
+            ("Human", 4),
+            ("Sponge", 5),
+            ("Paper", 6),
+            ("Air", 7),
+            ("Water", 8),
+            ("Gun", 9),
+        ])
+    }
+
+    /// A cyclic game of shapes known to make a fair one
+    fn preset(shapes: &[(&'static str, usize)]) -> Self {
+        let shapes = shapes
+            .iter()
+            .map(|&(name, score)| Shape::new(name, score))
+            .collect();
+        Self::cyclic(shapes).expect("presets are fair games")
+    }
+
+    fn check_shapes(shapes: &[Shape]) -> Result<(), RulesError> {
+        match shapes.len() {
+            count if count < 3 => return Err(RulesError::TooFewShapes(count)),
+            count if count > 64 => return Err(RulesError::TooManyShapes(count)),
+            count if count % 2 == 0 => return Err(RulesError::EvenShapes(count)),
+            _ => (),
+        }
+        for (index, shape) in shapes.iter().enumerate() {
+            if shapes[..index].iter().any(|other| other.name == shape.name) {
+                return Err(RulesError::DuplicateShape(shape.name.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_beats(&self) -> Result<(), RulesError> {
+        let name = |index: usize| self.shapes[index].name.to_string();
+        for first in 0..self.len() {
+            if self.beats(first, first) {
+                return Err(RulesError::SelfBeat(name(first)));
+            }
+            for second in first + 1..self.len() {
+                match (self.beats(first, second), self.beats(second, first)) {
+                    (true, true) => return Err(RulesError::Mutual(name(first), name(second))),
+                    (false, false) => return Err(RulesError::Undecided(name(first), name(second))),
+                    _ => (),
+                }
+            }
+        }
+        let expected = self.len() / 2;
+        for (shape, mask) in self.beats.iter().enumerate() {
+            let wins = mask.count_ones() as usize;
+            if wins != expected {
+                return Err(RulesError::Unbalanced {
+                    shape: name(shape),
+                    wins,
+                    expected,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The same game with the shape called `name` worth `score` points
+    pub fn with_shape_score(mut self, name: &str, score: usize) -> Result<Self, RulesError> {
+        let shape = self
+            .shapes
+            .to_mut()
+            .iter_mut()
+            .find(|shape| shape.name == name)
+            .ok_or_else(|| RulesError::UnknownShape(name.to_string()))?;
+        shape.score = score;
+        Ok(self)
+    }
+
+    /// The same game with rounds ending worth `outcomes`
+    pub fn with_outcome_scores(mut self, outcomes: OutcomeScores) -> Self {
+        self.outcomes = outcomes;
+        self
+    }
+
+    /// Every shape of the game, in the order they are listed
+    pub fn shapes(&self) -> &[Shape] {
+        &self.shapes
+    }
+
+    /// Points for how a round ends
+    pub fn outcome_scores(&self) -> OutcomeScores {
+        self.outcomes
+    }
+
+    /// Number of shapes in the game
+    pub fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    /// Never true, a game has at least three shapes
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    /// Every throw of the game, in the order the shapes are listed
+    pub fn throws(&self) -> impl Iterator<Item = Throw<'_>> {
+        (0..self.len()).map(move |shape| Throw::new(self, shape))
+    }
+
+    /// The throw of the shape called `name`
+    pub fn throw(&self, name: &str) -> Option<Throw<'_>> {
+        self.throws().find(|throw| throw.name() == name)
+    }
+
+    /// The shape at `index` in the list
+    pub(crate) fn shape(&self, index: usize) -> &Shape {
+        &self.shapes[index]
+    }
+
+    /// Whether shape `winner` beats shape `loser`
+    pub(crate) fn beats(&self, winner: usize, loser: usize) -> bool {
+        self.beats[winner] & 1 << loser != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MatchPair, MatchStrategy, Outcome, StrategyChoice};
+
+    fn shapes(names: &[&'static str]) -> Vec<Shape> {
+        names
+            .iter()
+            .enumerate()
+            .map(|(index, &name)| Shape::new(name, index + 1))
+            .collect()
+    }
+
+    #[test]
+    fn classic_is_cyclic() {
+        let cyclic = Rules::cyclic(vec![
+            Shape::new("Rock", 1),
+            Shape::new("Scissors", 3),
+            Shape::new("Paper", 2),
+        ])
+        .unwrap();
+        for mine in CLASSIC.throws() {
+            for theirs in CLASSIC.throws() {
+                let (cyclic_mine, cyclic_theirs) = (
+                    cyclic.throw(mine.name()).unwrap(),
+                    cyclic.throw(theirs.name()).unwrap(),
+                );
+                assert_eq!(
+                    mine.wins_over(&theirs),
+                    cyclic_mine.wins_over(&cyclic_theirs)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn every_game_is_fair() {
+        for rules in [
+            CLASSIC.clone(),
+            Rules::rock_paper_scissors_lizard_spock(),
+            Rules::seven(),
+            Rules::nine(),
+        ] {
+            for mine in rules.throws() {
+                let outcomes: Vec<Outcome> = rules
+                    .throws()
+                    .map(|theirs| mine.wins_over(&theirs))
+                    .collect();
+                let count = |outcome| outcomes.iter().filter(|&&found| found == outcome).count();
+                assert_eq!(count(Outcome::Draw), 1);
+                assert_eq!(count(Outcome::Win), rules.len() / 2);
+                assert_eq!(count(Outcome::Lose), rules.len() / 2);
+            }
+        }
+    }
+
+    #[test]
+    fn lizard_spock() {
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+        let throw = |name| rules.throw(name).unwrap();
+        assert_eq!(throw("Spock").wins_over(&throw("Scissors")), Outcome::Win);
+        assert_eq!(throw("Lizard").wins_over(&throw("Spock")), Outcome::Win);
+        assert_eq!(throw("Paper").wins_over(&throw("Lizard")), Outcome::Lose);
+        assert_eq!(throw("Rock").wins_over(&throw("Lizard")), Outcome::Win);
+
+        // Paper and Spock both beat rock, Spock is worth more
+        let pair = MatchPair::from(MatchStrategy::new(throw("Rock"), StrategyChoice::Win));
+        assert_eq!(pair.get_score(), 5 + 6);
+        let pair = MatchPair::new(throw("Lizard"), throw("Lizard"));
+        assert_eq!(pair.get_score(), 4 + 3);
+    }
+
+    #[test]
+    fn explicit_beats() {
+        let rules = Rules::new(
+            shapes(&["Rock", "Paper", "Scissors"]),
+            [
+                ("Rock", "Scissors"),
+                ("Paper", "Rock"),
+                ("Scissors", "Paper"),
+            ],
+        )
+        .unwrap();
+        let throw = |name| rules.throw(name).unwrap();
+        assert_eq!(throw("Paper").wins_over(&throw("Rock")), Outcome::Win);
+    }
+
+    #[test]
+    fn rescored() {
+        let rules = CLASSIC
+            .clone()
+            .with_shape_score("Paper", 10)
+            .unwrap()
+            .with_outcome_scores(OutcomeScores {
+                lose: 1,
+                draw: 2,
+                win: 4,
+            });
+        let throw = |name| rules.throw(name).unwrap();
+        assert_eq!(
+            MatchPair::new(throw("Rock"), throw("Paper")).get_score(),
+            14
+        );
+        assert_eq!(MatchPair::new(throw("Paper"), throw("Rock")).get_score(), 2);
+        assert_eq!(
+            CLASSIC.clone().with_shape_score("Lizard", 4),
+            Err(RulesError::UnknownShape(String::from("Lizard")))
+        );
+    }
+
+    #[test]
+    fn unfair_games() {
+        let three = || shapes(&["Rock", "Paper", "Scissors"]);
+        assert_eq!(
+            Rules::cyclic(shapes(&["Rock", "Paper"])),
+            Err(RulesError::TooFewShapes(2))
+        );
+        assert_eq!(
+            Rules::cyclic(shapes(&["A", "B", "C", "D"])),
+            Err(RulesError::EvenShapes(4))
+        );
+        assert_eq!(
+            Rules::cyclic(shapes(&["Rock", "Rock", "Paper"])),
+            Err(RulesError::DuplicateShape(String::from("Rock")))
+        );
+        assert_eq!(
+            Rules::new(three(), [("Rock", "Lizard")]),
+            Err(RulesError::UnknownShape(String::from("Lizard")))
+        );
+        assert_eq!(
+            Rules::new(three(), [("Rock", "Rock")]),
+            Err(RulesError::SelfBeat(String::from("Rock")))
+        );
+        assert_eq!(
+            Rules::new(three(), [("Rock", "Paper"), ("Paper", "Rock")]),
+            Err(RulesError::Mutual(
+                String::from("Rock"),
+                String::from("Paper")
+            ))
+        );
+        assert_eq!(
+            Rules::new(three(), [("Rock", "Paper"), ("Scissors", "Paper")]),
+            Err(RulesError::Undecided(
+                String::from("Rock"),
+                String::from("Scissors")
+            ))
+        );
+        assert_eq!(
+            Rules::new(
+                three(),
+                [
+                    ("Rock", "Paper"),
+                    ("Rock", "Scissors"),
+                    ("Paper", "Scissors")
+                ]
+            ),
+            Err(RulesError::Unbalanced {
+                shape: String::from("Rock"),
+                wins: 2,
+                expected: 1,
+            })
+        );
+    }
+}
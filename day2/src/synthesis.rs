@@ -0,0 +1,725 @@
+//! Writing a strategy guide that scores a given total
+//!
+//! Every round can only be worth a handful of totals, so the totals a guide
+//! can reach are found round by round, as a set per count of the outcomes
+//! that are bounded. The search covers every guide there is, so a target it
+//! cannot reach is out of reach of any guide.
+//!
+//! Only a few of the rounds' sets are kept on the way forward; the rest are
+//! found again from the nearest kept one while the guide is read back.
+
+use crate::analysis::place;
+use crate::encoding::Encoding;
+use crate::rules::Rules;
+use crate::{MatchPair, MatchStrategy, Outcome, StrategyChoice, Throw};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+use thiserror::Error;
+
+/// Why no guide could be written
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum SynthesisError {
+    /// An opponent sequence of the wrong length
+    #[error("the opponent has {given} throws for {rounds} rounds")]
+    OpponentLength {
+        /// Throws given for the opponent
+        given: usize,
+        /// Rounds in the guide
+        rounds: usize,
+    },
+    /// No guide within the bounds scores the target
+    ///
+    /// Every guide was considered, so the nearest totals that can be reached
+    /// are the proof.
+    #[error(
+        "no guide of {rounds} rounds scores {target}, the nearest totals are {} and {}",
+        or_none(*below),
+        or_none(*above)
+    )]
+    Unreachable {
+        /// The total asked for
+        target: usize,
+        /// Rounds in the guide
+        rounds: usize,
+        /// The highest total below the target any guide within the bounds scores
+        below: Option<usize>,
+        /// The lowest total above the target any guide within the bounds scores
+        above: Option<usize>,
+    },
+    /// The bounds leave more counts of outcomes to tell apart than the search can hold
+    #[error(
+        "searching for a guide of {rounds} rounds within these bounds needs over {limit} bytes, \
+         try fewer rounds or looser bounds"
+    )]
+    TooLarge {
+        /// Rounds in the guide
+        rounds: usize,
+        /// The most bytes of totals the search keeps at once
+        limit: usize,
+    },
+    /// A guide was found, but the encoding has no symbol for one of its rounds
+    #[error("the rules have no symbol for a throw or outcome of the guide")]
+    Unwritable,
+}
+
+/// The most bytes of reachable totals a search keeps at once
+pub const MAX_SEARCH_BYTES: usize = 1 << 30;
+
+fn or_none(total: Option<usize>) -> String {
+    total.map_or(String::from("none"), |total| total.to_string())
+}
+
+/// A count of rounds or an inclusive range of them, as N, N..M, N.. or ..M
+pub fn parse_bound(text: &str) -> Result<RangeInclusive<usize>, String> {
+    let count = |text: &str, open: usize| {
+        if text.is_empty() {
+            Ok(open)
+        } else {
+            text.parse::<usize>()
+                .map_err(|_| format!("`{text}` is not a count of rounds"))
+        }
+    };
+    match text.split_once("..") {
+        Some((low, high)) => Ok(count(low, 0)?..=count(high, usize::MAX)?),
+        None => {
+            let exactly = count(text, 0)?;
+            Ok(exactly..=exactly)
+        }
+    }
+}
+
+/// A guide written out in the symbols of an encoding, with what it was written to score
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WrittenGuide {
+    /// The total the guide scores
+    pub target: usize,
+    /// Rounds in the guide
+    pub rounds: usize,
+    /// A line per round
+    pub guide: Vec<String>,
+}
+
+/// The guide as it would be given to the puzzle, a round to a line
+impl fmt::Display for WrittenGuide {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.guide {
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A guide to write: how long, what it should score and what it may contain
+#[derive(Debug, Clone)]
+pub struct Synthesis<'r> {
+    rules: &'r Rules,
+    rounds: usize,
+    target: usize,
+    opponent: Option<Vec<Throw<'r>>>,
+    wins: RangeInclusive<usize>,
+    draws: RangeInclusive<usize>,
+    losses: RangeInclusive<usize>,
+}
+
+/// A choice for a single round and what it leads to
+#[derive(Debug, Clone, Copy)]
+struct Step<'r> {
+    pair: MatchPair<'r>,
+    outcome: Outcome,
+    points: usize,
+}
+
+/// How the count of one outcome is kept during the search
+#[derive(Debug, Clone, Copy)]
+enum Tracking {
+    /// Not kept, as any count meets the bound
+    Ignored,
+    /// Kept up to `low`, as any count from there on meets the bound
+    UpTo { low: usize },
+    /// Kept exactly, as the guide could go past `high`
+    Exact { low: usize, high: usize },
+}
+
+impl Tracking {
+    /// How many counts it can tell apart in a guide of `rounds` rounds
+    fn counts(&self, rounds: usize) -> usize {
+        match *self {
+            Tracking::Ignored => 1,
+            Tracking::UpTo { low } => low.min(rounds) + 1,
+            Tracking::Exact { high, .. } => high.min(rounds) + 1,
+        }
+    }
+}
+
+/// Wins, draws and losses so far, as far as the bounds need them counted
+type Counts = [usize; 3];
+
+/// Totals that can be reached, a bit per total
+type Reachable = Vec<u64>;
+
+/// The totals reachable after some rounds, by the counts so far
+type Layer = BTreeMap<Counts, Reachable>;
+
+fn has(reachable: &Reachable, total: usize) -> bool {
+    reachable
+        .get(total / 64)
+        .is_some_and(|word| word >> (total % 64) & 1 == 1)
+}
+
+/// Add `by` to every total of `from` and mark the results in `into`
+fn add_shifted(into: &mut Reachable, from: &Reachable, by: usize) {
+    let (words, bits) = (by / 64, by % 64);
+    for (index, &word) in from.iter().enumerate() {
+        let Some(target) = into.get_mut(index + words) else {
+            break;
+        };
+        *target |= word << bits;
+        if bits > 0 {
+            if let Some(next) = into.get_mut(index + words + 1) {
+                *next |= word >> (64 - bits);
+            }
+        }
+    }
+}
+
+impl<'r> Synthesis<'r> {
+    /// A guide of `rounds` rounds of a game of `rules` scoring `target` in total
+    pub fn new(rules: &'r Rules, rounds: usize, target: usize) -> Self {
+        Synthesis {
+            rules,
+            rounds,
+            target,
+            opponent: None,
+            wins: 0..=usize::MAX,
+            draws: 0..=usize::MAX,
+            losses: 0..=usize::MAX,
+        }
+    }
+
+    /// The opponent throws `opponent` in turn, instead of whatever suits the guide
+    pub fn with_opponent(mut self, opponent: Vec<Throw<'r>>) -> Self {
+        self.opponent = Some(opponent);
+        self
+    }
+
+    /// The guide wins a number of rounds in `wins`
+    pub fn with_wins(mut self, wins: RangeInclusive<usize>) -> Self {
+        self.wins = wins;
+        self
+    }
+
+    /// The guide draws a number of rounds in `draws`
+    pub fn with_draws(mut self, draws: RangeInclusive<usize>) -> Self {
+        self.draws = draws;
+        self
+    }
+
+    /// The guide loses a number of rounds in `losses`
+    pub fn with_losses(mut self, losses: RangeInclusive<usize>) -> Self {
+        self.losses = losses;
+        self
+    }
+
+    /// A guide whose second column is the throw to play
+    pub fn throws(&self) -> Result<Vec<MatchPair<'r>>, SynthesisError> {
+        self.search(|opponent| {
+            self.rules
+                .throws()
+                .map(|me| MatchPair::new(opponent, me))
+                .collect()
+        })
+    }
+
+    /// A guide written in the symbols of `encoding`, its second column read as
+    /// outcomes if `as_outcomes` and as throws otherwise
+    pub fn write(
+        &self,
+        encoding: &Encoding,
+        as_outcomes: bool,
+    ) -> Result<WrittenGuide, SynthesisError> {
+        let guide: Option<Vec<String>> = if as_outcomes {
+            self.outcomes()?
+                .iter()
+                .map(|strategy| encoding.write_outcomes(strategy))
+                .collect()
+        } else {
+            self.throws()?
+                .iter()
+                .map(|pair| encoding.write_throws(pair))
+                .collect()
+        };
+        let guide = guide.ok_or(SynthesisError::Unwritable)?;
+        Ok(WrittenGuide {
+            target: self.target,
+            rounds: guide.len(),
+            guide,
+        })
+    }
+
+    /// A guide whose second column is how the round should end
+    pub fn outcomes(&self) -> Result<Vec<MatchStrategy<'r>>, SynthesisError> {
+        let strategies = |opponent| {
+            [
+                StrategyChoice::Lose,
+                StrategyChoice::Draw,
+                StrategyChoice::Win,
+            ]
+            .map(|choice| MatchStrategy::new(opponent, choice))
+        };
+        let pairs = self.search(|opponent| {
+            strategies(opponent)
+                .into_iter()
+                .map(MatchPair::from)
+                .collect()
+        })?;
+        Ok(pairs
+            .into_iter()
+            .map(|pair| {
+                strategies(pair.opponent)
+                    .into_iter()
+                    .find(|strategy| MatchPair::from(*strategy) == pair)
+                    .expect("every pair was played for an outcome")
+            })
+            .collect())
+    }
+
+    /// How the count of each outcome, in the order of `OUTCOMES`, is kept during the search
+    fn tracking(&self) -> [Tracking; 3] {
+        [&self.wins, &self.draws, &self.losses].map(|bound| {
+            let (low, high) = (*bound.start(), *bound.end());
+            if high < self.rounds {
+                Tracking::Exact { low, high }
+            } else if low > 0 {
+                Tracking::UpTo { low }
+            } else {
+                Tracking::Ignored
+            }
+        })
+    }
+
+    /// Every distinct step of a round against `opponent`, or any opponent
+    fn steps(
+        &self,
+        opponent: Option<Throw<'r>>,
+        pairs: &impl Fn(Throw<'r>) -> Vec<MatchPair<'r>>,
+    ) -> Vec<Step<'r>> {
+        let opponents: Vec<Throw<'r>> = match opponent {
+            Some(opponent) => vec![opponent],
+            None => self.rules.throws().collect(),
+        };
+        let mut steps: Vec<Step<'r>> = Vec::new();
+        for pair in opponents.into_iter().flat_map(pairs) {
+            let step = Step {
+                pair,
+                outcome: pair.me.wins_over(&pair.opponent),
+                points: pair.get_score(),
+            };
+            // Steps to the same outcome and points lead to the same totals
+            if !steps
+                .iter()
+                .any(|other| (other.outcome, other.points) == (step.outcome, step.points))
+            {
+                steps.push(step);
+            }
+        }
+        steps
+    }
+
+    /// Whether `counts` after `played` rounds can still meet the bounds
+    fn fits(&self, tracking: &[Tracking; 3], counts: Counts, played: usize) -> bool {
+        let mut short = 0;
+        for (tracking, count) in tracking.iter().zip(counts) {
+            match *tracking {
+                Tracking::Ignored => {}
+                Tracking::UpTo { low } => short += low - count,
+                Tracking::Exact { low, high } => {
+                    if count > high {
+                        return false;
+                    }
+                    short += low.saturating_sub(count);
+                }
+            }
+        }
+        // Each round left can only make up for one outcome falling short
+        short <= self.rounds - played
+    }
+
+    /// Counts after a step from `counts`, if they can still meet the bounds
+    fn next(
+        &self,
+        tracking: &[Tracking; 3],
+        mut counts: Counts,
+        outcome: Outcome,
+        played: usize,
+    ) -> Option<Counts> {
+        let slot = place(outcome);
+        match tracking[slot] {
+            Tracking::Ignored => {}
+            Tracking::UpTo { low } => counts[slot] = (counts[slot] + 1).min(low),
+            Tracking::Exact { .. } => counts[slot] += 1,
+        }
+        self.fits(tracking, counts, played).then_some(counts)
+    }
+
+    /// The totals reachable after a round of `steps` from those of `layer`
+    fn advance(
+        &self,
+        tracking: &[Tracking; 3],
+        layer: &Layer,
+        steps: &[Step<'r>],
+        played: usize,
+        words: usize,
+    ) -> Layer {
+        let mut next_layer = Layer::new();
+        for (&counts, reachable) in layer {
+            for step in steps {
+                if let Some(next) = self.next(tracking, counts, step.outcome, played) {
+                    let into = next_layer.entry(next).or_insert_with(|| vec![0; words]);
+                    add_shifted(into, reachable, step.points);
+                }
+            }
+        }
+        next_layer
+    }
+
+    fn search(
+        &self,
+        pairs: impl Fn(Throw<'r>) -> Vec<MatchPair<'r>>,
+    ) -> Result<Vec<MatchPair<'r>>, SynthesisError> {
+        if let Some(opponent) = &self.opponent {
+            if opponent.len() != self.rounds {
+                return Err(SynthesisError::OpponentLength {
+                    given: opponent.len(),
+                    rounds: self.rounds,
+                });
+            }
+        }
+        let tracking = self.tracking();
+        let steps: Vec<Vec<Step<'r>>> = (0..self.rounds)
+            .map(|round| {
+                self.steps(
+                    self.opponent.as_ref().map(|opponent| opponent[round]),
+                    &pairs,
+                )
+            })
+            .collect();
+        // Words of totals each layer needs, as no total can pass the best of every round so far
+        let mut words = Vec::with_capacity(self.rounds + 1);
+        let mut most = 0;
+        words.push(1);
+        for round in &steps {
+            most += round.iter().map(|step| step.points).max().unwrap_or(0);
+            words.push(most / 64 + 1);
+        }
+
+        // Only every `every`th layer is kept on the way forward, and the
+        // layers between two of them are found again on the way back
+        let every = self.rounds.isqrt().max(1);
+        let mut start = Layer::new();
+        if self.fits(&tracking, [0; 3], 0) {
+            start.insert([0; 3], vec![1]);
+        }
+        // Kept layers and those of a stretch between them, at most a set per count told apart
+        let held = self.rounds / every + every + 2;
+        let largest = tracking
+            .iter()
+            .map(|tracking| tracking.counts(self.rounds))
+            .fold(words[self.rounds] * 8, usize::saturating_mul);
+        if largest.saturating_mul(held) > MAX_SEARCH_BYTES {
+            return Err(SynthesisError::TooLarge {
+                rounds: self.rounds,
+                limit: MAX_SEARCH_BYTES,
+            });
+        }
+        let mut checkpoints = Vec::with_capacity(self.rounds / every + 1);
+        let mut layer = start;
+        for (round, round_steps) in steps.iter().enumerate() {
+            if round % every == 0 {
+                checkpoints.push(layer.clone());
+            }
+            layer = self.advance(&tracking, &layer, round_steps, round + 1, words[round + 1]);
+        }
+
+        let last = layer;
+        let Some(mut counts) = last
+            .iter()
+            .filter(|(_, reachable)| has(reachable, self.target))
+            .map(|(&counts, _)| counts)
+            .min()
+        else {
+            let reachable = |total: &usize| last.values().any(|reachable| has(reachable, *total));
+            return Err(SynthesisError::Unreachable {
+                target: self.target,
+                rounds: self.rounds,
+                below: (0..self.target.min(most + 1)).rev().find(reachable),
+                above: (self.target + 1..=most).find(reachable),
+            });
+        };
+        drop(last);
+
+        // Walk back through the rounds, taking any step the round before could lead to
+        let mut total = self.target;
+        let mut guide = Vec::with_capacity(self.rounds);
+        while let Some(checkpoint) = checkpoints.pop() {
+            let from = checkpoints.len() * every;
+            let until = (from + every).min(self.rounds);
+            let mut layers = vec![checkpoint];
+            for round in from..until - 1 {
+                let layer = self.advance(
+                    &tracking,
+                    &layers[round - from],
+                    &steps[round],
+                    round + 1,
+                    words[round + 1],
+                );
+                layers.push(layer);
+            }
+            for round in (from..until).rev() {
+                let (step, before) = steps[round]
+                    .iter()
+                    .find_map(|step| {
+                        let before_total = total.checked_sub(step.points)?;
+                        layers[round - from]
+                            .iter()
+                            .find_map(|(&before, reachable)| {
+                                (self.next(&tracking, before, step.outcome, round + 1)
+                                    == Some(counts)
+                                    && has(reachable, before_total))
+                                .then_some((*step, before))
+                            })
+                    })
+                    .expect("a reachable total was reached from the round before");
+                guide.push(step.pair);
+                total -= step.points;
+                counts = before;
+            }
+        }
+        guide.reverse();
+        Ok(guide)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::CLASSIC;
+    use std::collections::BTreeSet;
+
+    fn total(guide: &[MatchPair]) -> usize {
+        guide.iter().map(MatchPair::get_score).sum()
+    }
+
+    fn count(guide: &[MatchPair], outcome: Outcome) -> usize {
+        guide
+            .iter()
+            .filter(|pair| pair.me.wins_over(&pair.opponent) == outcome)
+            .count()
+    }
+
+    /// Totals of every guide of `rounds` rounds against any opponent, with exactly `wins` wins if given
+    fn brute_force(rounds: usize, wins: Option<usize>) -> BTreeSet<usize> {
+        let pairs: Vec<MatchPair> = CLASSIC
+            .throws()
+            .flat_map(|opponent| CLASSIC.throws().map(move |me| MatchPair::new(opponent, me)))
+            .collect();
+        let mut guides: Vec<Vec<MatchPair>> = vec![Vec::new()];
+        for _ in 0..rounds {
+            guides = guides
+                .into_iter()
+                .flat_map(|guide| {
+                    pairs.iter().map(move |&pair| {
+                        let mut longer = guide.clone();
+                        longer.push(pair);
+                        longer
+                    })
+                })
+                .collect();
+        }
+        guides
+            .iter()
+            .filter(|guide| wins.is_none_or(|wins| count(guide, Outcome::Win) == wins))
+            .map(|guide| total(guide))
+            .collect()
+    }
+
+    #[test]
+    fn sample_totals() {
+        let opponent = vec![Throw::ROCK, Throw::PAPER, Throw::SCISSORS];
+        let synthesis = Synthesis::new(&CLASSIC, 3, 15).with_opponent(opponent.clone());
+        let guide = synthesis.throws().unwrap();
+        assert_eq!(total(&guide), 15);
+        let played: Vec<Throw> = guide.iter().map(|pair| pair.opponent).collect();
+        assert_eq!(played, opponent);
+
+        let strategies = Synthesis::new(&CLASSIC, 3, 12)
+            .with_opponent(opponent)
+            .outcomes()
+            .unwrap();
+        let pairs: Vec<MatchPair> = strategies.into_iter().map(MatchPair::from).collect();
+        assert_eq!(total(&pairs), 12);
+    }
+
+    #[test]
+    fn same_as_every_guide() {
+        for rounds in 0..=3 {
+            let reachable = brute_force(rounds, None);
+            let with_one_win = brute_force(rounds, Some(1));
+            for target in 0..=30 {
+                let found = Synthesis::new(&CLASSIC, rounds, target).throws();
+                assert_eq!(
+                    found.is_ok(),
+                    reachable.contains(&target),
+                    "{rounds} rounds, {target}"
+                );
+                if let Ok(guide) = found {
+                    assert_eq!((guide.len(), total(&guide)), (rounds, target));
+                }
+
+                let found = Synthesis::new(&CLASSIC, rounds, target)
+                    .with_wins(1..=1)
+                    .throws();
+                assert_eq!(found.is_ok(), with_one_win.contains(&target));
+                if let Ok(guide) = found {
+                    assert_eq!(count(&guide, Outcome::Win), 1);
+                    assert_eq!(total(&guide), target);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bounds() {
+        let guide = Synthesis::new(&CLASSIC, 10, 40)
+            .with_draws(3..=4)
+            .with_losses(5..=usize::MAX)
+            .throws()
+            .unwrap();
+        assert_eq!(total(&guide), 40);
+        assert!((3..=4).contains(&count(&guide, Outcome::Draw)));
+        assert!(count(&guide, Outcome::Lose) >= 5);
+
+        // Ten wins score at least 70
+        assert_eq!(
+            Synthesis::new(&CLASSIC, 10, 69).with_wins(10..=10).throws(),
+            Err(SynthesisError::Unreachable {
+                target: 69,
+                rounds: 10,
+                below: None,
+                above: Some(70),
+            })
+        );
+        // Bounds no guide can meet
+        assert!(Synthesis::new(&CLASSIC, 2, 10)
+            .with_wins(2..=2)
+            .with_draws(1..=1)
+            .throws()
+            .is_err());
+    }
+
+    #[test]
+    fn bound_ranges() {
+        assert_eq!(parse_bound("3"), Ok(3..=3));
+        assert_eq!(parse_bound("2..5"), Ok(2..=5));
+        assert_eq!(parse_bound("2.."), Ok(2..=usize::MAX));
+        assert_eq!(parse_bound("..5"), Ok(0..=5));
+        assert_eq!(
+            parse_bound("a..5"),
+            Err(String::from("`a` is not a count of rounds"))
+        );
+    }
+
+    #[test]
+    fn written_out() {
+        let encoding = Encoding::classic();
+        let synthesis = Synthesis::new(&CLASSIC, 3, 15);
+        for as_outcomes in [false, true] {
+            let written = synthesis.write(&encoding, as_outcomes).unwrap();
+            assert_eq!((written.target, written.rounds), (15, 3));
+            let rounds = encoding.parse_guide(&written.to_string()).unwrap();
+            let score = if as_outcomes {
+                crate::score_as_outcomes(&rounds)
+            } else {
+                crate::score_as_throws(&rounds)
+            };
+            assert_eq!(score, 15);
+        }
+    }
+
+    #[test]
+    fn proof_of_no_guide() {
+        // Against rock, rounds score 3, 4 or 8
+        let err = Synthesis::new(&CLASSIC, 1, 5)
+            .with_opponent(vec![Throw::ROCK])
+            .throws()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            SynthesisError::Unreachable {
+                target: 5,
+                rounds: 1,
+                below: Some(4),
+                above: Some(8),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "no guide of 1 rounds scores 5, the nearest totals are 4 and 8"
+        );
+        assert_eq!(
+            Synthesis::new(&CLASSIC, 2, 100).throws(),
+            Err(SynthesisError::Unreachable {
+                target: 100,
+                rounds: 2,
+                below: Some(18),
+                above: None,
+            })
+        );
+        assert_eq!(
+            Synthesis::new(&CLASSIC, 2, 5)
+                .with_opponent(vec![Throw::ROCK])
+                .throws(),
+            Err(SynthesisError::OpponentLength {
+                given: 1,
+                rounds: 2
+            })
+        );
+    }
+
+    #[test]
+    fn long_guides() {
+        let guide = Synthesis::new(&CLASSIC, 2500, 13052).throws().unwrap();
+        assert_eq!(total(&guide), 13052);
+        let guide = Synthesis::new(&CLASSIC, 40, 200)
+            .with_wins(10..=12)
+            .with_draws(15..=20)
+            .throws()
+            .unwrap();
+        assert_eq!(total(&guide), 200);
+        assert!((10..=12).contains(&count(&guide, Outcome::Win)));
+        assert!((15..=20).contains(&count(&guide, Outcome::Draw)));
+
+        // A lower bound alone stops mattering once it is met
+        let guide = Synthesis::new(&CLASSIC, 400, 2000)
+            .with_wins(150..=usize::MAX)
+            .throws()
+            .unwrap();
+        assert_eq!(total(&guide), 2000);
+        assert!(count(&guide, Outcome::Win) >= 150);
+    }
+
+    #[test]
+    fn too_large() {
+        assert_eq!(
+            Synthesis::new(&CLASSIC, 2500, 13000)
+                .with_wins(1000..=usize::MAX)
+                .with_draws(0..=1000)
+                .throws(),
+            Err(SynthesisError::TooLarge {
+                rounds: 2500,
+                limit: MAX_SEARCH_BYTES,
+            })
+        );
+    }
+}
@@ -0,0 +1,386 @@
+//! Tournaments between bots, with score tables and Elo ratings
+//!
+//! A match is a number of rounds scored as the strategy guide scores them, and
+//! the bot scoring more wins it. Ratings start at 1500 and move after every
+//! match by how surprising its result was.
+
+use crate::bots::{Bot, Played};
+use crate::rules::Rules;
+use crate::{MatchPair, Outcome};
+use common::generate::{rng, Rng};
+use serde::Serialize;
+use std::fmt;
+
+/// Rating of a bot before its first match
+pub const INITIAL_ELO: f64 = 1500.0;
+
+/// Most a rating moves after a single match
+pub const ELO_K: f64 = 32.0;
+
+/// How a tournament is run and scored
+#[derive(Debug, Clone, Copy)]
+pub struct Tournament<'r> {
+    rules: &'r Rules,
+    rounds: usize,
+    seed: u64,
+}
+
+/// A match between two bots, by their position in the list of bots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MatchResult {
+    /// The two bots
+    pub bots: [usize; 2],
+    /// Points each scored over the match
+    pub scores: [usize; 2],
+    /// Rounds each won
+    pub wins: [usize; 2],
+    /// Rounds neither won
+    pub draws: usize,
+}
+
+impl MatchResult {
+    /// The bot scoring more, if either did
+    pub fn winner(&self) -> Option<usize> {
+        match self.scores[0].cmp(&self.scores[1]) {
+            std::cmp::Ordering::Greater => Some(self.bots[0]),
+            std::cmp::Ordering::Less => Some(self.bots[1]),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+/// How a bot did over the tournament
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Standing {
+    /// Position of the bot in the list of bots
+    pub bot: usize,
+    /// Name of the bot
+    pub name: String,
+    /// Matches played
+    pub played: usize,
+    /// Matches won
+    pub won: usize,
+    /// Matches drawn
+    pub drawn: usize,
+    /// Matches lost
+    pub lost: usize,
+    /// Points scored over every round of every match
+    pub score: usize,
+    /// Rating after the last match
+    pub elo: f64,
+}
+
+/// Every match of a tournament and the standings it left
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Table {
+    /// The matches in the order they were played
+    pub matches: Vec<MatchResult>,
+    /// A standing per bot, the most matches won first, then the most points
+    pub standings: Vec<Standing>,
+}
+
+impl Table {
+    fn new(bots: &[Box<dyn Bot>]) -> Self {
+        let standings = bots
+            .iter()
+            .enumerate()
+            .map(|(bot, player)| Standing {
+                bot,
+                name: player.name().to_string(),
+                played: 0,
+                won: 0,
+                drawn: 0,
+                lost: 0,
+                score: 0,
+                elo: INITIAL_ELO,
+            })
+            .collect();
+        Table {
+            matches: Vec::new(),
+            standings,
+        }
+    }
+
+    /// Count a match into the standings of both bots
+    fn record(&mut self, result: MatchResult) {
+        let [first, second] = result.bots;
+        let points = match result.winner() {
+            Some(winner) if winner == first => 1.0,
+            Some(_) => 0.0,
+            None => 0.5,
+        };
+        let expected = 1.0
+            / (1.0 + 10f64.powf((self.standings[second].elo - self.standings[first].elo) / 400.0));
+        let change = ELO_K * (points - expected);
+        for (side, bot) in result.bots.into_iter().enumerate() {
+            let standing = &mut self.standings[bot];
+            standing.played += 1;
+            standing.score += result.scores[side];
+            match result.winner() {
+                None => standing.drawn += 1,
+                Some(winner) if winner == bot => standing.won += 1,
+                Some(_) => standing.lost += 1,
+            }
+            standing.elo += if side == 0 { change } else { -change };
+        }
+        self.matches.push(result);
+    }
+
+    fn rank(mut self) -> Self {
+        self.standings
+            .sort_by_key(|standing| std::cmp::Reverse((standing.won, standing.score)));
+        self
+    }
+}
+
+impl<'r> Tournament<'r> {
+    /// Matches of `rounds` rounds of a game of `rules`, random play seeded with `seed`
+    pub fn new(rules: &'r Rules, rounds: usize, seed: u64) -> Self {
+        Tournament {
+            rules,
+            rounds,
+            seed,
+        }
+    }
+
+    /// Play a single match between two bots, known in the result as 0 and 1
+    pub fn play(&self, first: &mut dyn Bot, second: &mut dyn Bot, rng: &mut Rng) -> MatchResult {
+        self.play_match([0, 1], [first, second], rng)
+    }
+
+    fn play_match(
+        &self,
+        bots: [usize; 2],
+        players: [&mut dyn Bot; 2],
+        rng: &mut Rng,
+    ) -> MatchResult {
+        let [first, second] = players;
+        let mut history: [Vec<Played<'r>>; 2] = [Vec::new(), Vec::new()];
+        let mut result = MatchResult {
+            bots,
+            scores: [0, 0],
+            wins: [0, 0],
+            draws: 0,
+        };
+        for _ in 0..self.rounds {
+            let throws = [
+                first.throw(self.rules, &history[0], rng),
+                second.throw(self.rules, &history[1], rng),
+            ];
+            for side in 0..2 {
+                let (mine, theirs) = (throws[side], throws[1 - side]);
+                result.scores[side] += MatchPair::new(theirs, mine).get_score();
+                if mine.wins_over(&theirs) == Outcome::Win {
+                    result.wins[side] += 1;
+                }
+                history[side].push(Played { mine, theirs });
+            }
+            if throws[0].wins_over(&throws[1]) == Outcome::Draw {
+                result.draws += 1;
+            }
+        }
+        result
+    }
+
+    /// The two bots at `pair`, borrowed at once
+    fn pair(bots: &mut [Box<dyn Bot>], pair: [usize; 2]) -> [&mut dyn Bot; 2] {
+        let [first, second] = pair;
+        assert!(first < second, "a bot cannot play itself");
+        let (head, tail) = bots.split_at_mut(second);
+        [head[first].as_mut(), tail[0].as_mut()]
+    }
+
+    /// Every bot plays every other once, the one listed first taking the first side
+    pub fn round_robin(&self, bots: &mut [Box<dyn Bot>]) -> Table {
+        let mut rng = rng(self.seed);
+        let mut table = Table::new(bots);
+        for first in 0..bots.len() {
+            for second in first + 1..bots.len() {
+                let pair = [first, second];
+                let result = self.play_match(pair, Self::pair(bots, pair), &mut rng);
+                table.record(result);
+            }
+        }
+        table.rank()
+    }
+
+    /// Bots play in pairs in the order listed, and the winners go on until one is left
+    ///
+    /// A bot without a partner goes on to the next stage without playing, and
+    /// the bot listed first goes on from a drawn match. Returns the table and
+    /// the winning bot, if there were any bots.
+    pub fn elimination(&self, bots: &mut [Box<dyn Bot>]) -> (Table, Option<usize>) {
+        let mut rng = rng(self.seed);
+        let mut table = Table::new(bots);
+        let mut left: Vec<usize> = (0..bots.len()).collect();
+        while left.len() > 1 {
+            left = left
+                .chunks(2)
+                .map(|stage| match *stage {
+                    [first, second] => {
+                        let result = self.play_match(
+                            [first, second],
+                            Self::pair(bots, [first, second]),
+                            &mut rng,
+                        );
+                        let winner = result.winner().unwrap_or(first);
+                        table.record(result);
+                        winner
+                    }
+                    [bye] => bye,
+                    _ => unreachable!("chunks of at most two"),
+                })
+                .collect();
+        }
+        (table.rank(), left.first().copied())
+    }
+}
+
+/// What the tournament mode shows
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    /// The matches and standings
+    #[serde(flatten)]
+    pub table: Table,
+    /// Name of the last bot standing, when there was an elimination
+    pub champion: Option<String>,
+}
+
+impl Report {
+    /// Report on `table`, naming `champion` from among `bots`
+    pub fn new(table: Table, champion: Option<usize>, bots: &[Box<dyn Bot>]) -> Self {
+        Report {
+            table,
+            champion: champion.map(|bot| bots[bot].name().to_string()),
+        }
+    }
+}
+
+/// The standings as a table of text, then the champion if there is one
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<20} {:>6} {:>4} {:>4} {:>4} {:>9} {:>7}",
+            "Bot", "Played", "Won", "Drew", "Lost", "Score", "Elo"
+        )?;
+        for standing in &self.table.standings {
+            writeln!(
+                f,
+                "{:<20} {:>6} {:>4} {:>4} {:>4} {:>9} {:>7.1}",
+                standing.name,
+                standing.played,
+                standing.won,
+                standing.drawn,
+                standing.lost,
+                standing.score,
+                standing.elo
+            )?;
+        }
+        if let Some(champion) = &self.champion {
+            writeln!(f, "Champion: {champion}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bots::{every_bot, FixedGuide, Frequency, Random, WinStayLoseShift};
+    use crate::rules::CLASSIC;
+
+    fn bots() -> Vec<Box<dyn Bot>> {
+        every_bot(FixedGuide::new("rock", vec![0]))
+    }
+
+    #[test]
+    fn a_single_match() {
+        let tournament = Tournament::new(&CLASSIC, 10, 0);
+        let mut rock = FixedGuide::new("rock", vec![0]);
+        let mut paper = FixedGuide::new("paper", vec![1]);
+        let result = tournament.play(&mut rock, &mut paper, &mut rng(0));
+        assert_eq!(result.scores, [10, 80]);
+        assert_eq!(result.wins, [0, 10]);
+        assert_eq!(result.winner(), Some(1));
+
+        // Frequency play catches on to a bot always throwing rock after one round
+        let result = tournament.play(&mut rock, &mut Frequency, &mut rng(0));
+        assert!(result.wins[1] >= 9);
+    }
+
+    #[test]
+    fn round_robin() {
+        let mut bots = bots();
+        let table = Tournament::new(&CLASSIC, 100, 7).round_robin(&mut bots);
+        assert_eq!(table.matches.len(), 10);
+        for standing in &table.standings {
+            assert_eq!(standing.played, 4);
+            assert_eq!(standing.won + standing.drawn + standing.lost, 4);
+        }
+        let total: usize = table.standings.iter().map(|standing| standing.score).sum();
+        let scored: usize = table.matches.iter().flat_map(|found| found.scores).sum();
+        assert_eq!(total, scored);
+        // Ratings move from one bot to another and are never made up
+        let elo: f64 = table.standings.iter().map(|standing| standing.elo).sum();
+        assert!((elo - 5.0 * INITIAL_ELO).abs() < 1e-6);
+        // The bot always throwing rock is found out by everything but random play
+        let last = table.standings.last().unwrap();
+        assert_eq!(last.name, "rock");
+    }
+
+    #[test]
+    fn seeded() {
+        let play = |seed| Tournament::new(&CLASSIC, 50, seed).round_robin(&mut bots());
+        assert_eq!(play(3), play(3));
+        assert_ne!(play(3), play(4));
+    }
+
+    #[test]
+    fn elimination() {
+        let mut bots = bots();
+        let (table, winner) = Tournament::new(&CLASSIC, 100, 2).elimination(&mut bots);
+        // Five bots take four matches to be down to one
+        assert_eq!(table.matches.len(), 4);
+        let winner = winner.unwrap();
+        let champion = table
+            .standings
+            .iter()
+            .find(|standing| standing.bot == winner);
+        assert_eq!(champion.unwrap().lost, 0);
+
+        let mut single: Vec<Box<dyn Bot>> = vec![Box::new(Random)];
+        let (table, winner) = Tournament::new(&CLASSIC, 10, 0).elimination(&mut single);
+        assert!(table.matches.is_empty());
+        assert_eq!(winner, Some(0));
+        assert_eq!(
+            Tournament::new(&CLASSIC, 10, 0).elimination(&mut []).1,
+            None
+        );
+    }
+
+    #[test]
+    fn drawn_matches_go_to_the_first() {
+        let mut bots: Vec<Box<dyn Bot>> = vec![
+            Box::new(FixedGuide::new("rock", vec![0])),
+            Box::new(WinStayLoseShift),
+        ];
+        let tournament = Tournament::new(&CLASSIC, 0, 0);
+        let (table, winner) = tournament.elimination(&mut bots);
+        assert_eq!(table.matches[0].winner(), None);
+        assert_eq!(winner, Some(0));
+    }
+
+    #[test]
+    fn report() {
+        let mut bots = bots();
+        let (table, winner) = Tournament::new(&CLASSIC, 100, 2).elimination(&mut bots);
+        let report = Report::new(table, winner, &bots);
+        let text = report.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 1 + 5 + 1);
+        assert!(lines[0].starts_with("Bot "));
+        let champion = report.champion.as_deref().unwrap();
+        assert_eq!(lines[6], format!("Champion: {champion}"));
+    }
+}
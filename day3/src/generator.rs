@@ -0,0 +1,82 @@
+//! Rucksack lists with known answers
+
+use crate::Day3;
+use common::generate::{rng, Generate, Generated};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+fn priority(item: u8) -> usize {
+    match item {
+        b'a'..=b'z' => (item - b'a') as usize + 1,
+        _ => (item - b'A') as usize + 27,
+    }
+}
+
+/// A list of at least `rucksacks` rucksacks, rounded up to whole groups of three
+pub fn generate(seed: u64, rucksacks: usize) -> Generated {
+    let mut rng = rng(seed);
+    let mut input = String::new();
+    let (mut shared, mut badges) = (0, 0);
+    for _ in 0..rucksacks.div_ceil(3) {
+        let badge = ITEMS[rng.random_range(0..ITEMS.len())];
+        badges += priority(badge);
+
+        // Each bag in the group only holds items from its own pool and the
+        // badge, so the badge is the only item all three have in common
+        let mut others: Vec<u8> = ITEMS.iter().copied().filter(|&i| i != badge).collect();
+        others.shuffle(&mut rng);
+        for pool in others.chunks(others.len() / 3) {
+            let both = if rng.random_bool(0.2) {
+                badge
+            } else {
+                pool[rng.random_range(0..pool.len())]
+            };
+            shared += priority(both);
+
+            // The rest of the pool goes to one compartment or the other, never both
+            let rest: Vec<u8> = pool.iter().copied().filter(|&i| i != both).collect();
+            let (left, right) = rest.split_at(rest.len() / 2);
+            let size = rng.random_range(2..=16);
+            let mut compartments = [vec![both], vec![both]];
+            if both != badge {
+                compartments[rng.random_range(0..2)].push(badge);
+            }
+            for (compartment, pool) in compartments.iter_mut().zip([left, right]) {
+                while compartment.len() < size {
+                    compartment.push(pool[rng.random_range(0..pool.len())]);
+                }
+                compartment.shuffle(&mut rng);
+                input.push_str(std::str::from_utf8(compartment).unwrap());
+            }
+            input.push('\n');
+        }
+    }
+
+    Generated::new(input, shared, badges)
+}
+
+impl Generate for Day3 {
+    fn generate(seed: u64, size: usize) -> Generated {
+        generate(seed, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solution_agrees() {
+        for seed in 0..20 {
+            let generated = generate(seed, 60);
+            assert_eq!(generated.answers(), generated.solve::<Day3>().unwrap());
+        }
+    }
+
+    #[test]
+    fn whole_groups() {
+        assert_eq!(6, generate(1, 4).input.lines().count());
+    }
+}
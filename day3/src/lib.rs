@@ -0,0 +1,814 @@
+//! Day 3: Rucksack Reorganization, finding misplaced items and group badges
+//!
+//! There are only 52 item types, so every set of items is a `u64` with a bit
+//! per type. The items two compartments or a whole group share are then a
+//! bitwise AND of their sets.
+#![warn(missing_docs)]
+
+pub mod generator;
+
+use common::report::{self, Part};
+use common::{parse_lines, Answer, Located, Location, Report, Solution};
+use std::ops::{BitAnd, BitOr};
+use thiserror::Error;
+
+/// Why a rucksack could not be read
+///
+/// Every variant holds the `location` of the problem and the `text` found there.
+#[allow(missing_docs)]
+#[derive(Debug, PartialEq, Error)]
+pub enum ParseError {
+    /// Anything but a letter
+    #[error("{location}: `{text}` is not an item type")]
+    InvalidItem { location: Location, text: String },
+    /// A line that can't be halved
+    #[error("{location}: `{text}` can't be split into two equal compartments")]
+    OddItemCount { location: Location, text: String },
+}
+
+impl Located for ParseError {
+    fn location_mut(&mut self) -> &mut Location {
+        match self {
+            ParseError::InvalidItem { location, .. }
+            | ParseError::OddItemCount { location, .. } => location,
+        }
+    }
+}
+
+/// Why rucksacks could not be put into groups
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum GroupError {
+    /// Groups of no rucksacks at all
+    #[error("a group needs at least one rucksack")]
+    NoRucksacks,
+    /// Rucksacks after the last whole group, when they are not allowed
+    #[error("{left} rucksacks are left over after the last whole group of {size}")]
+    Leftovers {
+        /// Rucksacks in a whole group
+        size: usize,
+        /// Rucksacks after the last whole group
+        left: usize,
+    },
+}
+
+/// Why a puzzle input could not be solved
+#[derive(Debug, PartialEq, Error)]
+pub enum InputError {
+    /// A rucksack that could not be read
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    /// Rucksacks that could not be put into groups
+    #[error(transparent)]
+    Group(#[from] GroupError),
+}
+
+/// What to do with the rucksacks after the last whole group
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Leftovers {
+    /// Fail, as every rucksack should be in a group
+    #[default]
+    Error,
+    /// Leave them out, so they have no badge to count
+    Ignore,
+    /// Put them in a group of their own, smaller than the others
+    ShortGroup,
+}
+
+/// A rucksack, with the first half of its items in one compartment and the rest in the other
+#[derive(Debug, PartialEq, Clone)]
+pub struct Rucksack {
+    cpt1: Compartment,
+    cpt2: Compartment,
+}
+
+impl Rucksack {
+    /// The item types found in both compartments
+    pub fn find_common_item(&self) -> ItemSet {
+        self.cpt1.items & self.cpt2.items
+    }
+
+    /// Every item type in the rucksack, whichever compartment it is in
+    pub fn merge_compartments(&self) -> ItemSet {
+        self.cpt1.items | self.cpt2.items
+    }
+}
+
+impl TryFrom<&str> for Rucksack {
+    type Error = ParseError;
+
+    fn try_from(from: &str) -> Result<Rucksack, ParseError> {
+        // Anything but a letter is an error, so once every byte has turned
+        // out to be an item the line can be halved by length
+        let half = from.len() / 2;
+        let (mut first, mut second) = (0, 0);
+        for (offset, byte) in from.bytes().enumerate() {
+            let Some(priority) = priority(byte) else {
+                // Every byte before was a letter, so a character starts here
+                let text = from[offset..].chars().next().unwrap_or_default();
+                return Err(ParseError::InvalidItem {
+                    location: Location::column(offset + 1),
+                    text: text.to_string(),
+                });
+            };
+            if offset < half {
+                first |= 1 << priority;
+            } else {
+                second |= 1 << priority;
+            }
+        }
+        if !from.len().is_multiple_of(2) {
+            return Err(ParseError::OddItemCount {
+                location: Location::column(1),
+                text: from.to_string(),
+            });
+        }
+        Ok(Rucksack {
+            cpt1: Compartment {
+                items: ItemSet(first),
+            },
+            cpt2: Compartment {
+                items: ItemSet(second),
+            },
+        })
+    }
+}
+
+/// One half of a rucksack
+#[derive(Debug, PartialEq, Clone)]
+pub struct Compartment {
+    items: ItemSet,
+}
+
+impl TryFrom<&str> for Compartment {
+    type Error = ParseError;
+
+    fn try_from(from: &str) -> Result<Compartment, ParseError> {
+        Ok(Compartment {
+            items: from
+                .char_indices()
+                .map(|(offset, c)| SupplyItem::try_from(c).map_err(|err| err.shifted(offset)))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// An item, which is a single letter
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct SupplyItem {
+    itemtype: Itemtype,
+    priority: Priority,
+}
+
+/// Priority of the item type of every byte, 0 for anything but a letter
+///
+/// Looking the priority up saves a hard to predict branch between lower and
+/// upper case on every item.
+const PRIORITIES: [u8; 256] = {
+    let mut priorities = [0; 256];
+    let mut priority = 0;
+    while priority < 26 {
+        priorities[(b'a' + priority) as usize] = priority + 1;
+        priorities[(b'A' + priority) as usize] = priority + 27;
+        priority += 1;
+    }
+    priorities
+};
+
+/// Priority of the item type `byte` stands for, if it is a letter
+fn priority(byte: u8) -> Option<usize> {
+    match PRIORITIES[byte as usize] {
+        0 => None,
+        priority => Some(priority as usize),
+    }
+}
+
+const PRIOLIST: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+impl TryFrom<char> for SupplyItem {
+    type Error = ParseError;
+
+    fn try_from(from: char) -> Result<SupplyItem, ParseError> {
+        let prio =
+            u8::try_from(from)
+                .ok()
+                .and_then(priority)
+                .ok_or_else(|| ParseError::InvalidItem {
+                    location: Location::column(1),
+                    text: from.to_string(),
+                })?;
+        Ok(SupplyItem {
+            itemtype: Itemtype(from),
+            priority: Priority(prio),
+        })
+    }
+}
+
+impl SupplyItem {
+    /// The item of priority `priority`, if there is one
+    fn with_priority(priority: usize) -> Option<SupplyItem> {
+        let itemtype = *PRIOLIST.as_bytes().get(priority.checked_sub(1)?)?;
+        Some(SupplyItem {
+            itemtype: Itemtype(itemtype as char),
+            priority: Priority(priority),
+        })
+    }
+
+    /// The bit standing for the item's type in an `ItemSet`
+    fn bit(&self) -> u64 {
+        1 << self.priority()
+    }
+
+    /// 1 to 26 for `a` to `z`, 27 to 52 for `A` to `Z`
+    pub fn priority(&self) -> usize {
+        *self.priority
+    }
+}
+
+/// The letter of an item
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Itemtype(char);
+
+/// How important it is to rearrange an item
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Priority(usize);
+
+impl std::ops::Deref for Priority {
+    type Target = usize;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// A set of item types, bit `n` standing for the type of priority `n`
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    /// No items at all
+    pub const EMPTY: ItemSet = ItemSet(0);
+    /// Every item type there is
+    pub const ALL: ItemSet = ItemSet(((1 << 52) - 1) << 1);
+
+    /// Whether the set has the type of `item`
+    pub fn contains(&self, item: SupplyItem) -> bool {
+        self.0 & item.bit() != 0
+    }
+
+    /// How many item types are in the set
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Whether the set has no items
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Every item type in the set, lowest priority first
+    pub fn items(&self) -> impl Iterator<Item = SupplyItem> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            let priority = bits.trailing_zeros() as usize;
+            bits &= bits.checked_sub(1)?;
+            SupplyItem::with_priority(priority)
+        })
+    }
+
+    /// The priorities of every item type in the set, added up
+    pub fn priority_sum(&self) -> usize {
+        let (mut bits, mut sum) = (self.0, 0);
+        while bits != 0 {
+            sum += bits.trailing_zeros() as usize;
+            bits &= bits - 1;
+        }
+        sum
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+}
+
+impl BitOr for ItemSet {
+    type Output = ItemSet;
+
+    fn bitor(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+}
+
+impl FromIterator<SupplyItem> for ItemSet {
+    fn from_iter<I: IntoIterator<Item = SupplyItem>>(items: I) -> Self {
+        ItemSet(items.into_iter().fold(0, |bits, item| bits | item.bit()))
+    }
+}
+
+/// A group of bags, three to a group in the puzzle
+#[derive(Debug, PartialEq)]
+pub struct Group<'a> {
+    bags: Vec<&'a Rucksack>,
+}
+
+impl<'a> Group<'a> {
+    /// A group carrying `bags`
+    pub fn new(bags: Vec<&'a Rucksack>) -> Self {
+        Group { bags }
+    }
+
+    /// The item types of every bag, compartments merged
+    pub fn get_bag_contents_merged(&self) -> Vec<ItemSet> {
+        self.bags
+            .iter()
+            .map(|bag| bag.merge_compartments())
+            .collect()
+    }
+
+    /// The item types every bag in the group has, which is the badge
+    ///
+    /// A group without bags has no badge.
+    pub fn get_common_item(&self) -> ItemSet {
+        if self.bags.is_empty() {
+            return ItemSet::EMPTY;
+        }
+        self.bags.iter().fold(ItemSet::ALL, |common, bag| {
+            common & bag.merge_compartments()
+        })
+    }
+}
+
+/// Find the item type that appears in both compartments of each rucksack and sum their priorities
+pub fn sum_common_priorities(sacks: &[Rucksack]) -> usize {
+    sacks
+        .iter()
+        .map(|sack| sack.find_common_item().priority_sum())
+        .sum()
+}
+
+/// Put the rucksacks into groups of `size` in order, dealing with any left over as `leftovers` says
+pub fn into_groups(
+    sacks: &[Rucksack],
+    size: usize,
+    leftovers: Leftovers,
+) -> Result<Vec<Group<'_>>, GroupError> {
+    if size == 0 {
+        return Err(GroupError::NoRucksacks);
+    }
+    let left = sacks.len() % size;
+    let whole = &sacks[..sacks.len() - left];
+    let mut groups: Vec<Group> = whole
+        .chunks_exact(size)
+        .map(|sacks| Group::new(sacks.iter().collect()))
+        .collect();
+    match leftovers {
+        _ if left == 0 => {}
+        Leftovers::Error => return Err(GroupError::Leftovers { size, left }),
+        Leftovers::Ignore => {}
+        Leftovers::ShortGroup => groups.push(Group::new(sacks[whole.len()..].iter().collect())),
+    }
+    Ok(groups)
+}
+
+/// Find the badge shared by every group of `size` rucksacks and sum their priorities
+pub fn sum_group_badges(
+    sacks: &[Rucksack],
+    size: usize,
+    leftovers: Leftovers,
+) -> Result<usize, GroupError> {
+    Ok(into_groups(sacks, size, leftovers)?
+        .iter()
+        .map(|group| group.get_common_item().priority_sum())
+        .sum())
+}
+
+/// Find the badge shared by every group of three rucksacks and sum their priorities
+///
+/// Every rucksack has to be in a group, as `Leftovers::default()` has it.
+pub fn sum_badge_priorities(sacks: &[Rucksack]) -> Result<usize, GroupError> {
+    sum_group_badges(sacks, 3, Leftovers::default())
+}
+
+/// Solve each of `parts` with groups of `size` and leftovers dealt with as
+/// `leftovers`, timing every step as `common::report::solve` does
+pub fn solve(
+    input: &str,
+    parts: &[Part],
+    size: usize,
+    leftovers: Leftovers,
+) -> Result<Vec<Report>, InputError> {
+    report::solve_with(
+        Day3::DAY,
+        input,
+        parts,
+        |input| Ok(parse_lines::<Rucksack>(input)?),
+        |part, sacks| match part {
+            Part::One => Ok(Answer::new(sum_common_priorities(sacks))),
+            Part::Two => Ok(Answer::new(sum_group_badges(sacks, size, leftovers)?)),
+        },
+    )
+}
+
+/// Rucksack Reorganization
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Parsed<'a> = Vec<Rucksack>;
+    type PartOne = usize;
+    type PartTwo = usize;
+    type Error = InputError;
+
+    fn parse(input: &str) -> Result<Vec<Rucksack>, InputError> {
+        Ok(parse_lines(input)?)
+    }
+
+    fn part_one(sacks: &Vec<Rucksack>) -> Result<usize, InputError> {
+        Ok(sum_common_priorities(sacks))
+    }
+
+    fn part_two(sacks: &Vec<Rucksack>) -> Result<usize, InputError> {
+        Ok(sum_badge_priorities(sacks)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn try_parse_supplyitem_p() {
+        let char = 'p';
+        let itm = SupplyItem::try_from(char).unwrap();
+        assert_eq!(Itemtype('p'), itm.itemtype);
+        assert_eq!(Priority(16), itm.priority);
+    }
+
+    #[test]
+    fn try_parse_supplyitem_upper_l() {
+        let char = 'L';
+        let itm = SupplyItem::try_from(char).unwrap();
+        assert_eq!(Itemtype('L'), itm.itemtype);
+        assert_eq!(Priority(38), itm.priority);
+    }
+    #[test]
+    fn try_parse_supplyitem_upper_p() {
+        let char = 'P';
+        let itm = SupplyItem::try_from(char).unwrap();
+        assert_eq!(Itemtype('P'), itm.itemtype);
+        assert_eq!(Priority(42), itm.priority);
+    }
+
+    #[test]
+    fn try_parse_supplyitem_v() {
+        let char = 'v';
+        let itm = SupplyItem::try_from(char).unwrap();
+        assert_eq!(Itemtype('v'), itm.itemtype);
+        assert_eq!(Priority(22), itm.priority);
+    }
+
+    #[test]
+    fn try_parse_supplyitem_t() {
+        let char = 't';
+        let itm = SupplyItem::try_from(char).unwrap();
+        assert_eq!(Itemtype('t'), itm.itemtype);
+        assert_eq!(Priority(20), itm.priority);
+    }
+
+    #[test]
+    fn try_parse_supplyitem_s() {
+        let char = 's';
+        let itm = SupplyItem::try_from(char).unwrap();
+        assert_eq!(Itemtype('s'), itm.itemtype);
+        assert_eq!(Priority(19), itm.priority);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Err(ParseError::InvalidItem {
+                location: Location::column(4),
+                text: String::from("4"),
+            }),
+            Rucksack::try_from("abc4")
+        );
+        assert_eq!(
+            Err(ParseError::OddItemCount {
+                location: Location::column(1),
+                text: String::from("abc"),
+            }),
+            Rucksack::try_from("abc")
+        );
+        assert_eq!(
+            Err(InputError::Parse(ParseError::InvalidItem {
+                location: Location::new(2, 2),
+                text: String::from("é"),
+            })),
+            Day3::parse("abcd\naébc\n")
+        );
+    }
+
+    #[test]
+    fn item_sets() {
+        let items: ItemSet = "aZbZa"
+            .chars()
+            .map(|c| SupplyItem::try_from(c).unwrap())
+            .collect();
+        assert_eq!(items.len(), 3);
+        assert!(items.contains(SupplyItem::try_from('Z').unwrap()));
+        assert!(!items.contains(SupplyItem::try_from('z').unwrap()));
+        let letters: String = items.items().map(|item| item.itemtype.0).collect();
+        assert_eq!(letters, "abZ");
+        assert_eq!(items.priority_sum(), 1 + 2 + 52);
+        assert_eq!(ItemSet::ALL.len(), 52);
+        assert_eq!(ItemSet::ALL.priority_sum(), (1..=52).sum::<usize>());
+        assert!(ItemSet::EMPTY.is_empty());
+    }
+
+    #[test]
+    fn badge_of_a_group() {
+        let sacks = Day3::parse(
+            "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\n",
+        )
+        .unwrap();
+        let group = Group::new(sacks.iter().collect());
+        let badge: Vec<SupplyItem> = group.get_common_item().items().collect();
+        assert_eq!(badge, [SupplyItem::try_from('r').unwrap()]);
+        assert_eq!(Group::new(vec![]).get_common_item(), ItemSet::EMPTY);
+        assert_eq!(Day3::part_two(&sacks), Ok(18));
+    }
+
+    /// `groups` groups of `size` rucksacks, every group with only `badge` in common
+    fn with_badge(groups: usize, size: usize, badge: char) -> Vec<Rucksack> {
+        // Every rucksack has a letter of its own next to the badge, twice over
+        // so both compartments hold the same items
+        let letters = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ".replace(badge, "");
+        letters
+            .chars()
+            .cycle()
+            .take(groups * size)
+            .map(|own| Rucksack::try_from(format!("{own}{badge}{own}{badge}").as_str()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn groups_of_any_size() {
+        for (size, badge) in [(2, 'b'), (3, 'Q'), (4, 'z'), (7, 'A')] {
+            let sacks = with_badge(5, size, badge);
+            let groups = into_groups(&sacks, size, Leftovers::Error).unwrap();
+            assert_eq!(groups.len(), 5);
+            for group in &groups {
+                let common: Vec<SupplyItem> = group.get_common_item().items().collect();
+                assert_eq!(
+                    common,
+                    [SupplyItem::try_from(badge).unwrap()],
+                    "groups of {size}"
+                );
+            }
+            let priority = SupplyItem::try_from(badge).unwrap().priority();
+            assert_eq!(
+                sum_group_badges(&sacks, size, Leftovers::Error),
+                Ok(5 * priority)
+            );
+        }
+    }
+
+    #[test]
+    fn leftover_rucksacks() {
+        // Two whole groups of four, then three rucksacks sharing `g` as well
+        let mut sacks = with_badge(2, 4, 'x');
+        sacks.extend(with_badge(1, 3, 'g'));
+        assert_eq!(
+            sum_group_badges(&sacks, 4, Leftovers::Error),
+            Err(GroupError::Leftovers { size: 4, left: 3 })
+        );
+        assert_eq!(sum_group_badges(&sacks, 4, Leftovers::Ignore), Ok(2 * 24));
+        assert_eq!(
+            sum_group_badges(&sacks, 4, Leftovers::ShortGroup),
+            Ok(2 * 24 + 7)
+        );
+        let groups = into_groups(&sacks, 4, Leftovers::ShortGroup).unwrap();
+        assert_eq!(groups.last().unwrap().bags.len(), 3);
+        assert_eq!(
+            into_groups(&sacks, 0, Leftovers::Ignore),
+            Err(GroupError::NoRucksacks)
+        );
+        // The puzzle never has leftovers, so they are an error unless asked for
+        assert_eq!(
+            sum_badge_priorities(&sacks[..7]),
+            Err(GroupError::Leftovers { size: 3, left: 1 })
+        );
+        // Part one never groups, so only part two minds them
+        let sacks = Day3::parse("ab\ncd\n").unwrap();
+        assert_eq!(Day3::part_one(&sacks), Ok(0));
+        assert_eq!(
+            Day3::part_two(&sacks),
+            Err(InputError::Group(GroupError::Leftovers {
+                size: 3,
+                left: 2
+            }))
+        );
+    }
+
+    #[test]
+    fn solved_with_any_groups() {
+        // A group of four sharing only `x`
+        let input = "axax\nbxbx\ncxcx\ndxdx\n";
+        let reports = solve(input, &Part::BOTH, 4, Leftovers::Error).unwrap();
+        assert_eq!(reports[0].answer, Answer::Number(1 + 2 + 3 + 4 + 4 * 24));
+        assert_eq!(reports[1].answer, Answer::Number(24));
+        assert_eq!(
+            solve(input, &[Part::Two], 3, Leftovers::Error),
+            Err(InputError::Group(GroupError::Leftovers {
+                size: 3,
+                left: 1
+            }))
+        );
+    }
+
+    #[test]
+    fn compartment_parse_1() {
+        let raw = "p";
+        let cpt = Compartment::try_from(raw).unwrap();
+        assert_eq!(
+            Compartment {
+                items: ItemSet::from_iter([SupplyItem {
+                    itemtype: Itemtype('p'),
+                    priority: Priority(16)
+                }])
+            },
+            cpt
+        );
+    }
+
+    #[test]
+    fn compartment_parse_2() {
+        let raw = "psL";
+        let cpt = Compartment::try_from(raw).unwrap();
+        assert_eq!(
+            Compartment {
+                items: ItemSet::from_iter([
+                    SupplyItem {
+                        itemtype: Itemtype('p'),
+                        priority: Priority(16)
+                    },
+                    SupplyItem {
+                        itemtype: Itemtype('s'),
+                        priority: Priority(19)
+                    },
+                    SupplyItem {
+                        itemtype: Itemtype('L'),
+                        priority: Priority(38)
+                    }
+                ])
+            },
+            cpt
+        );
+    }
+    #[test]
+    fn find_common_1() {
+        let raw = "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL";
+        let rucksack = Rucksack::try_from(raw).unwrap();
+        let common: Vec<SupplyItem> = rucksack.find_common_item().items().collect();
+
+        assert_eq!(
+            common,
+            vec![SupplyItem {
+                itemtype: Itemtype('L'),
+                priority: Priority(38)
+            }]
+        );
+
+        assert_eq!(
+            38,
+            rucksack
+                .find_common_item()
+                .items()
+                .map(|item| *item.priority)
+                .sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn find_common_2() {
+        let raw = "vJrwpWtwJgWrhcsFMMfFFhFp";
+        let rucksack = Rucksack::try_from(raw).unwrap();
+        let common: Vec<SupplyItem> = rucksack.find_common_item().items().collect();
+        assert_eq!(
+            common,
+            vec![SupplyItem {
+                itemtype: Itemtype('p'),
+                priority: Priority(16)
+            }]
+        )
+    }
+
+    #[test]
+    fn find_common_3() {
+        let raw = "PmmdzqPrVvPwwTWBwg";
+        let rucksack = Rucksack::try_from(raw).unwrap();
+        let common: Vec<SupplyItem> = rucksack.find_common_item().items().collect();
+        assert_eq!(
+            common,
+            vec![SupplyItem {
+                itemtype: Itemtype('P'),
+                priority: Priority(42)
+            }]
+        )
+    }
+
+    #[test]
+    fn rucksack_parse_1() {
+        let raw = "PmmdzqPrVvPwwTWBwg";
+        let rucksack = Rucksack::try_from(raw).unwrap();
+
+        let eq = Rucksack {
+            // PmmdzqPrV
+            cpt1: Compartment {
+                items: ItemSet::from_iter([
+                    SupplyItem {
+                        itemtype: Itemtype('P'),
+                        priority: Priority(42),
+                    },
+                    SupplyItem {
+                        itemtype: Itemtype('m'),
+                        priority: Priority(13),
+                    },
+                    SupplyItem {
+                        itemtype: Itemtype('m'),
+                        priority: Priority(13),
+                    },
+                    SupplyItem {
+                        itemtype: Itemtype('d'),
+                        priority: Priority(4),
+                    },
+                    SupplyItem {
+                        itemtype: Itemtype('z'),
+                        priority: Priority(26),
+                    },
+                    SupplyItem {
+                        itemtype: Itemtype('q'),
+                        priority: Priority(17),
+                    },
+                    SupplyItem {
+                        itemtype: Itemtype('P'),
+                        priority: Priority(42),
+                    },
+                    SupplyItem {
+                        itemtype: Itemtype('r'),
+                        priority: Priority(18),
+                    },
+                    SupplyItem {
+                        itemtype: Itemtype('V'),
+                        priority: Priority(48),
+                    },
+                ]),
+            },
+            // vPwwTWBwg
+            cpt2: Compartment {
+                items: ItemSet::from_iter([
+                    SupplyItem {
+                        itemtype: Itemtype('v'),
+                        priority: Priority(22),
+                    },
+                    SupplyItem {
+                        itemtype: Itemtype('P'),
+                        priority: Priority(42),
+                    },
+                    SupplyItem {
+                        itemtype: Itemtype('w'),
+                        priority: Priority(23),
+                    },
+                    SupplyItem {
+                        itemtype: Itemtype('w'),
+                        priority: Priority(23),
+                    },
+                    SupplyItem {
+                        itemtype: Itemtype('T'),
+                        priority: Priority(46),
+                    },
+                    SupplyItem {
+                        itemtype: Itemtype('W'),
+                        priority: Priority(49),
+                    },
+                    SupplyItem {
+                        itemtype: Itemtype('B'),
+                        priority: Priority(28),
+                    },
+                    SupplyItem {
+                        itemtype: Itemtype('w'),
+                        priority: Priority(23),
+                    },
+                    SupplyItem {
+                        itemtype: Itemtype('g'),
+                        priority: Priority(7),
+                    },
+                ]),
+            },
+        };
+        assert_eq!(eq, rucksack);
+    }
+}
@@ -0,0 +1,48 @@
+//! Section assignment pairs with known answers
+
+use crate::Day4;
+use common::generate::{rng, Generate, Generated};
+use rand::Rng;
+
+/// A list of `pairs` pairs of section ranges between 1 and 99
+pub fn generate(seed: u64, pairs: usize) -> Generated {
+    let mut rng = rng(seed);
+    let mut input = String::new();
+    let (mut containing, mut overlapping) = (0, 0);
+    for _ in 0..pairs {
+        let mut range = || {
+            let start: u32 = rng.random_range(1..=99);
+            (start, rng.random_range(start..=99))
+        };
+        let ((a, b), (c, d)) = (range(), range());
+        input.push_str(&format!("{a}-{b},{c}-{d}\n"));
+
+        if (a <= c && d <= b) || (c <= a && b <= d) {
+            containing += 1;
+        }
+        if a <= d && c <= b {
+            overlapping += 1;
+        }
+    }
+
+    Generated::new(input, containing, overlapping)
+}
+
+impl Generate for Day4 {
+    fn generate(seed: u64, size: usize) -> Generated {
+        generate(seed, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solution_agrees() {
+        for seed in 0..20 {
+            let generated = generate(seed, 100);
+            assert_eq!(generated.answers(), generated.solve::<Day4>().unwrap());
+        }
+    }
+}
@@ -0,0 +1,304 @@
+//! Day 4: Camp Cleanup, comparing the section assignments of pairs of elves
+#![warn(missing_docs)]
+
+pub mod generator;
+
+use common::{column_of, parse_lines, Located, Location, Solution};
+use std::ops::Deref;
+use thiserror::Error;
+
+/// Why a pair of section assignments could not be read
+///
+/// Every variant holds the `location` of the problem and the `text` found there.
+#[allow(missing_docs)]
+#[derive(Debug, PartialEq, Error)]
+pub enum ParseError {
+    /// A section that is not a whole number
+    #[error("{location}: `{text}` is not a section id")]
+    InvalidSectionId { location: Location, text: String },
+    /// A range without a `-`
+    #[error("{location}: `{text}` is not a range like `2-4`")]
+    MalformedRange { location: Location, text: String },
+    /// A line without a `,`
+    #[error("{location}: `{text}` is not a pair of ranges like `2-4,6-8`")]
+    MalformedPair { location: Location, text: String },
+}
+
+impl Located for ParseError {
+    fn location_mut(&mut self) -> &mut Location {
+        match self {
+            ParseError::InvalidSectionId { location, .. }
+            | ParseError::MalformedRange { location, .. }
+            | ParseError::MalformedPair { location, .. } => location,
+        }
+    }
+}
+
+/// The number of a section of the camp
+#[derive(Debug, PartialEq, Copy, Clone, PartialOrd)]
+pub struct SectionId(u64);
+
+impl TryFrom<&str> for SectionId {
+    type Error = ParseError;
+
+    fn try_from(from: &str) -> Result<Self, Self::Error> {
+        let Ok(id) = from.parse::<u64>() else {
+            return Err(ParseError::InvalidSectionId {
+                location: Location::column(1),
+                text: from.to_string(),
+            });
+        };
+
+        Ok(Self(id))
+    }
+}
+
+impl Deref for SectionId {
+    type Target = u64;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// The sections from `start` to `end`, both included
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SectionRange {
+    start: SectionId,
+    end: SectionId,
+}
+
+impl SectionRange {
+    /// Whether every section in this range is also in `other`
+    pub fn is_fully_within(&self, other: SectionRange) -> bool {
+        // Is my start gteq other, and is my end lteq other
+        self.start >= other.start && self.end <= other.end
+    }
+
+    /// Whether this range starts or ends inside `other`
+    pub fn is_overlapping_at_all(&self, other: SectionRange) -> bool {
+        // is my start somewhere within others range
+        (self.start >= other.start && self.start <= other.end) | 
+        // OR is my end somewhere within others range
+        (self.end <= other.end && self.end >= other.start)
+    }
+}
+/// An elf and the sections it is assigned to clean
+#[derive(Debug, Clone, PartialEq)]
+pub struct Elf {
+    assigned: SectionRange,
+}
+/// Two elves cleaning together, a line of the input
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElfPair(Elf, Elf);
+
+impl ElfPair {
+    /// Does one of the elves assignment fully contain the others?
+    pub fn is_any_containing_other(&self) -> bool {
+        self.0.assigned.is_fully_within(self.1.assigned)
+            | self.1.assigned.is_fully_within(self.0.assigned)
+    }
+
+    /// Is there any overlap at all in the assignments?
+    pub fn is_overlap_with_other(&self) -> bool {
+        self.0.assigned.is_overlapping_at_all(self.1.assigned)
+            | self.1.assigned.is_overlapping_at_all(self.0.assigned)
+    }
+}
+impl TryFrom<&str> for SectionRange {
+    type Error = ParseError;
+
+    fn try_from(from: &str) -> Result<Self, Self::Error> {
+        let Some((start, end)) = from.split_once('-') else {
+            return Err(ParseError::MalformedRange {
+                location: Location::column(1),
+                text: from.to_string(),
+            });
+        };
+        let section_id =
+            |id: &str| SectionId::try_from(id).map_err(|err| err.shifted(column_of(from, id) - 1));
+
+        Ok(SectionRange {
+            start: section_id(start)?,
+            end: section_id(end)?,
+        })
+    }
+}
+
+impl TryFrom<&str> for ElfPair {
+    type Error = ParseError;
+
+    fn try_from(from: &str) -> Result<Self, Self::Error> {
+        let Some((first, second)) = from.split_once(',') else {
+            return Err(ParseError::MalformedPair {
+                location: Location::column(1),
+                text: from.to_string(),
+            });
+        };
+        let elf = |range: &str| {
+            SectionRange::try_from(range)
+                .map(|assigned| Elf { assigned })
+                .map_err(|err| err.shifted(column_of(from, range) - 1))
+        };
+
+        Ok(ElfPair(elf(first)?, elf(second)?))
+    }
+}
+
+/// Count the pairs where one elf's assignment fully contains the other's
+pub fn count_fully_containing(pairs: &[ElfPair]) -> usize {
+    pairs
+        .iter()
+        .filter(|pair| pair.is_any_containing_other())
+        .count()
+}
+
+/// Count the pairs whose assignments overlap at all
+pub fn count_overlapping(pairs: &[ElfPair]) -> usize {
+    pairs
+        .iter()
+        .filter(|pair| pair.is_overlap_with_other())
+        .count()
+}
+
+/// Camp Cleanup
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Parsed<'a> = Vec<ElfPair>;
+    type PartOne = usize;
+    type PartTwo = usize;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Vec<ElfPair>, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part_one(pairs: &Vec<ElfPair>) -> Result<usize, ParseError> {
+        Ok(count_fully_containing(pairs))
+    }
+
+    fn part_two(pairs: &Vec<ElfPair>) -> Result<usize, ParseError> {
+        Ok(count_overlapping(pairs))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parsing() {
+        let case1 = ElfPair(
+            Elf {
+                assigned: SectionRange {
+                    start: SectionId(2),
+                    end: SectionId(4),
+                },
+            },
+            Elf {
+                assigned: SectionRange {
+                    start: SectionId(6),
+                    end: SectionId(8),
+                },
+            },
+        );
+
+        let case2 = ElfPair::try_from("2-4,6-8").unwrap();
+        assert_eq!(case1, case2);
+    }
+
+    #[test]
+    fn comparing() {
+        let case1 = ElfPair::try_from("2-4,6-8").unwrap();
+        assert!(!case1.is_any_containing_other());
+        assert!(!case1.is_overlap_with_other());
+        let case2 = ElfPair::try_from("2-3,4-5").unwrap();
+        assert!(!case2.is_any_containing_other());
+        assert!(!case2.is_overlap_with_other());
+        let case3 = ElfPair::try_from("5-7,7-9").unwrap();
+        assert!(!case3.is_any_containing_other());
+        assert!(case3.is_overlap_with_other());
+        let case4 = ElfPair::try_from("2-8,3-7").unwrap();
+        assert!(case4.is_any_containing_other());
+        assert!(case4.is_overlap_with_other());
+        let case5 = ElfPair::try_from("6-6,4-6").unwrap();
+        assert!(case5.is_any_containing_other());
+        assert!(case5.is_overlap_with_other());
+        let case6 = ElfPair::try_from("2-6,4-8").unwrap();
+        assert!(!case6.is_any_containing_other());
+        assert!(case6.is_overlap_with_other());
+
+        let sim = [
+            case1.clone(),
+            case2.clone(),
+            case3.clone(),
+            case4.clone(),
+            case5.clone(),
+            case6.clone(),
+        ]
+        .iter()
+        .filter(|pair| pair.is_any_containing_other())
+        .count();
+        assert_eq!(2, sim);
+
+        let sim2 = [case1, case2, case3, case4, case5, case6]
+            .iter()
+            .filter(|pair| pair.is_overlap_with_other())
+            .count();
+        assert_eq!(4, sim2);
+    }
+
+    #[test]
+    fn solution_on_sample() {
+        let pairs = Day4::parse("2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8").unwrap();
+        assert_eq!(Ok(2), Day4::part_one(&pairs));
+        assert_eq!(Ok(4), Day4::part_two(&pairs));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Err(ParseError::InvalidSectionId {
+                location: Location::column(7),
+                text: String::from("x"),
+            }),
+            ElfPair::try_from("2-4,6-x")
+        );
+        assert_eq!(
+            Err(ParseError::MalformedRange {
+                location: Location::column(5),
+                text: String::from("68"),
+            }),
+            ElfPair::try_from("2-4,68")
+        );
+        assert_eq!(
+            Err(ParseError::MalformedPair {
+                location: Location::new(2, 1),
+                text: String::from("2-4"),
+            }),
+            Day4::parse("1-2,3-4\n2-4\n")
+        );
+    }
+
+    #[test]
+    fn misc() {
+        let id1 = SectionId::try_from("42").unwrap();
+        let id2 = SectionId(42);
+
+        assert_eq!(id1, id2);
+
+        let range = SectionRange {
+            start: SectionId(1),
+            end: SectionId(4),
+        };
+
+        let elf1 = Elf { assigned: range };
+        let elf2 = Elf { assigned: range };
+        let pair = ElfPair(elf1, elf2);
+
+        assert_eq!(1u64, *pair.0.assigned.start);
+        assert_eq!(4u64, *pair.0.assigned.end);
+    }
+}
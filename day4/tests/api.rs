@@ -0,0 +1,38 @@
+//! Use the section assignments from outside of the crate
+
+use common::Solution;
+use day4::{Day4, ElfPair, ParseError, SectionRange};
+
+#[test]
+fn section_ranges() {
+    let inner = SectionRange::try_from("3-7").unwrap();
+    let outer = SectionRange::try_from("2-8").unwrap();
+    let apart = SectionRange::try_from("9-10").unwrap();
+
+    assert!(inner.is_fully_within(outer));
+    assert!(!outer.is_fully_within(inner));
+    assert!(inner.is_overlapping_at_all(outer));
+    assert!(!inner.is_overlapping_at_all(apart));
+}
+
+#[test]
+fn elf_pairs() {
+    let pair = ElfPair::try_from("5-7,7-9").unwrap();
+    assert!(pair.is_overlap_with_other());
+    assert!(!pair.is_any_containing_other());
+
+    let pair = ElfPair::try_from("6-6,4-6").unwrap();
+    assert!(pair.is_any_containing_other());
+}
+
+#[test]
+fn parse_errors() {
+    assert!(matches!(
+        SectionRange::try_from("2:4"),
+        Err(ParseError::MalformedRange { .. })
+    ));
+    assert!(matches!(
+        Day4::parse("2-4,6-8\n2-x,4-5\n"),
+        Err(ParseError::InvalidSectionId { .. })
+    ));
+}
@@ -0,0 +1,88 @@
+//! Crate drawings and move lists with known answers
+
+use crate::Day5;
+use common::generate::{rng, Generate, Generated};
+use rand::Rng;
+
+/// Stacks are numbered with a single digit in the drawing
+const STACKS: usize = 9;
+
+/// Nine stacks of crates followed by `moves` moves
+///
+/// Moves never take the last crate off a stack, so every stack has a crate
+/// on top at the end. Stacks start with at least two crates, so there are
+/// always more crates than stacks and some stack can be moved from.
+pub fn generate(seed: u64, moves: usize) -> Generated {
+    let mut rng = rng(seed);
+    let stacks: Vec<Vec<char>> = (0..STACKS)
+        .map(|_| {
+            (0..rng.random_range(2..=8))
+                .map(|_| char::from(rng.random_range(b'A'..=b'Z')))
+                .collect()
+        })
+        .collect();
+
+    let mut input = String::new();
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    for level in (0..height).rev() {
+        let row: Vec<String> = stacks
+            .iter()
+            .map(|stack| match stack.get(level) {
+                Some(letter) => format!("[{letter}]"),
+                None => String::from("   "),
+            })
+            .collect();
+        input.push_str(&row.join(" "));
+        input.push('\n');
+    }
+    let numbers: Vec<String> = (1..=STACKS).map(|n| format!(" {n} ")).collect();
+    input.push_str(&numbers.join(" "));
+    input.push_str("\n\n");
+
+    // Crane 9000 moves one crate at a time, crane 9001 moves them all at once
+    let mut one_by_one = stacks.clone();
+    let mut all_in_one = stacks;
+    for _ in 0..moves {
+        let from = loop {
+            let from = rng.random_range(0..STACKS);
+            if one_by_one[from].len() > 1 {
+                break from;
+            }
+        };
+        let to = (from + rng.random_range(1..STACKS)) % STACKS;
+        let count = rng.random_range(1..one_by_one[from].len());
+        input.push_str(&format!("move {count} from {} to {}\n", from + 1, to + 1));
+
+        for _ in 0..count {
+            let lifted = one_by_one[from].pop().unwrap();
+            one_by_one[to].push(lifted);
+        }
+        let at = all_in_one[from].len() - count;
+        let lifted = all_in_one[from].split_off(at);
+        all_in_one[to].extend(lifted);
+    }
+
+    let tops = |stacks: &[Vec<char>]| -> String {
+        stacks.iter().map(|stack| stack.last().unwrap()).collect()
+    };
+    Generated::new(input, tops(&one_by_one), tops(&all_in_one))
+}
+
+impl Generate for Day5 {
+    fn generate(seed: u64, size: usize) -> Generated {
+        generate(seed, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solution_agrees() {
+        for seed in 0..20 {
+            let generated = generate(seed, 100);
+            assert_eq!(generated.answers(), generated.solve::<Day5>().unwrap());
+        }
+    }
+}
@@ -0,0 +1,423 @@
+//! Day 5: Supply Stacks, rearranging stacks of crates with a crane
+#![warn(missing_docs)]
+
+pub mod generator;
+
+extern crate pest;
+#[macro_use]
+extern crate pest_derive;
+
+use common::{Location, Solution};
+use pest::error::LineColLocation;
+use pest::{iterators::Pair, Parser};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// The pest grammar for the crate drawing and the list of moves
+mod grammar {
+    #![allow(missing_docs)]
+
+    #[derive(Parser)]
+    #[grammar = "inputstack.pest"]
+    pub struct StacksParser;
+}
+pub use grammar::{Rule, StacksParser};
+
+/// A representation of a crate
+#[derive(Debug, Clone)]
+pub struct ElfCrate<'a>(&'a str);
+/// A representation of a stack of crates
+#[derive(Debug, Clone)]
+pub struct CrateStack<'a> {
+    stack: Vec<ElfCrate<'a>>,
+}
+/// A representation of the loading dock
+#[derive(Debug, Clone)]
+pub struct Dock<'a> {
+    stacks: Vec<CrateStack<'a>>,
+}
+
+impl<'a> Dock<'a> {
+    /// Put the crates of every stack bottom first, as they are read top first
+    pub fn sort(&mut self) {
+        for stack in &mut self.stacks {
+            stack.stack.reverse();
+        }
+    }
+
+    /// Take the crates `some_move` moves off their stack, bottom first
+    fn take(&mut self, some_move: &Move) -> Result<Vec<ElfCrate<'a>>, ParseError> {
+        let stack = &mut self.stacks[some_move.from - 1].stack;
+        let left = usize::try_from(some_move.amount)
+            .ok()
+            .and_then(|amount| stack.len().checked_sub(amount))
+            .ok_or_else(|| ParseError::TooManyCrates {
+                location: some_move.location,
+                text: some_move.amount.to_string(),
+            })?;
+        Ok(stack.split_off(left))
+    }
+
+    /// Move the crates of `some_move` one at a time, reversing their order
+    pub fn apply_move_one_by_one(&mut self, some_move: &Move) -> Result<(), ParseError> {
+        let mut all_crates = self.take(some_move)?;
+        all_crates.reverse();
+        self.stacks[some_move.to - 1].stack.append(&mut all_crates);
+        Ok(())
+    }
+
+    /// Move the crates of `some_move` at once, keeping their order
+    pub fn apply_move_all_in_one(&mut self, some_move: &Move) -> Result<(), ParseError> {
+        let mut all_crates = self.take(some_move)?;
+        self.stacks[some_move.to - 1].stack.append(&mut all_crates);
+        Ok(())
+    }
+
+    /// The letters of the crates on top of each stack, with a space for an empty stack
+    pub fn top_crates(&self) -> String {
+        self.stacks
+            .iter()
+            .map(|stack| stack.stack.last().map_or(" ", |crat| crat.0))
+            .collect()
+    }
+
+    /// Print the crates on top of each stack to stdout
+    pub fn print_top_crates(&self) {
+        for stack in &self.stacks {
+            if let Some(crat) = stack.stack.last() {
+                print!("{:?}", crat);
+            } else {
+                print!(" ");
+            }
+        }
+    }
+}
+
+/// Why the crate drawing or the list of moves could not be read
+///
+/// Every variant holds the `location` of the problem and the `text` found there.
+#[allow(missing_docs)]
+#[derive(Debug, PartialEq, Error)]
+pub enum ParseError {
+    /// Input the grammar does not allow
+    #[error("{location}: unexpected input in `{text}`")]
+    Syntax { location: Location, text: String },
+    /// A number that does not fit
+    #[error("{location}: `{text}` is too large")]
+    InvalidNumber { location: Location, text: String },
+    /// A move from or to a stack that is not in the drawing
+    #[error("{location}: there is no stack {text}")]
+    UnknownStack { location: Location, text: String },
+    /// A crate to the right of every stack in the first row
+    #[error("{location}: crate `{text}` is outside of the first row")]
+    UnexpectedCrate { location: Location, text: String },
+    /// A move of more crates than are on the stack when it is made
+    #[error("{location}: there are fewer than {text} crates on the stack to move")]
+    TooManyCrates { location: Location, text: String },
+}
+
+impl From<pest::error::Error<Rule>> for ParseError {
+    fn from(err: pest::error::Error<Rule>) -> Self {
+        let (line, column) = match err.line_col {
+            LineColLocation::Pos(pos) => pos,
+            LineColLocation::Span(start, _) => start,
+        };
+        ParseError::Syntax {
+            location: Location::new(line, column),
+            text: err.line().to_string(),
+        }
+    }
+}
+
+/// The digits of a move field and where they start
+fn digits(field: Pair<'_, Rule>) -> (Location, &str) {
+    let digits = field.into_inner();
+    let (line, column) = digits
+        .clone()
+        .next()
+        .map_or((0, 0), |digit| digit.line_col());
+    (Location::new(line, column), digits.as_str())
+}
+
+/// Parse the digits of a move field
+fn parse_number<T: FromStr>(field: Pair<'_, Rule>) -> Result<T, ParseError> {
+    let (location, text) = digits(field);
+    text.parse().map_err(|_| ParseError::InvalidNumber {
+        location,
+        text: text.to_string(),
+    })
+}
+
+/// Parse the digits of a move field naming one of `stacks` stacks
+fn parse_stack(field: Pair<'_, Rule>, stacks: usize) -> Result<usize, ParseError> {
+    let (location, text) = digits(field.clone());
+    let stack = parse_number(field)?;
+    if !(1..=stacks).contains(&stack) {
+        return Err(ParseError::UnknownStack {
+            location,
+            text: text.to_string(),
+        });
+    }
+    Ok(stack)
+}
+
+#[derive(Debug, PartialEq)]
+enum ParserState {
+    NotStarted,
+    CrateRowsStarted,
+    CrateRows,
+}
+
+/// A move of x crates from and to another stack
+#[derive(Debug)]
+pub struct Move {
+    amount: u32,
+    from: usize,
+    to: usize,
+    /// Where the amount is written, for a move that takes too many crates
+    location: Location,
+}
+
+/// The list of moves, in order
+pub type Moves = Vec<Move>;
+
+/// Parse the crate drawing and the list of moves
+pub fn parse_input(input: &str) -> Result<(Dock<'_>, Moves), ParseError> {
+    let file = StacksParser::parse(Rule::file, input)?.next().unwrap();
+
+    let mut dock = Dock { stacks: Vec::new() };
+    let mut parserstate = ParserState::NotStarted;
+    let mut moves = Moves::new();
+
+    for record in file.into_inner() {
+        match record.as_rule() {
+            Rule::craterow => {
+                if parserstate == ParserState::NotStarted {
+                    parserstate = ParserState::CrateRowsStarted;
+                }
+
+                for (stacknum, field) in record.into_inner().enumerate() {
+                    if parserstate == ParserState::CrateRowsStarted {
+                        dock.stacks.push(CrateStack { stack: Vec::new() });
+                    }
+
+                    let Some(stack) = dock.stacks.get_mut(stacknum) else {
+                        let (line, column) = field.line_col();
+                        return Err(ParseError::UnexpectedCrate {
+                            location: Location::new(line, column),
+                            text: field.as_str().to_string(),
+                        });
+                    };
+
+                    match field.as_rule() {
+                        Rule::emptycrate => (),
+                        Rule::elfcrate => {
+                            stack.stack.push(ElfCrate(field.into_inner().as_str()));
+                        }
+                        _ => (),
+                    }
+                }
+
+                parserstate = ParserState::CrateRows;
+            }
+            Rule::moverow => {
+                let (line, column) = record.line_col();
+                let mut somemove = Move {
+                    amount: 0,
+                    from: 0,
+                    to: 0,
+                    location: Location::new(line, column),
+                };
+
+                for field in record.into_inner() {
+                    match field.as_rule() {
+                        Rule::moveamount => {
+                            somemove.location = digits(field.clone()).0;
+                            somemove.amount = parse_number(field)?;
+                        }
+                        Rule::movefrom => somemove.from = parse_stack(field, dock.stacks.len())?,
+                        Rule::moveto => somemove.to = parse_stack(field, dock.stacks.len())?,
+                        _ => (),
+                    }
+                }
+
+                moves.push(somemove);
+            }
+            Rule::EOI => (),
+            _ => (),
+        }
+    }
+
+    dock.sort();
+    Ok((dock, moves))
+}
+
+/// Supply Stacks
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Parsed<'a> = (Dock<'a>, Moves);
+    type PartOne = String;
+    type PartTwo = String;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<(Dock<'_>, Moves), ParseError> {
+        parse_input(input)
+    }
+
+    /// Top crates when the crane moves one crate at a time
+    fn part_one((dock, moves): &(Dock<'_>, Moves)) -> Result<String, ParseError> {
+        let mut dock = dock.clone();
+        for some_move in moves {
+            dock.apply_move_one_by_one(some_move)?;
+        }
+        Ok(dock.top_crates())
+    }
+
+    /// Top crates when the crane moves several crates at once
+    fn part_two((dock, moves): &(Dock<'_>, Moves)) -> Result<String, ParseError> {
+        let mut dock = dock.clone();
+        for some_move in moves {
+            dock.apply_move_all_in_one(some_move)?;
+        }
+        Ok(dock.top_crates())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn move_crates_all() {
+        let mut dock = Dock {
+            stacks: vec![
+                CrateStack {
+                    stack: vec![ElfCrate("N"), ElfCrate("Z")],
+                },
+                CrateStack {
+                    stack: vec![ElfCrate("D"), ElfCrate("C"), ElfCrate("M")],
+                },
+                CrateStack {
+                    stack: vec![ElfCrate("P")],
+                },
+            ],
+        };
+        dock.sort();
+
+        let move1 = Move {
+            amount: 1,
+            from: 2,
+            to: 1,
+            location: Location::new(1, 6),
+        };
+        dock.apply_move_all_in_one(&move1).unwrap();
+
+        let move2 = Move {
+            amount: 3,
+            from: 1,
+            to: 3,
+            location: Location::new(1, 6),
+        };
+        dock.apply_move_all_in_one(&move2).unwrap();
+
+        let move3 = Move {
+            amount: 2,
+            from: 2,
+            to: 1,
+            location: Location::new(1, 6),
+        };
+        dock.apply_move_all_in_one(&move3).unwrap();
+
+        let move4 = Move {
+            amount: 1,
+            from: 1,
+            to: 2,
+            location: Location::new(1, 6),
+        };
+        dock.apply_move_all_in_one(&move4).unwrap();
+        assert_eq!(dock.top_crates(), "MCD");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let bad_letter = "    [d]\n[Z] [M]\n 1   2 \n\nmove 1 from 2 to 1\n";
+        assert_eq!(
+            Err(ParseError::Syntax {
+                location: Location::new(1, 6),
+                text: String::from("    [d]"),
+            }),
+            parse_input(bad_letter).map(|_| ())
+        );
+
+        let bad_stack = "    [D]\n[Z] [M]\n 1   2 \n\nmove 1 from 2 to 1\nmove 1 from 3 to 1\n";
+        assert_eq!(
+            Err(ParseError::UnknownStack {
+                location: Location::new(6, 13),
+                text: String::from("3"),
+            }),
+            parse_input(bad_stack).map(|_| ())
+        );
+
+        let too_many = "    [D]\n[Z] [M]\n 1   2 \n\nmove 99999999999 from 2 to 1\n";
+        assert_eq!(
+            Err(ParseError::InvalidNumber {
+                location: Location::new(5, 6),
+                text: String::from("99999999999"),
+            }),
+            parse_input(too_many).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn move_crates_one() {
+        let mut dock = Dock {
+            stacks: vec![
+                CrateStack {
+                    stack: vec![ElfCrate("N"), ElfCrate("Z")],
+                },
+                CrateStack {
+                    stack: vec![ElfCrate("D"), ElfCrate("C"), ElfCrate("M")],
+                },
+                CrateStack {
+                    stack: vec![ElfCrate("P")],
+                },
+            ],
+        };
+        dock.sort();
+        let move1 = Move {
+            amount: 1,
+            from: 2,
+            to: 1,
+            location: Location::new(1, 6),
+        };
+        dock.apply_move_one_by_one(&move1).unwrap();
+
+        let move2 = Move {
+            amount: 3,
+            from: 1,
+            to: 3,
+            location: Location::new(1, 6),
+        };
+        dock.apply_move_one_by_one(&move2).unwrap();
+
+        let move3 = Move {
+            amount: 2,
+            from: 2,
+            to: 1,
+            location: Location::new(1, 6),
+        };
+        dock.apply_move_one_by_one(&move3).unwrap();
+
+        let move4 = Move {
+            amount: 1,
+            from: 1,
+            to: 2,
+            location: Location::new(1, 6),
+        };
+        dock.apply_move_one_by_one(&move4).unwrap();
+        assert_eq!(dock.top_crates(), "CMZ");
+    }
+}
@@ -0,0 +1,51 @@
+//! Use the loading dock from outside of the crate
+
+use common::{Location, Solution};
+use day5::{parse_input, Day5, ParseError};
+
+const EXAMPLE: &str = "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+";
+
+#[test]
+fn dock_moves() {
+    let (dock, moves) = parse_input(EXAMPLE).unwrap();
+    assert_eq!(dock.top_crates(), "NDP");
+
+    let mut one_by_one = dock.clone();
+    let mut all_in_one = dock;
+    for some_move in &moves {
+        one_by_one.apply_move_one_by_one(some_move).unwrap();
+        all_in_one.apply_move_all_in_one(some_move).unwrap();
+    }
+    assert_eq!(one_by_one.top_crates(), "CMZ");
+    assert_eq!(all_in_one.top_crates(), "MCD");
+}
+
+#[test]
+fn unknown_stack() {
+    let input = EXAMPLE.replace("from 1 to 3", "from 1 to 4");
+    assert!(matches!(
+        Day5::parse(&input),
+        Err(ParseError::UnknownStack { .. })
+    ));
+}
+
+#[test]
+fn too_many_crates() {
+    let input = EXAMPLE.replace("move 1 from 2 to 1", "move 4 from 2 to 1");
+    let parsed = Day5::parse(&input).unwrap();
+    let error = || ParseError::TooManyCrates {
+        location: Location::new(6, 6),
+        text: String::from("4"),
+    };
+    assert_eq!(Day5::part_one(&parsed), Err(error()));
+    assert_eq!(Day5::part_two(&parsed), Err(error()));
+}
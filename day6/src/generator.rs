@@ -0,0 +1,78 @@
+//! Signal streams with known answers
+
+use crate::Day6;
+use common::generate::{rng, Generate, Generated};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Shortest stream that fits both markers, see `generate`
+const MIN_LEN: usize = 30;
+
+fn random_from(rng: &mut impl Rng, letters: &[u8], len: usize) -> Vec<u8> {
+    (0..len)
+        .map(|_| letters[rng.random_range(0..letters.len())])
+        .collect()
+}
+
+/// A stream of `len` characters, at least 30
+///
+/// The stream is built so the markers land in known places:
+///
+/// - noise of `a`, `b` and `c`, which can't hold a packet marker, ending in
+///   all three, so adding `d` completes the packet marker
+/// - noise of `a` to `m`, which can't hold a message marker, ending in
+///   thirteen `a`s, so the message marker is only completed by adding all of
+///   `n` to `z`
+/// - anything at all
+pub fn generate(seed: u64, len: usize) -> Generated {
+    let mut rng = rng(seed);
+    let spare = len.max(MIN_LEN) - MIN_LEN;
+    let first = rng.random_range(0..=spare);
+    let second = rng.random_range(0..=spare - first);
+    let rest = spare - first - second;
+
+    let mut stream = random_from(&mut rng, b"abc", first);
+    let mut abc = *b"abc";
+    abc.shuffle(&mut rng);
+    stream.extend(abc);
+    stream.push(b'd');
+    let packet = stream.len();
+
+    stream.extend(random_from(&mut rng, b"abcdefghijklm", second));
+    stream.extend([b'a'; 13]);
+    stream.extend(b"nopqrstuvwxyz");
+    let message = stream.len();
+
+    stream.extend(random_from(&mut rng, b"abcdefghijklmnopqrstuvwxyz", rest));
+    let mut input = String::from_utf8(stream).unwrap();
+    input.push('\n');
+
+    Generated::new(input, packet, message)
+}
+
+impl Generate for Day6 {
+    fn generate(seed: u64, size: usize) -> Generated {
+        generate(seed, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Answer;
+
+    #[test]
+    fn solution_agrees() {
+        for seed in 0..20 {
+            let generated = generate(seed, 200);
+            assert_eq!(generated.answers(), generated.solve::<Day6>().unwrap());
+        }
+    }
+
+    #[test]
+    fn shortest_stream() {
+        let generated = generate(1, 0);
+        assert_eq!(MIN_LEN, generated.input.trim_end().len());
+        assert_eq!((Answer::Number(4), Answer::Number(30)), generated.answers());
+    }
+}
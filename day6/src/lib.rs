@@ -0,0 +1,132 @@
+//! Day 6: Tuning Trouble, finding markers in the signal from the device
+#![warn(missing_docs)]
+
+pub mod generator;
+
+use common::Solution;
+use thiserror::Error;
+
+/// Why no marker was found
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum SignalError {
+    /// No window of `len` characters in the signal is all distinct
+    #[error("no {len} characters in a row of the signal are all different")]
+    NoMarker {
+        /// Characters in the marker looked for
+        len: usize,
+    },
+}
+
+/// Find the position right after the first window of `packet_size` distinct characters
+pub fn check_signal(input: &str, packet_size: usize) -> Result<usize, SignalError> {
+    let split = input //slider
+        .split("")
+        .filter(|c| !c.is_empty())
+        .collect::<Vec<&str>>();
+    split
+        .windows(packet_size)
+        .enumerate()
+        .find_map(|(pos, characters)| {
+            let mut hash_set = std::collections::HashSet::new();
+            for c in characters {
+                if !hash_set.insert(c) {
+                    return None;
+                }
+            }
+            Some(pos + packet_size)
+        })
+        .ok_or(SignalError::NoMarker { len: packet_size })
+}
+
+/// Tuning Trouble
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Parsed<'a> = &'a str;
+    type PartOne = usize;
+    type PartTwo = usize;
+    type Error = SignalError;
+
+    fn parse(input: &str) -> Result<&str, SignalError> {
+        Ok(input)
+    }
+
+    /// Position of the first start-of-packet marker
+    fn part_one(signal: &&str) -> Result<usize, SignalError> {
+        check_signal(signal, 4)
+    }
+
+    /// Position of the first start-of-message marker
+    fn part_two(signal: &&str) -> Result<usize, SignalError> {
+        check_signal(signal, 14)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn case1() {
+        assert_eq!(Ok(7), check_signal("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4));
+    }
+    #[test]
+    fn case2() {
+        assert_eq!(Ok(5), check_signal("bvwbjplbgvbhsrlpgdmjqwftvncz", 4));
+    }
+    #[test]
+    fn case3() {
+        assert_eq!(Ok(6), check_signal("nppdvjthqldpwncqszvftbrmjlhg", 4));
+    }
+    #[test]
+    fn case4() {
+        assert_eq!(Ok(10), check_signal("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 4));
+    }
+    #[test]
+    fn case5() {
+        assert_eq!(Ok(11), check_signal("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 4));
+    }
+
+    // Part 2
+    #[test]
+    fn case6() {
+        assert_eq!(Ok(19), check_signal("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14));
+    }
+
+    #[test]
+    fn case7() {
+        assert_eq!(Ok(23), check_signal("bvwbjplbgvbhsrlpgdmjqwftvncz", 14));
+    }
+    #[test]
+    fn case8() {
+        assert_eq!(Ok(23), check_signal("nppdvjthqldpwncqszvftbrmjlhg", 14));
+    }
+    #[test]
+    fn case9() {
+        assert_eq!(
+            Ok(29),
+            check_signal("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 14)
+        );
+    }
+    #[test]
+    fn case10() {
+        assert_eq!(Ok(26), check_signal("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14));
+    }
+
+    #[test]
+    fn no_marker() {
+        assert_eq!(
+            Err(SignalError::NoMarker { len: 4 }),
+            check_signal("abc", 4)
+        );
+        // A start-of-packet marker is no start-of-message marker
+        let signal = Day6::parse("mjqjpqmgb").unwrap();
+        assert_eq!(Ok(7), Day6::part_one(&signal));
+        assert_eq!(
+            Err(SignalError::NoMarker { len: 14 }),
+            Day6::part_two(&signal)
+        );
+    }
+}
@@ -0,0 +1,141 @@
+//! Terminal transcripts with known answers
+
+use crate::Day7;
+use common::generate::{rng, Generate, Generated};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+
+const DISK: usize = 70_000_000;
+const NEEDED: usize = 30_000_000;
+
+/// Never made up by `name`, which keeps names to six letters
+const PADDING: &str = "padding.dat";
+
+#[derive(Default)]
+struct Dir {
+    name: String,
+    children: Vec<usize>,
+    files: Vec<(usize, String)>,
+}
+
+fn name(rng: &mut impl Rng, taken: &mut HashSet<String>) -> String {
+    loop {
+        let name: String = (0..rng.random_range(1..=6))
+            .map(|_| char::from(rng.random_range(b'a'..=b'z')))
+            .collect();
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+/// Listing the entries of `dir`, then moving into and back out of every directory in it
+fn transcript(dirs: &[Dir], dir: usize, rng: &mut impl Rng, out: &mut Vec<String>) {
+    out.push(String::from("$ ls"));
+    let mut entries: Vec<String> = dirs[dir]
+        .children
+        .iter()
+        .map(|&child| format!("dir {}", dirs[child].name))
+        .chain(
+            dirs[dir]
+                .files
+                .iter()
+                .map(|(size, name)| format!("{size} {name}")),
+        )
+        .collect();
+    entries.shuffle(rng);
+    out.extend(entries);
+
+    for &child in &dirs[dir].children {
+        out.push(format!("$ cd {}", dirs[child].name));
+        transcript(dirs, child, rng, out);
+        out.push(String::from("$ cd .."));
+    }
+}
+
+/// A transcript of a file system with `dirs` directories below the root
+///
+/// The disk is between 40000001 and 70000000 full, so there is always a
+/// directory to delete for the update.
+pub fn generate(seed: u64, dirs: usize) -> Generated {
+    let mut rng = rng(seed);
+    let mut tree = vec![Dir::default()];
+    let mut names = vec![HashSet::new()];
+    for idx in 1..=dirs {
+        // Parents always come before their children
+        let parent = rng.random_range(0..idx);
+        let name = name(&mut rng, &mut names[parent]);
+        tree[parent].children.push(idx);
+        tree.push(Dir {
+            name,
+            ..Dir::default()
+        });
+        names.push(HashSet::new());
+    }
+    for (dir, taken) in tree.iter_mut().zip(&mut names) {
+        for _ in 0..rng.random_range(0..=4) {
+            let mut file = name(&mut rng, taken);
+            if rng.random_bool(0.5) {
+                file.push('.');
+                file.push_str(&name(&mut rng, &mut HashSet::new()));
+            }
+            dir.files.push((rng.random_range(1..=300_000), file));
+        }
+    }
+
+    // Shrink the files to fit the disk if needed, then fill it up to the chosen level
+    let used = rng.random_range(DISK - NEEDED + 1..=DISK);
+    let total: usize = tree.iter().flat_map(|dir| &dir.files).map(|f| f.0).sum();
+    if total >= used {
+        for (size, _) in tree.iter_mut().flat_map(|dir| &mut dir.files) {
+            *size = (*size * (used / 2) / total).max(1);
+        }
+    }
+    let total: usize = tree.iter().flat_map(|dir| &dir.files).map(|f| f.0).sum();
+    tree[0].files.push((used - total, String::from(PADDING)));
+
+    let mut sizes: Vec<usize> = tree
+        .iter()
+        .map(|dir| dir.files.iter().map(|f| f.0).sum())
+        .collect();
+    for idx in (0..tree.len()).rev() {
+        let below: usize = tree[idx].children.iter().map(|&child| sizes[child]).sum();
+        sizes[idx] += below;
+    }
+    let small: usize = sizes.iter().filter(|&&size| size <= 100_000).sum();
+    let to_free = used - (DISK - NEEDED);
+    let delete = sizes.iter().copied().filter(|&size| size >= to_free).min();
+
+    let mut lines = vec![String::from("$ cd /")];
+    transcript(&tree, 0, &mut rng, &mut lines);
+    let mut input = lines.join("\n");
+    input.push('\n');
+
+    Generated::new(input, small, delete.expect("the root is big enough"))
+}
+
+impl Generate for Day7 {
+    fn generate(seed: u64, size: usize) -> Generated {
+        generate(seed, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solution_agrees() {
+        for seed in 0..20 {
+            let generated = generate(seed, 40);
+            assert_eq!(generated.answers(), generated.solve::<Day7>().unwrap());
+        }
+    }
+
+    #[test]
+    fn only_a_root() {
+        let generated = generate(5, 0);
+        assert_eq!(generated.answers(), generated.solve::<Day7>().unwrap());
+    }
+}
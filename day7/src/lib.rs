@@ -0,0 +1,381 @@
+//! Day 7: No Space Left On Device, measuring directories from a terminal transcript
+#![warn(missing_docs)]
+
+pub mod generator;
+
+extern crate pest;
+#[macro_use]
+extern crate pest_derive;
+
+use common::{Location, Solution};
+use pest::error::LineColLocation;
+use pest::{iterators::Pair, Parser};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// The pest grammar for a terminal transcript
+mod grammar {
+    #![allow(missing_docs)]
+
+    #[derive(Parser)]
+    #[grammar = "grammar.pest"]
+    pub struct CliParser;
+}
+pub use grammar::{CliParser, Rule};
+
+/// Why a terminal transcript could not be read
+///
+/// Every variant holds the `location` of the problem and the `text` found there.
+#[allow(missing_docs)]
+#[derive(Debug, PartialEq, Error)]
+pub enum ParseError {
+    /// Input the grammar does not allow
+    #[error("{location}: unexpected input in `{text}`")]
+    Syntax { location: Location, text: String },
+    /// A file size that does not fit
+    #[error("{location}: `{text}` is too large for a file size")]
+    InvalidSize { location: Location, text: String },
+    /// A directory that is listed but never entered
+    #[error("{location}: directory `{text}` is listed but its contents never are")]
+    UnvisitedDirectory { location: Location, text: String },
+}
+
+/// Why a part could not be solved from a transcript
+#[derive(Debug, PartialEq, Error)]
+pub enum InputError {
+    /// A transcript that could not be read
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    /// No directory is large enough to free up the space the update needs
+    #[error("no directory is large enough to free up {need_to_delete}")]
+    NothingToDelete {
+        /// How much space has to be freed
+        need_to_delete: usize,
+    },
+}
+
+impl From<pest::error::Error<Rule>> for ParseError {
+    fn from(err: pest::error::Error<Rule>) -> Self {
+        let (line, column) = match err.line_col {
+            LineColLocation::Pos(pos) => pos,
+            LineColLocation::Span(start, _) => start,
+        };
+        ParseError::Syntax {
+            location: Location::new(line, column),
+            text: err.line().to_string(),
+        }
+    }
+}
+
+fn location_of(pair: &Pair<'_, Rule>) -> Location {
+    let (line, column) = pair.line_col();
+    Location::new(line, column)
+}
+
+/// A file and its size
+pub struct File<'a> {
+    name: &'a str,
+    size: usize,
+}
+
+/// Every directory in the filesystem, keyed by its full path
+pub type DirDb<'a> = RefCell<BTreeMap<Vec<String>, Directory<'a>>>;
+
+/// The files and the names of the subdirectories listed in a directory
+#[derive(Default)]
+pub struct Directory<'a> {
+    files: Vec<File<'a>>,
+    dirs: Vec<&'a str>,
+}
+
+/// The size of the files directly in `dir`
+pub fn get_size(dir: &Directory<'_>) -> usize {
+    dir.files.iter().map(|file| file.size).sum()
+}
+
+/// The size of the directory at path `root`, including all its subdirectories
+pub fn get_dirs_size(dir_db: &DirDb, root: &Vec<String>) -> usize {
+    let local_db = dir_db.borrow();
+    let directory = local_db.get(root).unwrap();
+    let mut self_size = get_size(directory);
+    for subdir in &directory.dirs {
+        let mut next = root.clone();
+        next.push(String::from(*subdir));
+        self_size += get_dirs_size(dir_db, &next);
+    }
+
+    self_size
+}
+
+/// Build the filesystem from the parsed `file` rule of a transcript
+pub fn parse_input(input: Pair<Rule>) -> Result<DirDb, ParseError> {
+    let root_dir = Directory::default();
+    let dir_db: DirDb = RefCell::new(BTreeMap::new());
+    // Every listed directory has to be visited as well, or its size is unknown
+    let mut listed: Vec<(Vec<String>, Location)> = Vec::new();
+    let mut current_pos = vec![String::from("/")];
+    dir_db.borrow_mut().insert(current_pos.clone(), root_dir);
+    for row in input.clone().into_inner() {
+        if row.as_rule() == Rule::row {
+            for record in row.into_inner() {
+                match record.as_rule() {
+                    Rule::command => {
+                        match record.as_str() {
+                            "cd /" => {
+                                current_pos = vec![String::from("/")];
+                                continue;
+                            }
+                            "cd .." => {
+                                if current_pos.len() > 1 {
+                                    current_pos.pop();
+                                }
+                                continue;
+                            }
+                            "ls" => {
+                                continue;
+                            }
+                            _ => (),
+                        };
+                        for cmd in record.into_inner() {
+                            match cmd.as_str() {
+                                "cd" => {
+                                    continue;
+                                }
+                                dirname => {
+                                    let mut local_db = dir_db.borrow_mut();
+                                    let newdir = Directory {
+                                        files: Vec::new(),
+                                        dirs: Vec::new(),
+                                    };
+                                    current_pos.push(String::from(dirname));
+                                    local_db.insert(current_pos.clone(), newdir);
+                                }
+                            }
+                        }
+                    }
+                    Rule::dir => {
+                        let mut local_db = dir_db.borrow_mut();
+                        let dir = local_db.get_mut(&current_pos).unwrap();
+                        let mut name = "";
+                        let mut location = location_of(&record);
+                        for pair in record.into_inner() {
+                            name = pair.as_str();
+                            location = location_of(&pair);
+                        }
+                        dir.dirs.push(name);
+                        let mut path = current_pos.clone();
+                        path.push(String::from(name));
+                        listed.push((path, location));
+                    }
+                    Rule::file => {
+                        let mut local_db = dir_db.borrow_mut();
+                        let dir = local_db.get_mut(&current_pos).unwrap();
+                        let mut file = File { name: "", size: 0 };
+                        for pair in record.into_inner() {
+                            match pair.as_rule() {
+                                Rule::filename => {
+                                    file.name = pair.as_str();
+                                }
+                                Rule::size => {
+                                    file.size = pair.as_str().parse().map_err(|_| {
+                                        ParseError::InvalidSize {
+                                            location: location_of(&pair),
+                                            text: pair.as_str().to_string(),
+                                        }
+                                    })?;
+                                }
+                                _ => (),
+                            }
+                        }
+                        dir.files.push(file);
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    if let Some((path, location)) = listed
+        .into_iter()
+        .find(|(path, _)| !dir_db.borrow().contains_key(path))
+    {
+        return Err(ParseError::UnvisitedDirectory {
+            location,
+            text: path.last().cloned().unwrap_or_default(),
+        });
+    }
+
+    Ok(dir_db)
+}
+
+/// Parse a terminal transcript into the directory database
+pub fn parse_str(input: &str) -> Result<DirDb<'_>, ParseError> {
+    let file = CliParser::parse(Rule::all, input)?.next().unwrap();
+
+    parse_input(file)
+}
+
+/// Total size of every directory, keyed by its path
+pub fn get_all_dir_sizes(dir_db: &DirDb) -> Vec<(Vec<String>, usize)> {
+    let db = dir_db.borrow();
+    db.keys()
+        .map(|dirname| (dirname.clone(), get_dirs_size(dir_db, dirname)))
+        .collect()
+}
+
+/// Sum of the sizes of all directories of at most 100k
+pub fn sum_of_small_dirs(dir_db: &DirDb) -> usize {
+    get_all_dir_sizes(dir_db)
+        .iter()
+        .filter(|(_, v)| v <= &100_000usize)
+        .map(|(_, v)| v)
+        .sum()
+}
+
+/// The smallest directory that frees up enough space for the update
+#[derive(Debug, PartialEq)]
+pub struct DeleteCandidate {
+    /// How much space has to be freed
+    pub need_to_delete: usize,
+    /// The full path of the directory
+    pub path: Vec<String>,
+    /// The total size of the directory
+    pub size: usize,
+}
+
+/// Find the smallest directory to delete so the update fits on the disk
+pub fn find_dir_to_delete(dir_db: &DirDb) -> Result<DeleteCandidate, InputError> {
+    let total_diskspace = 70_000_000usize;
+    let need = 30_000_000usize;
+    let current_used = get_dirs_size(dir_db, &vec![String::from("/")]);
+    let current_unused = total_diskspace.saturating_sub(current_used);
+    let need_to_delete = need.saturating_sub(current_unused);
+    let (path, size) = get_all_dir_sizes(dir_db)
+        .into_iter()
+        .filter(|(_, v)| v >= &need_to_delete)
+        .min_by_key(|(_, v)| *v)
+        .ok_or(InputError::NothingToDelete { need_to_delete })?;
+
+    Ok(DeleteCandidate {
+        need_to_delete,
+        path,
+        size,
+    })
+}
+
+/// No Space Left On Device
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Parsed<'a> = DirDb<'a>;
+    type PartOne = usize;
+    type PartTwo = usize;
+    type Error = InputError;
+
+    fn parse(input: &str) -> Result<DirDb<'_>, InputError> {
+        Ok(parse_str(input)?)
+    }
+
+    /// Sum of the sizes of all directories of at most 100k
+    fn part_one(dir_db: &DirDb<'_>) -> Result<usize, InputError> {
+        Ok(sum_of_small_dirs(dir_db))
+    }
+
+    /// Size of the smallest directory that frees up enough space
+    fn part_two(dir_db: &DirDb<'_>) -> Result<usize, InputError> {
+        Ok(find_dir_to_delete(dir_db)?.size)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::*;
+
+    #[test]
+    fn parse_sample_input() {
+        let input = "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k";
+
+        let sample_parsed = CliParser::parse(Rule::all, input)
+            .expect("error parsing")
+            .next()
+            .unwrap();
+
+        let dir_db = parse_input(sample_parsed).unwrap();
+        let mut result_table: Vec<(&str, usize)> = Vec::new();
+        let db = dir_db.borrow();
+        for (dirname, _dir) in db.iter() {
+            result_table.push(("", get_dirs_size(&dir_db, &dirname.clone())));
+        }
+
+        let sum_of_less_than_100k: usize = result_table
+            .iter()
+            .filter(|(_, v)| v < &100_000usize)
+            .map(|(_, v)| v)
+            .sum();
+        assert_eq!(95437, sum_of_less_than_100k);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Err(ParseError::Syntax {
+                location: Location::new(3, 1),
+                text: String::from("12a4 b.txt"),
+            }),
+            parse_str("$ cd /\n$ ls\n12a4 b.txt\n").map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseError::InvalidSize {
+                location: Location::new(3, 1),
+                text: String::from("123456789012345678901234567890"),
+            }),
+            parse_str("$ cd /\n$ ls\n123456789012345678901234567890 b.txt\n").map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseError::UnvisitedDirectory {
+                location: Location::new(3, 5),
+                text: String::from("a"),
+            }),
+            parse_str("$ cd /\n$ ls\ndir a\n14848514 b.txt\n").map(|_| ())
+        );
+    }
+
+    #[test]
+    fn nothing_needs_deleting() {
+        let dir_db = parse_str("$ cd /\n$ ls\n1 a\n").unwrap();
+        assert_eq!(
+            Ok(DeleteCandidate {
+                need_to_delete: 0,
+                path: vec![String::from("/")],
+                size: 1,
+            }),
+            find_dir_to_delete(&dir_db)
+        );
+    }
+}
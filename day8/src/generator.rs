@@ -0,0 +1,79 @@
+//! Tree height maps with known answers
+
+use crate::Day8;
+use common::generate::{rng, Generate, Generated};
+use rand::Rng;
+
+/// A square forest `side` trees wide, at least one
+pub fn generate(seed: u64, side: usize) -> Generated {
+    let mut rng = rng(seed);
+    let side = side.max(1);
+    let trees: Vec<Vec<u8>> = (0..side)
+        .map(|_| (0..side).map(|_| rng.random_range(0..=9)).collect())
+        .collect();
+
+    let mut input = String::new();
+    for row in &trees {
+        input.extend(row.iter().map(|&height| char::from(b'0' + height)));
+        input.push('\n');
+    }
+
+    // Looking from each tree towards each edge, one step at a time
+    let (mut visible, mut best) = (0, 0);
+    for y in 0..side {
+        for x in 0..side {
+            let height = trees[y][x];
+            let (mut seen_from_outside, mut score) = (false, 1);
+            for (dx, dy) in [(0, -1), (0, 1), (-1, 0), (1, 0)] {
+                let (mut cx, mut cy) = (x as isize, y as isize);
+                let mut distance = 0;
+                let mut blocked = false;
+                loop {
+                    cx += dx;
+                    cy += dy;
+                    if cx < 0 || cy < 0 || cx >= side as isize || cy >= side as isize {
+                        break;
+                    }
+                    distance += 1;
+                    if trees[cy as usize][cx as usize] >= height {
+                        blocked = true;
+                        break;
+                    }
+                }
+                seen_from_outside |= !blocked;
+                score *= distance;
+            }
+            if seen_from_outside {
+                visible += 1;
+            }
+            best = best.max(score);
+        }
+    }
+
+    Generated::new(input, visible, best)
+}
+
+impl Generate for Day8 {
+    fn generate(seed: u64, size: usize) -> Generated {
+        generate(seed, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solution_agrees() {
+        for seed in 0..20 {
+            let generated = generate(seed, 30);
+            assert_eq!(generated.answers(), generated.solve::<Day8>().unwrap());
+        }
+    }
+
+    #[test]
+    fn single_tree() {
+        let generated = generate(2, 0);
+        assert_eq!(generated.answers(), generated.solve::<Day8>().unwrap());
+    }
+}
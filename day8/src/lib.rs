@@ -0,0 +1,346 @@
+//! Day 8: Treetop Tree House, finding visible trees and the best view in a forest
+#![warn(missing_docs)]
+
+pub mod generator;
+
+use common::{Located, Location, Solution};
+use grid::{Direction, Grid, GridError, Point};
+use thiserror::Error;
+
+/// Why a map of the forest could not be read
+///
+/// Every variant holds the `location` of the problem and the `text` found there.
+#[allow(missing_docs)]
+#[derive(Debug, PartialEq, Error)]
+pub enum ParseError {
+    /// A character that is not a digit
+    #[error("{location}: `{text}` is not a tree height")]
+    InvalidHeight { location: Location, text: String },
+    /// A row that is wider or narrower than the first
+    #[error("{location}: row `{text}` is not as wide as the first row")]
+    RaggedRow { location: Location, text: String },
+}
+
+impl Located for ParseError {
+    fn location_mut(&mut self) -> &mut Location {
+        match self {
+            ParseError::InvalidHeight { location, .. } | ParseError::RaggedRow { location, .. } => {
+                location
+            }
+        }
+    }
+}
+
+/// A tree and its height from 0 to 9
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct Tree(u8);
+impl TryFrom<&str> for Tree {
+    type Error = ParseError;
+
+    fn try_from(from: &str) -> Result<Self, Self::Error> {
+        match from.as_bytes() {
+            [height @ b'0'..=b'9'] => Ok(Self(height - b'0')),
+            _ => Err(ParseError::InvalidHeight {
+                location: Location::column(1),
+                text: from.to_string(),
+            }),
+        }
+    }
+}
+
+/// The map of the trees planted in a grid
+#[derive(Debug)]
+pub struct Forest {
+    trees: Grid<Tree>,
+}
+impl Forest {
+    /// A forest from its rows of trees, which all have to be as wide as the first
+    pub fn new(forest: Vec<Vec<Tree>>) -> Result<Self, ParseError> {
+        let width = forest.first().map_or(0, Vec::len);
+        if let Some((row, trees)) = forest
+            .iter()
+            .enumerate()
+            .find(|(_, trees)| trees.len() != width)
+        {
+            return Err(ParseError::RaggedRow {
+                location: Location::new(row + 1, 1),
+                text: trees.iter().map(|tree| char::from(b'0' + tree.0)).collect(),
+            });
+        }
+        Ok(Forest {
+            trees: Grid::from_rows(forest).expect("the rows were checked to be as wide"),
+        })
+    }
+
+    /// Whether every tree from `(x, y)` to the edge in `dir` is shorter
+    fn visible_from(&self, x: usize, y: usize, dir: Direction) -> bool {
+        let at = Point::new(x as isize, y as isize);
+        let tree = self.trees[at];
+        self.trees.ray(at, dir).all(|(_, other)| *other < tree)
+    }
+
+    fn visible_from_left_of_pos(&self, x: usize, y: usize) -> bool {
+        self.visible_from(x, y, Direction::Left)
+    }
+
+    fn visible_from_right_of_pos(&self, x: usize, y: usize) -> bool {
+        self.visible_from(x, y, Direction::Right)
+    }
+
+    fn visible_from_top(&self, x: usize, y: usize) -> bool {
+        self.visible_from(x, y, Direction::Up)
+    }
+
+    fn visible_from_bot(&self, x: usize, y: usize) -> bool {
+        self.visible_from(x, y, Direction::Down)
+    }
+
+    /// Whether the tree at `(x, y)` can be seen from outside the forest
+    pub fn check_all_directions(&self, x: usize, y: usize) -> bool {
+        self.visible_from_left_of_pos(x, y)
+            || self.visible_from_right_of_pos(x, y)
+            || self.visible_from_top(x, y)
+            || self.visible_from_bot(x, y)
+    }
+
+    /// Number of trees that can be seen from outside the forest
+    pub fn count_visible_trees(&self) -> usize {
+        self.trees
+            .points()
+            .filter(|at| self.check_all_directions(at.x as usize, at.y as usize))
+            .count()
+    }
+
+    /// Number of trees seen from `(x, y)` in `dir`, up to and including the first that blocks the view
+    ///
+    /// A tree right on the edge sees nothing in that direction.
+    fn viewing_distance(&self, x: usize, y: usize, dir: Direction) -> usize {
+        let at = Point::new(x as isize, y as isize);
+        let tree = self.trees[at];
+        let mut seen = 0;
+        for (_, other) in self.trees.ray(at, dir) {
+            seen += 1;
+            if *other >= tree {
+                break;
+            }
+        }
+        seen
+    }
+
+    fn get_scenic_up(&self, x: usize, y: usize) -> usize {
+        self.viewing_distance(x, y, Direction::Up)
+    }
+
+    fn get_scenic_left(&self, x: usize, y: usize) -> usize {
+        self.viewing_distance(x, y, Direction::Left)
+    }
+
+    fn get_scenic_right(&self, x: usize, y: usize) -> usize {
+        self.viewing_distance(x, y, Direction::Right)
+    }
+
+    fn get_scenic_down(&self, x: usize, y: usize) -> usize {
+        self.viewing_distance(x, y, Direction::Down)
+    }
+
+    /// The viewing distances from `(x, y)` in all four directions multiplied
+    pub fn get_scenic_score(&self, x: usize, y: usize) -> usize {
+        self.get_scenic_up(x, y)
+            * self.get_scenic_down(x, y)
+            * self.get_scenic_left(x, y)
+            * self.get_scenic_right(x, y)
+    }
+
+    /// The highest scenic score of any tree in the forest
+    pub fn get_highest_scenic_score(&self) -> usize {
+        self.trees
+            .points()
+            .map(|at| self.get_scenic_score(at.x as usize, at.y as usize))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl TryFrom<&str> for Forest {
+    type Error = ParseError;
+
+    fn try_from(from: &str) -> Result<Self, Self::Error> {
+        let trees = Grid::parse(from, |c| Tree::try_from(c.encode_utf8(&mut [0; 4]) as &str))
+            .map_err(|err| match err {
+                GridError::Cell { at, error } => {
+                    let line = from.lines().nth(at.y as usize).unwrap_or_default();
+                    let column = line.chars().take(at.x as usize).map(char::len_utf8).sum();
+                    error.on_line(at.y as usize + 1).shifted(column)
+                }
+                GridError::Ragged { row, .. } => ParseError::RaggedRow {
+                    location: Location::new(row + 1, 1),
+                    text: from.lines().nth(row).unwrap_or_default().to_string(),
+                },
+            })?;
+
+        Ok(Forest { trees })
+    }
+}
+
+/// Treetop Tree House
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Parsed<'a> = Forest;
+    type PartOne = usize;
+    type PartTwo = usize;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Forest, ParseError> {
+        Forest::try_from(input)
+    }
+
+    fn part_one(forest: &Forest) -> Result<usize, ParseError> {
+        Ok(forest.count_visible_trees())
+    }
+
+    fn part_two(forest: &Forest) -> Result<usize, ParseError> {
+        Ok(forest.get_highest_scenic_score())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            ParseError::InvalidHeight {
+                location: Location::new(2, 3),
+                text: String::from("x"),
+            },
+            Forest::try_from("30373\n25x12\n").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::RaggedRow {
+                location: Location::new(3, 1),
+                text: String::from("6533"),
+            },
+            Forest::try_from("30373\n25512\n6533\n").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::RaggedRow {
+                location: Location::new(2, 1),
+                text: String::from("25"),
+            },
+            Forest::new(vec![
+                vec![Tree(3), Tree(0), Tree(3)],
+                vec![Tree(2), Tree(5)]
+            ])
+            .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn scenic() {
+        let forest = Forest::new(vec![
+            vec![Tree(3), Tree(0), Tree(3), Tree(7), Tree(3)],
+            vec![Tree(2), Tree(5), Tree(5), Tree(1), Tree(2)],
+            vec![Tree(6), Tree(5), Tree(3), Tree(3), Tree(2)],
+            vec![Tree(3), Tree(3), Tree(5), Tree(4), Tree(9)],
+            vec![Tree(3), Tree(5), Tree(3), Tree(9), Tree(0)],
+        ])
+        .unwrap();
+
+        assert_eq!(1, forest.get_scenic_up(2, 1));
+        assert_eq!(1, forest.get_scenic_left(2, 1));
+        assert_eq!(2, forest.get_scenic_right(2, 1));
+        assert_eq!(2, forest.get_scenic_down(2, 1));
+
+        assert_eq!(2, forest.get_scenic_up(2, 3));
+        assert_eq!(2, forest.get_scenic_left(2, 3));
+        assert_eq!(1, forest.get_scenic_down(2, 3));
+        assert_eq!(2, forest.get_scenic_right(2, 3));
+    }
+    #[test]
+    fn look_up_on_forest() {
+        let forest = Forest::new(vec![
+            vec![Tree(3), Tree(0), Tree(3), Tree(7), Tree(3)],
+            vec![Tree(2), Tree(5), Tree(5), Tree(1), Tree(2)],
+            vec![Tree(6), Tree(5), Tree(3), Tree(3), Tree(2)],
+            vec![Tree(3), Tree(3), Tree(5), Tree(4), Tree(9)],
+            vec![Tree(3), Tree(5), Tree(3), Tree(9), Tree(0)],
+        ])
+        .unwrap();
+
+        assert!(forest.visible_from_bot(0, 4));
+        assert!(forest.visible_from_bot(0, 2));
+        assert!(!forest.visible_from_bot(1, 1));
+        assert!(!forest.visible_from_bot(1, 3));
+        assert!(!forest.visible_from_bot(4, 2));
+        assert!(forest.visible_from_bot(4, 3));
+
+        assert_eq!(21, forest.count_visible_trees());
+    }
+
+    #[test]
+    fn look_down_on_forest() {
+        let forest = Forest::new(vec![
+            vec![Tree(3), Tree(0), Tree(3), Tree(7), Tree(3)],
+            vec![Tree(2), Tree(5), Tree(5), Tree(1), Tree(2)],
+            vec![Tree(6), Tree(5), Tree(3), Tree(3), Tree(2)],
+            vec![Tree(3), Tree(3), Tree(5), Tree(4), Tree(9)],
+            vec![Tree(3), Tree(5), Tree(3), Tree(9), Tree(0)],
+        ])
+        .unwrap();
+
+        // first row, should be visible
+        assert!(forest.visible_from_top(0, 0));
+        assert!(!forest.visible_from_top(0, 1)); // 3 > 2
+        assert!(!forest.visible_from_top(1, 2)); // 5 is ahead of this one
+        assert!(forest.visible_from_top(3, 4)); // 9 is highest
+        assert!(!forest.visible_from_top(4, 4)); // 0
+    }
+
+    #[test]
+    fn look_left_on_forest() {
+        let forest = Forest::new(vec![
+            vec![Tree(3), Tree(0), Tree(3), Tree(7), Tree(3)],
+            vec![Tree(2), Tree(5), Tree(5), Tree(1), Tree(2)],
+            vec![Tree(6), Tree(5), Tree(3), Tree(3), Tree(2)],
+            vec![Tree(3), Tree(3), Tree(5), Tree(4), Tree(9)],
+            vec![Tree(3), Tree(5), Tree(3), Tree(9), Tree(0)],
+        ])
+        .unwrap();
+
+        // First tree is always visible
+        assert!(forest.visible_from_left_of_pos(0, 0));
+        // 0 is less than 3
+        assert!(!forest.visible_from_left_of_pos(1, 0));
+
+        // 3,3 is less than 5
+        assert!(forest.visible_from_left_of_pos(2, 3));
+        assert!(!forest.visible_from_left_of_pos(2, 4));
+    }
+
+    #[test]
+    fn look_right_on_forest() {
+        let forest = Forest::new(vec![
+            vec![Tree(3), Tree(0), Tree(3), Tree(7), Tree(3)],
+            vec![Tree(2), Tree(5), Tree(5), Tree(1), Tree(2)],
+            vec![Tree(6), Tree(5), Tree(3), Tree(3), Tree(2)],
+            vec![Tree(3), Tree(3), Tree(5), Tree(4), Tree(9)],
+            vec![Tree(3), Tree(5), Tree(3), Tree(9), Tree(0)],
+        ])
+        .unwrap();
+
+        // 7 > 3
+        assert!(forest.visible_from_right_of_pos(3, 0));
+        // 2 < 5
+        assert!(!forest.visible_from_right_of_pos(0, 1));
+        // outer, should be visible
+        assert!(forest.visible_from_right_of_pos(4, 2));
+        // 5 < 9
+        assert!(!forest.visible_from_right_of_pos(2, 3));
+        // 9 > 0
+        assert!(forest.visible_from_right_of_pos(3, 4));
+    }
+}
@@ -0,0 +1,46 @@
+//! Use the forest from outside of the crate
+
+use day8::{Forest, ParseError, Tree};
+
+const EXAMPLE: &str = "30373
+25512
+65332
+33549
+35390
+";
+
+#[test]
+fn forest_queries() {
+    let forest = Forest::try_from(EXAMPLE).unwrap();
+    assert_eq!(forest.count_visible_trees(), 21);
+    assert!(forest.check_all_directions(1, 1));
+    assert!(!forest.check_all_directions(3, 1));
+    assert_eq!(forest.get_scenic_score(2, 3), 8);
+    assert_eq!(forest.get_highest_scenic_score(), 8);
+}
+
+#[test]
+fn forest_from_trees() {
+    let row = |heights: &str| {
+        heights
+            .split("")
+            .filter(|height| !height.is_empty())
+            .map(|height| Tree::try_from(height).unwrap())
+            .collect()
+    };
+    let forest = Forest::new(vec![row("111"), row("121"), row("111")]).unwrap();
+    assert_eq!(forest.count_visible_trees(), 9);
+    assert_eq!(forest.get_highest_scenic_score(), 1);
+}
+
+#[test]
+fn parse_errors() {
+    assert!(matches!(
+        Forest::try_from("123\n1x3\n"),
+        Err(ParseError::InvalidHeight { .. })
+    ));
+    assert!(matches!(
+        Forest::try_from("123\n12\n"),
+        Err(ParseError::RaggedRow { .. })
+    ));
+}
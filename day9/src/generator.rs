@@ -0,0 +1,63 @@
+//! Rope moves with known answers
+
+use crate::Day9;
+use common::generate::{rng, Generate, Generated};
+use rand::Rng;
+use std::collections::HashSet;
+
+/// A list of `moves` moves of one to nine steps, at least one
+pub fn generate(seed: u64, moves: usize) -> Generated {
+    let mut rng = rng(seed);
+    let mut input = String::new();
+
+    // The knot right behind the head moves like the tail of a two knot rope
+    let mut knots = [(0i64, 0i64); 10];
+    let mut behind_head = HashSet::from([(0, 0)]);
+    let mut last = HashSet::from([(0, 0)]);
+    for _ in 0..moves.max(1) {
+        let (letter, (dx, dy)) = match rng.random_range(0..4) {
+            0 => ('U', (0, 1)),
+            1 => ('D', (0, -1)),
+            2 => ('L', (-1, 0)),
+            _ => ('R', (1, 0)),
+        };
+        let steps = rng.random_range(1..=9);
+        input.push_str(&format!("{letter} {steps}\n"));
+
+        for _ in 0..steps {
+            knots[0].0 += dx;
+            knots[0].1 += dy;
+            for idx in 1..knots.len() {
+                let (ahead, knot) = (knots[idx - 1], &mut knots[idx]);
+                let (gap_x, gap_y) = (ahead.0 - knot.0, ahead.1 - knot.1);
+                if gap_x.abs() > 1 || gap_y.abs() > 1 {
+                    knot.0 += gap_x.signum();
+                    knot.1 += gap_y.signum();
+                }
+            }
+            behind_head.insert(knots[1]);
+            last.insert(knots[9]);
+        }
+    }
+
+    Generated::new(input, behind_head.len(), last.len())
+}
+
+impl Generate for Day9 {
+    fn generate(seed: u64, size: usize) -> Generated {
+        generate(seed, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solution_agrees() {
+        for seed in 0..20 {
+            let generated = generate(seed, 200);
+            assert_eq!(generated.answers(), generated.solve::<Day9>().unwrap());
+        }
+    }
+}
@@ -0,0 +1,421 @@
+//! Day 9: Rope Bridge, following the knots of a rope as its head moves
+#![warn(missing_docs)]
+
+pub mod generator;
+
+use common::{column_of, parse_lines, Located, Location, Solution};
+use grid::Point;
+use std::collections::HashSet;
+use thiserror::Error;
+
+/// Why a move of the rope could not be read
+///
+/// Every variant holds the `location` of the problem and the `text` found there.
+#[allow(missing_docs)]
+#[derive(Debug, PartialEq, Error)]
+pub enum ParseError {
+    /// A direction other than `U`, `D`, `L` or `R`
+    #[error("{location}: `{text}` is not a direction")]
+    InvalidDirection { location: Location, text: String },
+    /// A distance that is not a whole number
+    #[error("{location}: `{text}` is not a distance")]
+    InvalidDistance { location: Location, text: String },
+    /// A line that is not a direction and a distance
+    #[error("{location}: `{text}` is not a move like `R 4`")]
+    MalformedMove { location: Location, text: String },
+}
+
+impl Located for ParseError {
+    fn location_mut(&mut self) -> &mut Location {
+        match self {
+            ParseError::InvalidDirection { location, .. }
+            | ParseError::InvalidDistance { location, .. }
+            | ParseError::MalformedMove { location, .. } => location,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Head(Point);
+impl Head {
+    fn step(&mut self, dir: Direction) {
+        self.0 += dir.offset();
+    }
+}
+#[derive(Debug)]
+struct Tail(Point, HashSet<Point>);
+impl Tail {
+    /// Follow the knot ahead, which is `vector` away, once it is no longer touching
+    fn step(&mut self, vector: Point) {
+        if vector.chebyshev(Point::ORIGIN) > 1 {
+            self.0 += vector.signum();
+        }
+
+        self.1.insert(self.0);
+    }
+}
+
+/// A rope of knots, the head pulling the tail along
+#[derive(Debug)]
+pub struct Rope {
+    head: Head,
+    tail: Vec<Tail>,
+}
+impl Rope {
+    /// Move the head step by step, the tail following after each step
+    pub fn apply_move(&mut self, mv: Move) {
+        for _step in 0..mv.dist {
+            self.head.step(mv.dir);
+            let vector = self.head.0 - self.tail[0].0;
+            self.tail[0].step(vector);
+            for idx in 1..self.tail.len() {
+                let vector = self.tail[idx - 1].0 - self.tail[idx].0;
+                self.tail[idx].step(vector);
+            }
+        }
+    }
+
+    /// A rope with `len` knots trailing the head, all starting at the origin
+    pub fn with_tail_len(len: usize) -> Self {
+        Rope {
+            head: Head(Point::ORIGIN),
+            tail: (0..len)
+                .map(|_| Tail(Point::ORIGIN, HashSet::new()))
+                .collect(),
+        }
+    }
+
+    /// Number of positions the last knot of the rope has visited
+    pub fn tail_visited(&self) -> usize {
+        self.tail.last().map_or(0, |tail| tail.1.len())
+    }
+}
+impl Default for Rope {
+    fn default() -> Self {
+        Rope {
+            head: Head(Point::ORIGIN),
+            tail: vec![Tail(Point::ORIGIN, HashSet::new())],
+        }
+    }
+}
+/// A direction the head is moved in
+///
+/// Unlike on a `Grid`, `Up` is towards a greater `y` here.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Direction {
+    /// `U`
+    Up,
+    /// `D`
+    Down,
+    /// `L`
+    Left,
+    /// `R`
+    Right,
+}
+
+impl Direction {
+    /// How far a single step moves the head
+    fn offset(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, 1),
+            Direction::Down => Point::new(0, -1),
+            Direction::Left => Point::new(-1, 0),
+            Direction::Right => Point::new(1, 0),
+        }
+    }
+}
+
+/// A move of the head, a line like `R 4`
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Move {
+    dir: Direction,
+    dist: usize,
+}
+impl TryFrom<&str> for Direction {
+    type Error = ParseError;
+
+    fn try_from(from: &str) -> Result<Self, Self::Error> {
+        match from {
+            "U" => Ok(Direction::Up),
+            "D" => Ok(Direction::Down),
+            "L" => Ok(Direction::Left),
+            "R" => Ok(Direction::Right),
+            _ => Err(ParseError::InvalidDirection {
+                location: Location::column(1),
+                text: from.to_string(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<&str> for Move {
+    type Error = ParseError;
+
+    fn try_from(from: &str) -> Result<Self, Self::Error> {
+        let mut split = from.split_whitespace();
+        let (Some(dir), Some(dist), None) = (split.next(), split.next(), split.next()) else {
+            return Err(ParseError::MalformedMove {
+                location: Location::column(1),
+                text: from.to_string(),
+            });
+        };
+        Ok(Move {
+            dir: Direction::try_from(dir).map_err(|err| err.shifted(column_of(from, dir) - 1))?,
+            dist: dist.parse().map_err(|_| ParseError::InvalidDistance {
+                location: Location::column(column_of(from, dist)),
+                text: dist.to_string(),
+            })?,
+        })
+    }
+}
+
+/// Rope Bridge
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Parsed<'a> = Vec<Move>;
+    type PartOne = usize;
+    type PartTwo = usize;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Vec<Move>, ParseError> {
+        parse_lines(input)
+    }
+
+    /// Positions visited by the tail of a rope with two knots
+    fn part_one(moves: &Vec<Move>) -> Result<usize, ParseError> {
+        let mut rope = Rope::default();
+        for mv in moves {
+            rope.apply_move(*mv);
+        }
+        Ok(rope.tail_visited())
+    }
+
+    /// Positions visited by the tail of a rope with ten knots
+    fn part_two(moves: &Vec<Move>) -> Result<usize, ParseError> {
+        let mut rope = Rope::with_tail_len(9);
+        for mv in moves {
+            rope.apply_move(*mv);
+        }
+        Ok(rope.tail_visited())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Err(ParseError::InvalidDirection {
+                location: Location::new(2, 1),
+                text: String::from("X"),
+            }),
+            Day9::parse("R 4\nX 4\n")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidDistance {
+                location: Location::new(1, 3),
+                text: String::from("-4"),
+            }),
+            Day9::parse("L -4\n")
+        );
+        assert_eq!(
+            Err(ParseError::MalformedMove {
+                location: Location::new(3, 1),
+                text: String::from("U"),
+            }),
+            Day9::parse("R 4\nD 1\nU\n")
+        );
+    }
+
+    #[test]
+    fn long_tail() {
+        let mut rope = Rope {
+            head: Head(Point::ORIGIN),
+            tail: vec![
+                Tail(Point::ORIGIN, HashSet::new()),
+                Tail(Point::ORIGIN, HashSet::new()),
+                Tail(Point::ORIGIN, HashSet::new()),
+                Tail(Point::ORIGIN, HashSet::new()),
+                Tail(Point::ORIGIN, HashSet::new()),
+                Tail(Point::ORIGIN, HashSet::new()),
+                Tail(Point::ORIGIN, HashSet::new()),
+                Tail(Point::ORIGIN, HashSet::new()),
+                Tail(Point::ORIGIN, HashSet::new()),
+            ],
+        };
+
+        let mv = Move {
+            dir: Direction::Right,
+            dist: 4,
+        };
+        rope.apply_move(mv);
+        assert_eq!(0, rope.tail[8].0.x);
+    }
+
+    #[test]
+    fn apply_moves() {
+        let mut rope = Rope {
+            head: Head(Point::ORIGIN),
+            tail: vec![Tail(Point::ORIGIN, HashSet::new())],
+        };
+
+        let mv = Move {
+            dir: Direction::Right,
+            dist: 4,
+        };
+
+        rope.apply_move(mv);
+        assert_eq!(4, rope.head.0.x);
+        assert_eq!(3, rope.tail[0].0.x);
+        assert_eq!(0, rope.head.0.y);
+        assert_eq!(0, rope.tail[0].0.y);
+
+        // up 4
+        let mv = Move {
+            dir: Direction::Up,
+            dist: 1,
+        };
+
+        rope.apply_move(mv);
+
+        assert_eq!(4, rope.head.0.x);
+        assert_eq!(3, rope.tail[0].0.x);
+        assert_eq!(1, rope.head.0.y);
+        assert_eq!(0, rope.tail[0].0.y);
+
+        rope.apply_move(mv);
+
+        assert_eq!(4, rope.head.0.x);
+        assert_eq!(4, rope.tail[0].0.x);
+        assert_eq!(2, rope.head.0.y);
+        assert_eq!(1, rope.tail[0].0.y);
+
+        rope.apply_move(mv);
+
+        assert_eq!(4, rope.head.0.x);
+        assert_eq!(4, rope.tail[0].0.x);
+        assert_eq!(3, rope.head.0.y);
+        assert_eq!(2, rope.tail[0].0.y);
+
+        rope.apply_move(mv);
+
+        assert_eq!(4, rope.head.0.x);
+        assert_eq!(4, rope.tail[0].0.x);
+        assert_eq!(4, rope.head.0.y);
+        assert_eq!(3, rope.tail[0].0.y);
+
+        // Left 3
+        let mv = Move {
+            dir: Direction::Left,
+            dist: 1,
+        };
+
+        rope.apply_move(mv);
+
+        assert_eq!(3, rope.head.0.x);
+        assert_eq!(4, rope.tail[0].0.x);
+        assert_eq!(4, rope.head.0.y);
+        assert_eq!(3, rope.tail[0].0.y);
+
+        rope.apply_move(mv);
+
+        assert_eq!(2, rope.head.0.x);
+        assert_eq!(3, rope.tail[0].0.x);
+        assert_eq!(4, rope.head.0.y);
+        assert_eq!(4, rope.tail[0].0.y);
+
+        rope.apply_move(mv);
+
+        assert_eq!(1, rope.head.0.x);
+        assert_eq!(2, rope.tail[0].0.x);
+        assert_eq!(4, rope.head.0.y);
+        assert_eq!(4, rope.tail[0].0.y);
+
+        // Down 1
+        let mv = Move {
+            dir: Direction::Down,
+            dist: 1,
+        };
+        rope.apply_move(mv);
+
+        assert_eq!(1, rope.head.0.x);
+        assert_eq!(2, rope.tail[0].0.x);
+        assert_eq!(3, rope.head.0.y);
+        assert_eq!(4, rope.tail[0].0.y);
+
+        // Right 4
+        let mv = Move {
+            dir: Direction::Right,
+            dist: 1,
+        };
+
+        rope.apply_move(mv);
+
+        assert_eq!(2, rope.head.0.x);
+        assert_eq!(2, rope.tail[0].0.x);
+        assert_eq!(3, rope.head.0.y);
+        assert_eq!(4, rope.tail[0].0.y);
+
+        rope.apply_move(mv);
+
+        assert_eq!(3, rope.head.0.x);
+        assert_eq!(2, rope.tail[0].0.x);
+        assert_eq!(3, rope.head.0.y);
+        assert_eq!(4, rope.tail[0].0.y);
+
+        rope.apply_move(mv);
+
+        assert_eq!(4, rope.head.0.x);
+        assert_eq!(3, rope.tail[0].0.x);
+        assert_eq!(3, rope.head.0.y);
+        assert_eq!(3, rope.tail[0].0.y);
+
+        rope.apply_move(mv);
+
+        assert_eq!(5, rope.head.0.x);
+        assert_eq!(4, rope.tail[0].0.x);
+        assert_eq!(3, rope.head.0.y);
+        assert_eq!(3, rope.tail[0].0.y);
+
+        let mv = Move {
+            dir: Direction::Down,
+            dist: 1,
+        };
+        rope.apply_move(mv);
+
+        assert_eq!(5, rope.head.0.x);
+        assert_eq!(4, rope.tail[0].0.x);
+        assert_eq!(2, rope.head.0.y);
+        assert_eq!(3, rope.tail[0].0.y);
+
+        let mv = Move {
+            dir: Direction::Left,
+            dist: 5,
+        };
+        rope.apply_move(mv);
+
+        assert_eq!(0, rope.head.0.x);
+        assert_eq!(1, rope.tail[0].0.x);
+        assert_eq!(2, rope.head.0.y);
+        assert_eq!(2, rope.tail[0].0.y);
+
+        let mv = Move {
+            dir: Direction::Right,
+            dist: 2,
+        };
+        rope.apply_move(mv);
+
+        assert_eq!(2, rope.head.0.x);
+        assert_eq!(1, rope.tail[0].0.x);
+        assert_eq!(2, rope.head.0.y);
+        assert_eq!(2, rope.tail[0].0.y);
+
+        let positions_visited = rope.tail[0].1.len();
+        assert_eq!(13, positions_visited);
+    }
+}
@@ -0,0 +1,351 @@
+//! A rectangular grid of cells addressed by `Point`s
+
+mod point;
+
+pub use point::{Direction, Point};
+
+use std::convert::Infallible;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use thiserror::Error;
+
+/// Why text or rows could not be turned into a grid
+///
+/// Rows and columns are counted from 0, like the coordinates of a `Point`.
+#[derive(Debug, PartialEq, Error)]
+pub enum GridError<E = Infallible> {
+    #[error("cell {at} could not be read: {error}")]
+    Cell { at: Point, error: E },
+    #[error("row {row} is {width} wide, but the first row is {expected} wide")]
+    Ragged {
+        row: usize,
+        width: usize,
+        expected: usize,
+    },
+}
+
+/// Cells stored row by row, with the first row at `y == 0`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A `width` by `height` grid with every cell set to `fill`
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// A grid from its rows, which all have to be as wide as the first
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, GridError> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (row, cells_in_row) in rows.into_iter().enumerate() {
+            if cells_in_row.len() != width {
+                return Err(GridError::Ragged {
+                    row,
+                    width: cells_in_row.len(),
+                    expected: width,
+                });
+            }
+            cells.extend(cells_in_row);
+        }
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// A grid with a row per line of `text` and a cell per character, read with `cell`
+    pub fn parse<E>(
+        text: &str,
+        mut cell: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, GridError<E>> {
+        let mut rows = Vec::new();
+        for (y, line) in text.lines().enumerate() {
+            let row = line
+                .chars()
+                .enumerate()
+                .map(|(x, c)| {
+                    cell(c).map_err(|error| GridError::Cell {
+                        at: Point::new(x as isize, y as isize),
+                        error,
+                    })
+                })
+                .collect::<Result<Vec<T>, _>>()?;
+            rows.push(row);
+        }
+        Grid::from_rows(rows).map_err(|err| match err {
+            GridError::Ragged {
+                row,
+                width,
+                expected,
+            } => GridError::Ragged {
+                row,
+                width,
+                expected,
+            },
+            GridError::Cell { error, .. } => match error {},
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, at: Point) -> bool {
+        self.offset(at).is_some()
+    }
+
+    fn offset(&self, at: Point) -> Option<usize> {
+        let x = usize::try_from(at.x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(at.y).ok().filter(|&y| y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    /// The cell at `at`, or `None` when it is off the grid
+    pub fn get(&self, at: Point) -> Option<&T> {
+        self.offset(at).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, at: Point) -> Option<&mut T> {
+        self.offset(at).map(|offset| &mut self.cells[offset])
+    }
+
+    /// Row `y`, panics when there is no such row
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} is off the grid");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a width of 0, which is also a grid without rows worth showing
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Column `x` from the top down, panics when there is no such column
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is off the grid");
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    /// Every point on the grid, row by row
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height)
+            .flat_map(move |y| (0..width).map(move |x| Point::new(x as isize, y as isize)))
+    }
+
+    /// Every cell with its point, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    /// The neighbours up, down, left and right of `at` that are on the grid
+    pub fn neighbours4(&self, at: Point) -> impl Iterator<Item = Point> + '_ {
+        at.neighbours4().into_iter().filter(|&p| self.contains(p))
+    }
+
+    /// The neighbours around `at`, diagonals included, that are on the grid
+    pub fn neighbours8(&self, at: Point) -> impl Iterator<Item = Point> + '_ {
+        at.neighbours8().into_iter().filter(|&p| self.contains(p))
+    }
+
+    /// The cells seen when looking from `from` in `dir`, nearest first and up to the edge
+    ///
+    /// The cell at `from` itself is not part of the ray.
+    pub fn ray(&self, from: Point, dir: Direction) -> Ray<'_, T> {
+        Ray {
+            grid: self,
+            at: from,
+            dir,
+        }
+    }
+
+    /// Text with a line per row, each cell written with `cell`
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows().take(self.height) {
+            out.extend(row.iter().map(&mut cell));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, at: Point) -> &T {
+        self.get(at)
+            .unwrap_or_else(|| panic!("{at} is off the {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, at: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(at)
+            .unwrap_or_else(|| panic!("{at} is off the {width}x{height} grid"))
+    }
+}
+
+/// Rows on their own lines with the cells written next to each other
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().take(self.height).enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Iterator over the cells in a direction, see `Grid::ray`
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    at: Point,
+    dir: Direction,
+}
+
+impl<'a, T> Iterator for Ray<'a, T> {
+    type Item = (Point, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.at.step(self.dir);
+        let cell = self.grid.get(next)?;
+        self.at = next;
+        Some((next, cell))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits() -> Grid<u32> {
+        Grid::parse("123\n456\n789\n", |c| c.to_digit(10).ok_or(c)).unwrap()
+    }
+
+    #[test]
+    fn parse_and_render() {
+        let grid = digits();
+
+        assert_eq!(3, grid.width());
+        assert_eq!(3, grid.height());
+        assert_eq!(6, grid[Point::new(2, 1)]);
+        assert_eq!(
+            "123\n456\n789\n",
+            grid.render(|d| char::from_digit(*d, 10).unwrap())
+        );
+        assert_eq!("123\n456\n789", grid.to_string());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Err(GridError::Cell {
+                at: Point::new(1, 1),
+                error: 'x',
+            }),
+            Grid::parse("123\n4x6\n", |c| c.to_digit(10).ok_or(c))
+        );
+        assert_eq!(
+            Err(GridError::Ragged {
+                row: 2,
+                width: 2,
+                expected: 3,
+            }),
+            Grid::parse("123\n456\n78\n", |c| c.to_digit(10).ok_or(c))
+        );
+    }
+
+    #[test]
+    fn bounds_checked_access() {
+        let mut grid = digits();
+
+        assert_eq!(None, grid.get(Point::new(-1, 0)));
+        assert_eq!(None, grid.get(Point::new(3, 0)));
+        assert_eq!(None, grid.get(Point::new(0, 3)));
+        assert!(grid.contains(Point::new(2, 2)));
+
+        *grid.get_mut(Point::ORIGIN).unwrap() = 0;
+        grid[Point::new(1, 0)] = 0;
+        assert_eq!(&[0, 0, 3], grid.row(0));
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = digits();
+
+        assert_eq!(
+            vec![&[4, 5, 6][..]],
+            grid.rows().skip(1).take(1).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![&2, &5, &8], grid.column(1).collect::<Vec<_>>());
+        assert_eq!(Some((Point::new(0, 1), &4)), grid.iter().nth(3));
+    }
+
+    #[test]
+    fn neighbourhoods() {
+        let grid = digits();
+        let values = |points: Vec<Point>| points.into_iter().map(|p| grid[p]).collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![2, 8, 4, 6],
+            values(grid.neighbours4(Point::new(1, 1)).collect())
+        );
+        assert_eq!(
+            vec![4, 2],
+            values(grid.neighbours4(Point::ORIGIN).collect())
+        );
+        assert_eq!(8, grid.neighbours8(Point::new(1, 1)).count());
+        assert_eq!(3, grid.neighbours8(Point::new(2, 2)).count());
+    }
+
+    #[test]
+    fn rays() {
+        let grid = digits();
+        let ray = |from, dir| grid.ray(from, dir).map(|(_, d)| *d).collect::<Vec<_>>();
+
+        assert_eq!(vec![4, 1], ray(Point::new(0, 2), Direction::Up));
+        assert_eq!(vec![5, 9], ray(Point::ORIGIN, Direction::DownRight));
+        assert_eq!(Vec::<u32>::new(), ray(Point::new(2, 1), Direction::Right));
+        assert_eq!(
+            vec![8, 7],
+            ray(Point::new(2, 2), Direction::Left)
+                .into_iter()
+                .take(2)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn points() {
+        let p = Point::new(2, -3);
+
+        assert_eq!(Point::new(1, -1), p.signum());
+        assert_eq!(Point::new(2, -4), p.step(Direction::Up));
+        assert_eq!(3, p.chebyshev(Point::ORIGIN));
+        assert_eq!(Point::new(3, -1), p - Point::new(-1, -2));
+    }
+}
@@ -0,0 +1,120 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Sub};
+
+/// A coordinate on a grid, `x` grows to the right and `y` grows downwards
+///
+/// Coordinates are signed so points can wander off the edge of a grid, or
+/// live on a plane without any edges at all.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Point { x, y }
+    }
+
+    /// The next point in `dir`
+    pub fn step(self, dir: Direction) -> Self {
+        self + dir.offset()
+    }
+
+    /// Both coordinates clamped to -1, 0 or 1
+    pub fn signum(self) -> Self {
+        Point::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Number of king moves between the points, so 1 for every neighbour
+    pub fn chebyshev(self, other: Point) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The points up, down, left and right of this one
+    pub fn neighbours4(self) -> [Point; 4] {
+        Direction::CARDINAL.map(|dir| self.step(dir))
+    }
+
+    /// The points around this one, diagonals included
+    pub fn neighbours8(self) -> [Point; 8] {
+        Direction::ALL.map(|dir| self.step(dir))
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// A direction to step or look in, `Up` is towards the first row
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+        Direction::UpLeft,
+        Direction::UpRight,
+        Direction::DownLeft,
+        Direction::DownRight,
+    ];
+
+    /// How far a single step in this direction moves
+    pub fn offset(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+            Direction::Right => Point::new(1, 0),
+            Direction::UpLeft => Point::new(-1, -1),
+            Direction::UpRight => Point::new(1, -1),
+            Direction::DownLeft => Point::new(-1, 1),
+            Direction::DownRight => Point::new(1, 1),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{Fetch, InputError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use ureq::Agent;
+
+const USER_AGENT: &str = "github.com/mattriverm/advent_of_code_2022";
+
+/// Fetches inputs over HTTP, authenticated with a session cookie
+///
+/// Requests are spaced at least `min_interval` apart, so fetching several
+/// days in a row does not hammer the server.
+pub struct HttpFetcher {
+    base_url: String,
+    session: Option<String>,
+    agent: Agent,
+    min_interval: Duration,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl HttpFetcher {
+    pub fn new(base_url: impl Into<String>, session: Option<String>) -> Self {
+        let agent = Agent::config_builder()
+            .timeout_global(Some(Duration::from_secs(30)))
+            .http_status_as_error(false)
+            .user_agent(USER_AGENT)
+            .build()
+            .into();
+
+        HttpFetcher {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session,
+            agent,
+            min_interval: Duration::from_secs(1),
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// Change how long to wait between two requests
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// URL of the input for `day`
+    pub fn url(&self, day: u8) -> String {
+        format!("{}/day/{}/input", self.base_url, day)
+    }
+
+    /// Sleep until `min_interval` has passed since the previous request
+    fn wait_for_turn(&self) {
+        let mut last_request = self.last_request.lock().unwrap();
+        if let Some(last) = *last_request {
+            let elapsed = last.elapsed();
+            if elapsed < self.min_interval {
+                thread::sleep(self.min_interval - elapsed);
+            }
+        }
+        *last_request = Some(Instant::now());
+    }
+}
+
+impl Fetch for HttpFetcher {
+    fn fetch(&self, day: u8) -> Result<String, InputError> {
+        let Some(session) = &self.session else {
+            return Err(InputError::MissingSession { day });
+        };
+        let url = self.url(day);
+        let transport = |source| InputError::Transport {
+            url: url.clone(),
+            source,
+        };
+
+        self.wait_for_turn();
+        let mut response = self
+            .agent
+            .get(&url)
+            .header("Cookie", &format!("session={session}"))
+            .call()
+            .map_err(transport)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(InputError::Status {
+                url,
+                status: status.as_u16(),
+            });
+        }
+
+        response.body_mut().read_to_string().map_err(transport)
+    }
+}
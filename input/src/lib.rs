@@ -0,0 +1,154 @@
+//! Puzzle inputs, read from a local cache and fetched over HTTP on a cache miss
+//!
+//! Inputs are cached as `day<N>.txt` in the cache directory. The defaults can
+//! be changed with these environment variables:
+//!
+//! - `AOC_CACHE_DIR`: where inputs are cached, `inputs/` in the workspace by default
+//! - `AOC_BASE_URL`: where inputs are fetched from, `https://adventofcode.com/2022` by default
+//! - `AOC_SESSION`: the session cookie to fetch inputs with
+
+mod http;
+
+pub use http::HttpFetcher;
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2022";
+pub const DEFAULT_CACHE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../inputs");
+
+/// Why the input for a day could not be provided
+#[derive(Debug, Error)]
+pub enum InputError {
+    #[error("could not access {}: {source}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+    #[error("the input for day {day} is not cached and AOC_SESSION is not set to fetch it")]
+    MissingSession { day: u8 },
+    #[error("fetching {url} failed with HTTP status {status}")]
+    Status { url: String, status: u16 },
+    #[error("fetching {url} failed: {source}")]
+    Transport { url: String, source: ureq::Error },
+}
+
+/// Somewhere to get a day's input from when it is not cached
+pub trait Fetch {
+    fn fetch(&self, day: u8) -> Result<String, InputError>;
+}
+
+impl<T> Fetch for T
+where
+    T: Fn(u8) -> Result<String, InputError>,
+{
+    fn fetch(&self, day: u8) -> Result<String, InputError> {
+        self(day)
+    }
+}
+
+/// Resolves puzzle inputs from the cache directory, fetching and storing them on a miss
+pub struct InputProvider<F = HttpFetcher> {
+    cache_dir: PathBuf,
+    fetcher: F,
+}
+
+impl InputProvider<HttpFetcher> {
+    /// A provider configured from the `AOC_*` environment variables
+    pub fn from_env() -> Self {
+        let cache_dir = std::env::var_os("AOC_CACHE_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_CACHE_DIR));
+        let base_url =
+            std::env::var("AOC_BASE_URL").unwrap_or_else(|_| String::from(DEFAULT_BASE_URL));
+        let session = std::env::var("AOC_SESSION").ok();
+
+        InputProvider::new(cache_dir, HttpFetcher::new(base_url, session))
+    }
+}
+
+impl<F: Fetch> InputProvider<F> {
+    pub fn new(cache_dir: impl Into<PathBuf>, fetcher: F) -> Self {
+        InputProvider {
+            cache_dir: cache_dir.into(),
+            fetcher,
+        }
+    }
+
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    /// Where the input for `day` is cached
+    pub fn cache_path(&self, day: u8) -> PathBuf {
+        self.cache_dir.join(format!("day{day}.txt"))
+    }
+
+    /// The input for `day`, from the cache if it is there and fetched otherwise
+    pub fn get(&self, day: u8) -> Result<String, InputError> {
+        let path = self.cache_path(day);
+        match fs::read_to_string(&path) {
+            Ok(input) => return Ok(input),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(source) => return Err(InputError::Io { path, source }),
+        }
+
+        let input = self.fetcher.fetch(day)?;
+        self.store(&path, &input)?;
+        Ok(input)
+    }
+
+    /// Write through a temporary file so an interrupted write never leaves a truncated input behind
+    fn store(&self, path: &Path, input: &str) -> Result<(), InputError> {
+        let io_err = |path: &Path| {
+            let path = path.to_path_buf();
+            move |source| InputError::Io { path, source }
+        };
+        fs::create_dir_all(&self.cache_dir).map_err(io_err(&self.cache_dir))?;
+        let partial = path.with_extension("txt.partial");
+        fs::write(&partial, input).map_err(io_err(&partial))?;
+        fs::rename(&partial, path).map_err(io_err(path))
+    }
+}
+
+/// The input for `day`, using a provider configured from the environment
+pub fn load(day: u8) -> Result<String, InputError> {
+    InputProvider::from_env().get(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn fetches_only_on_a_cache_miss() {
+        let cache = tempfile::tempdir().unwrap();
+        let fetches = Cell::new(0);
+        let provider = InputProvider::new(cache.path(), |day: u8| {
+            fetches.set(fetches.get() + 1);
+            Ok(format!("input for day {day}\n"))
+        });
+
+        assert_eq!("input for day 3\n", provider.get(3).unwrap());
+        assert_eq!("input for day 3\n", provider.get(3).unwrap());
+        assert_eq!(1, fetches.get());
+        assert_eq!(
+            "input for day 3\n",
+            fs::read_to_string(cache.path().join("day3.txt")).unwrap()
+        );
+    }
+
+    #[test]
+    fn fetch_errors_are_not_cached() {
+        let cache = tempfile::tempdir().unwrap();
+        let provider = InputProvider::new(cache.path(), |day: u8| {
+            Err(InputError::MissingSession { day })
+        });
+
+        assert!(matches!(
+            provider.get(5),
+            Err(InputError::MissingSession { day: 5 })
+        ));
+        assert!(!provider.cache_path(5).exists());
+    }
+}
@@ -0,0 +1,138 @@
+//! Exercise the HTTP fetcher against a mock server on localhost
+
+use input::{Fetch, HttpFetcher, InputError, InputProvider};
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// A request as seen by the mock server
+struct Request {
+    head: String,
+    received: Instant,
+}
+
+/// Answers one connection per canned response, then stops
+struct MockServer {
+    url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+    handle: JoinHandle<()>,
+}
+
+impl MockServer {
+    fn start(responses: Vec<(u16, &'static str)>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/2022", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&requests);
+
+        let handle = thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut head = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                    head.push_str(&line);
+                }
+                seen.lock().unwrap().push(Request {
+                    head,
+                    received: Instant::now(),
+                });
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        MockServer {
+            url,
+            requests,
+            handle,
+        }
+    }
+
+    /// Wait for every canned response to be served and return the requests
+    fn finish(self) -> Vec<Request> {
+        self.handle.join().unwrap();
+        Arc::try_unwrap(self.requests)
+            .ok()
+            .unwrap()
+            .into_inner()
+            .unwrap()
+    }
+}
+
+#[test]
+fn fetches_with_the_session_cookie_and_caches() {
+    let server = MockServer::start(vec![(200, "1000\n2000\n")]);
+    let cache = tempfile::tempdir().unwrap();
+    let fetcher = HttpFetcher::new(server.url.clone(), Some(String::from("abc123")));
+    let provider = InputProvider::new(cache.path(), fetcher);
+
+    assert_eq!("1000\n2000\n", provider.get(1).unwrap());
+    // Served from the cache, the mock server only answers once
+    assert_eq!("1000\n2000\n", provider.get(1).unwrap());
+
+    let requests = server.finish();
+    assert_eq!(1, requests.len());
+    assert!(requests[0]
+        .head
+        .starts_with("GET /2022/day/1/input HTTP/1.1"));
+    assert!(requests[0].head.contains("session=abc123"));
+    assert!(provider.cache_path(1).exists());
+}
+
+#[test]
+fn reports_http_status() {
+    let server = MockServer::start(vec![(404, "Not Found")]);
+    let cache = tempfile::tempdir().unwrap();
+    let fetcher = HttpFetcher::new(server.url.clone(), Some(String::from("abc123")));
+    let provider = InputProvider::new(cache.path(), fetcher);
+
+    let err = provider.get(25).unwrap_err();
+    assert!(matches!(err, InputError::Status { status: 404, .. }));
+    assert_eq!(
+        format!(
+            "fetching {}/day/25/input failed with HTTP status 404",
+            server.url
+        ),
+        err.to_string()
+    );
+    assert!(!provider.cache_path(25).exists());
+    server.finish();
+}
+
+#[test]
+fn needs_a_session_to_fetch() {
+    let fetcher = HttpFetcher::new("http://127.0.0.1:9", None);
+    assert!(matches!(
+        fetcher.fetch(4),
+        Err(InputError::MissingSession { day: 4 })
+    ));
+}
+
+#[test]
+fn spaces_out_requests() {
+    let server = MockServer::start(vec![(200, "a"), (200, "b")]);
+    let fetcher = HttpFetcher::new(server.url.clone(), Some(String::from("abc123")))
+        .with_min_interval(Duration::from_millis(300));
+
+    assert_eq!("a", fetcher.fetch(1).unwrap());
+    assert_eq!("b", fetcher.fetch(2).unwrap());
+
+    let requests = server.finish();
+    let gap = requests[1].received - requests[0].received;
+    assert!(gap >= Duration::from_millis(250), "requests {gap:?} apart");
+}